@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uranus::session::Session;
+
+const STATEMENTS: [(&str, &str); 6] = [
+    ("point_read", "SELECT * FROM analytics.user_sessions WHERE user_id = 3e3be9fb-5888-4b0e-8f22-287b7d90a32f AND session_id = 42"),
+    ("projection", "SELECT user_id, visits * 2 + 1 AS score, upper(device_type) FROM user_sessions AS s WHERE s.started_at >= TIMESTAMP '2024-10-21 00:00:00' ALLOW FILTERING"),
+    ("insert", "INSERT INTO user_sessions (user_id, session_id, device_type, visits) VALUES (1, 2, 'mobile', 3), (4, 5, 'laptop', 6) USING TTL 86400"),
+    ("conditional_update", "UPDATE user_sessions USING TIMESTAMP 1000 SET visits = visits + 1, device_type = 'tablet' WHERE user_id = 1 AND session_id = 2 IF visits = 3"),
+    ("delete", "DELETE device_type FROM user_sessions WHERE user_id = 1 AND session_id = 2"),
+    ("create_table", "CREATE TABLE IF NOT EXISTS analytics.user_sessions (user_id UUID, session_id INT, started_at TIMESTAMP, device_type TEXT, visits INT, PRIMARY KEY ((user_id, session_id), started_at)) WITH CLUSTERING ORDER BY (started_at DESC) AND compression = {'class': 'LZ4Compressor'} AND default_time_to_live = 86400"),
+];
+
+/// Generates INSERT statements with random keys and values from a fixed seed, so every run
+/// parses the same workload.
+fn synthetic_inserts(count: usize, seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let devices = ["mobile", "laptop", "tablet", "O''Brien''s TV"];
+
+    (0..count)
+        .map(|_| {
+            format!(
+                "INSERT INTO user_sessions (user_id, session_id, device_type, score) VALUES ({}, {}, '{}', {:.3})",
+                rng.gen::<u32>(),
+                rng.gen_range(0..1_000),
+                devices[rng.gen_range(0..devices.len())],
+                rng.gen_range(0.0..100.0),
+            )
+        })
+        .collect()
+}
+
+fn parse_statements(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, statement) in STATEMENTS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), statement, |b, statement| {
+            let mut session = Session::new();
+            b.iter(|| session.parse(black_box(statement)).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+fn parse_workload(c: &mut Criterion) {
+    let statements = synthetic_inserts(1_000, 7);
+
+    let mut group = c.benchmark_group("parse_workload");
+    group.throughput(Throughput::Elements(statements.len() as u64));
+
+    group.bench_function("inserts", |b| {
+        let mut session = Session::new();
+        b.iter(|| {
+            for statement in &statements {
+                session.parse(black_box(statement)).unwrap();
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse_statements, parse_workload);
+criterion_main!(benches);
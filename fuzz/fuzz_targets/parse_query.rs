@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uranus::session::Session;
+
+fuzz_target!(|source: &str| {
+    if let Ok(query) = Session::new().parse(source) {
+        let _ = Session::new().parse(&query.to_cql_string());
+    }
+});
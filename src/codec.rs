@@ -0,0 +1,2 @@
+pub mod row;
+mod error;
@@ -0,0 +1,18 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::codec::row::CodecError;
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecError::UnsupportedVersion(version) => write!(f, "the row format version {} is not supported", version),
+            CodecError::ColumnCountMismatch { expected, actual } => write!(f, "the row has {} values but the table has {} columns", actual, expected),
+            CodecError::TypeMismatch(column) => write!(f, "the value of the column {} doesn't match its type", column),
+            CodecError::Truncated => write!(f, "the row is truncated"),
+            CodecError::InvalidText(column) => write!(f, "the column {} contains invalid UTF-8", column),
+            CodecError::ComponentTooLarge(column) => write!(f, "the value of the partition key column {} is longer than {} bytes", column, u16::MAX),
+        }
+    }
+}
+
+impl Error for CodecError {}
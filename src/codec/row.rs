@@ -0,0 +1,302 @@
+use std::borrow::Cow;
+use crate::query_parser::query::{Column, ColumnType, Value};
+
+/// The first byte of every encoded row. Bump it whenever the layout below changes.
+pub const FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CodecError {
+    UnsupportedVersion(u8),
+    ColumnCountMismatch { expected: usize, actual: usize },
+    TypeMismatch(String),
+    Truncated,
+    InvalidText(String),
+    ComponentTooLarge(String),
+}
+
+/// Encodes a row laid out as the table columns into the format
+/// `version | varint column count | null bitmap | (varint length, payload)*`.
+/// Every payload is length-prefixed, so a reader with an older schema can skip the columns added
+/// after it.
+pub fn encode_row(columns: &[Column], row: &[Option<Value>]) -> Result<Vec<u8>, CodecError> {
+    if columns.len() != row.len() {
+        return Err(CodecError::ColumnCountMismatch { expected: columns.len(), actual: row.len() });
+    }
+
+    let mut bytes = vec![FORMAT_VERSION];
+    write_varint(&mut bytes, columns.len() as u64);
+
+    let mut bitmap = vec![0u8; columns.len().div_ceil(8)];
+    for (index, value) in row.iter().enumerate() {
+        if matches!(value, None | Some(Value::Null)) {
+            bitmap[index / 8] |= 1 << (index % 8);
+        }
+    }
+    bytes.extend(bitmap);
+
+    for (column, value) in columns.iter().zip(row) {
+        if let Some(value) = value.as_ref().filter(|value| **value != Value::Null) {
+            let payload = encode_value(column, value)?;
+            write_varint(&mut bytes, payload.len() as u64);
+            bytes.extend(payload);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Decodes a row written by `encode_row`. Columns are matched by position, so the schema may only
+/// change at its end: columns appended after the row was written decode as nulls and values past
+/// the last column of the schema are skipped. Dropping, renaming or retyping any other column
+/// makes the columns after it decode wrong.
+pub fn decode_row<'a>(columns: &[Column], bytes: &'a [u8]) -> Result<Vec<Option<Value<'a>>>, CodecError> {
+    let payloads = read_payloads(bytes, usize::MAX)?;
+    let mut row = Vec::with_capacity(columns.len());
+
+    for (column, payload) in columns.iter().zip(payloads) {
+        row.push(payload.map(|payload| decode_value(column, payload)).transpose()?);
+    }
+
+    row.resize_with(columns.len(), || None);
+    Ok(row)
+}
+
+/// Decodes only the columns at the given positions of the schema, in the order they are given.
+/// The payloads of other columns are skipped by their length without being decoded and reading
+/// stops after the last wanted column, which is what makes narrow reads of wide rows cheap.
+pub fn decode_columns<'a>(columns: &[Column], bytes: &'a [u8], projection: &[usize]) -> Result<Vec<Option<Value<'a>>>, CodecError> {
+    let end = projection.iter().max().map_or(0, |index| index + 1);
+    let payloads = read_payloads(bytes, end)?;
+
+    projection.iter()
+        .map(|&index| match (columns.get(index), payloads.get(index).copied().flatten()) {
+            (Some(column), Some(payload)) => decode_value(column, payload).map(Some),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Splits a row into the payloads of its first `end` columns, with `None` for nulls.
+fn read_payloads(bytes: &[u8], end: usize) -> Result<Vec<Option<&[u8]>>, CodecError> {
+    let mut reader = Reader { bytes, position: 0 };
+
+    let version = reader.take(1)?[0];
+    if version != FORMAT_VERSION {
+        return Err(CodecError::UnsupportedVersion(version));
+    }
+
+    let encoded_columns = reader.read_varint()? as usize;
+    let bitmap = reader.take(encoded_columns.div_ceil(8))?;
+    let mut payloads = Vec::with_capacity(encoded_columns.min(end));
+
+    for index in 0..encoded_columns.min(end) {
+        let is_null = bitmap[index / 8] & (1 << (index % 8)) != 0;
+
+        let payload = if is_null {
+            None
+        } else {
+            let length = reader.read_varint()? as usize;
+            Some(reader.take(length)?)
+        };
+
+        payloads.push(payload);
+    }
+
+    Ok(payloads)
+}
+
+pub(crate) fn encode_value(column: &Column, value: &Value) -> Result<Vec<u8>, CodecError> {
+    let mismatch = || CodecError::TypeMismatch(column.name.to_string());
+
+    let payload = match (&column.column_type, value) {
+        (ColumnType::Int, Value::Integer(value)) => {
+            let value = i32::try_from(*value).map_err(|_| mismatch())?;
+            zigzag(value as i64)
+        }
+        (ColumnType::Long | ColumnType::Counter, Value::Integer(value)) => zigzag(*value),
+        (ColumnType::Timestamp, Value::Timestamp(value) | Value::Integer(value)) => zigzag(*value),
+        (ColumnType::Float, Value::Float(value)) => (*value as f32).to_le_bytes().to_vec(),
+        (ColumnType::Double, Value::Float(value)) => value.to_le_bytes().to_vec(),
+        (ColumnType::Text, Value::String(value)) => value.as_bytes().to_vec(),
+        (ColumnType::Bool, Value::Bool(value)) => vec![*value as u8],
+        (ColumnType::Uuid, Value::Uuid(value)) => value.to_be_bytes().to_vec(),
+        _ => return Err(mismatch()),
+    };
+
+    Ok(payload)
+}
+
+fn decode_value<'a>(column: &Column, payload: &'a [u8]) -> Result<Value<'a>, CodecError> {
+    let mismatch = || CodecError::TypeMismatch(column.name.to_string());
+
+    let value = match column.column_type {
+        ColumnType::Int | ColumnType::Long | ColumnType::Counter => Value::Integer(unzigzag(payload).ok_or_else(mismatch)?),
+        ColumnType::Timestamp => Value::Timestamp(unzigzag(payload).ok_or_else(mismatch)?),
+        ColumnType::Float => Value::Float(f32::from_le_bytes(payload.try_into().map_err(|_| mismatch())?) as f64),
+        ColumnType::Double => Value::Float(f64::from_le_bytes(payload.try_into().map_err(|_| mismatch())?)),
+        ColumnType::Text => match std::str::from_utf8(payload) {
+            Ok(text) => Value::String(Cow::Borrowed(text)),
+            Err(_) => return Err(CodecError::InvalidText(column.name.to_string())),
+        },
+        ColumnType::Bool => match payload {
+            [0] => Value::Bool(false),
+            [1] => Value::Bool(true),
+            _ => return Err(mismatch()),
+        },
+        ColumnType::Uuid => Value::Uuid(u128::from_be_bytes(payload.try_into().map_err(|_| mismatch())?)),
+        ColumnType::Frozen(_) | ColumnType::List(_) | ColumnType::Set(_) | ColumnType::Map(..) => return Err(mismatch()),
+    };
+
+    Ok(value)
+}
+
+fn zigzag(value: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, ((value << 1) ^ (value >> 63)) as u64);
+    bytes
+}
+
+fn unzigzag(payload: &[u8]) -> Option<i64> {
+    let mut reader = Reader { bytes: payload, position: 0 };
+    let value = reader.read_varint().ok()?;
+
+    if reader.position != payload.len() {
+        return None;
+    }
+
+    Some((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], CodecError> {
+        let end = self.position.checked_add(length).ok_or(CodecError::Truncated)?;
+        let bytes = self.bytes.get(self.position..end).ok_or(CodecError::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, CodecError> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(CodecError::Truncated)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn column<'a>(name: &'a str, column_type: ColumnType<'a>) -> Column<'a> {
+        Column { name: name.into(), column_type }
+    }
+
+    fn user_sessions() -> Vec<Column<'static>> {
+        vec![
+            column("user_id", ColumnType::Uuid),
+            column("visits", ColumnType::Int),
+            column("bytes", ColumnType::Long),
+            column("started_at", ColumnType::Timestamp),
+            column("score", ColumnType::Double),
+            column("ratio", ColumnType::Float),
+            column("device_type", ColumnType::Text),
+            column("active", ColumnType::Bool),
+            column("country", ColumnType::Text),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let columns = user_sessions();
+
+        let row = vec![
+            Some(Value::Uuid(0x123e4567_e89b_12d3_a456_426614174000)),
+            Some(Value::Integer(-42)),
+            Some(Value::Integer(i64::MAX)),
+            Some(Value::Timestamp(1_729_468_800_000)),
+            Some(Value::Float(0.125)),
+            Some(Value::Float(0.5)),
+            Some(Value::String("mobile ✓".into())),
+            Some(Value::Bool(true)),
+            None,
+        ];
+
+        let bytes = encode_row(&columns, &row).unwrap();
+
+        assert_eq!(bytes[0], FORMAT_VERSION);
+        assert_eq!(decode_row(&columns, &bytes), Ok(row));
+    }
+
+    #[test]
+    fn test_schema_evolution() {
+        let columns = vec![column("user_id", ColumnType::Int), column("device_type", ColumnType::Text)];
+        let bytes = encode_row(&columns, &[Some(Value::Integer(1)), Some(Value::String("mobile".into()))]).unwrap();
+
+        let added = vec![column("user_id", ColumnType::Int), column("device_type", ColumnType::Text), column("country", ColumnType::Text)];
+        assert_eq!(decode_row(&added, &bytes), Ok(vec![Some(Value::Integer(1)), Some(Value::String("mobile".into())), None]));
+
+        let dropped = vec![column("user_id", ColumnType::Int)];
+        assert_eq!(decode_row(&dropped, &bytes), Ok(vec![Some(Value::Integer(1))]));
+    }
+
+    #[test]
+    fn test_decode_columns() {
+        let columns = user_sessions();
+
+        let row = vec![
+            Some(Value::Uuid(1)),
+            Some(Value::Integer(7)),
+            None,
+            Some(Value::Timestamp(1_729_468_800_000)),
+            Some(Value::Float(0.25)),
+            None,
+            Some(Value::String("mobile".into())),
+            Some(Value::Bool(false)),
+            Some(Value::String("NL".into())),
+        ];
+
+        let bytes = encode_row(&columns, &row).unwrap();
+
+        assert_eq!(decode_columns(&columns, &bytes, &[6, 1]), Ok(vec![Some(Value::String("mobile".into())), Some(Value::Integer(7))]));
+        assert_eq!(decode_columns(&columns, &bytes, &[2, 9]), Ok(vec![None, None]));
+        assert_eq!(decode_columns(&columns, &bytes, &[]), Ok(Vec::new()));
+
+        assert_eq!(decode_columns(&columns, &bytes[..bytes.len() - 1], &[0, 1]), Ok(vec![Some(Value::Uuid(1)), Some(Value::Integer(7))]));
+        assert_eq!(decode_columns(&columns, &bytes[..bytes.len() - 1], &[8]), Err(CodecError::Truncated));
+    }
+
+    #[test]
+    fn test_codec_errors() {
+        let columns = vec![column("visits", ColumnType::Int)];
+
+        assert_eq!(encode_row(&columns, &[]), Err(CodecError::ColumnCountMismatch { expected: 1, actual: 0 }));
+        assert_eq!(encode_row(&columns, &[Some(Value::String("1".into()))]), Err(CodecError::TypeMismatch("visits".to_string())));
+        assert_eq!(encode_row(&columns, &[Some(Value::Integer(i64::MAX))]), Err(CodecError::TypeMismatch("visits".to_string())));
+
+        let bytes = encode_row(&columns, &[Some(Value::Integer(300))]).unwrap();
+
+        assert_eq!(decode_row(&columns, &bytes[..bytes.len() - 1]), Err(CodecError::Truncated));
+        assert_eq!(decode_row(&columns, &[2, 0]), Err(CodecError::UnsupportedVersion(2)));
+    }
+}
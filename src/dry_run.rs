@@ -0,0 +1,178 @@
+use crate::error::Error;
+use crate::partitioner::{partition_key_hash, Token};
+use crate::query_parser::parser::parse_query;
+use crate::query_parser::query::{CreateTableQuery, DataManipulationQuery, Expression, Query};
+use crate::query_planner::planner::{explain, find_table, partition_keys, plan_transaction};
+pub use crate::query_planner::plan::AccessPath;
+
+/// What a statement would do if it ran: how its rows are found, which partitions it touches
+/// and what in it deserves a second look.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct DryRunReport {
+    /// `None` for statements that don't read or write rows, such as DDL.
+    pub access_path: Option<AccessPath>,
+    /// The tokens of the partitions the statement touches, once each. Empty when the partition
+    /// key isn't given as literals.
+    pub partitions: Vec<Token>,
+    pub warnings: Vec<String>,
+}
+
+/// Parses, validates and plans a statement against a snapshot of the schema without touching any
+/// data, so migrations and application queries can be checked in CI. Reads and writes must target
+/// one of the tables of the schema.
+pub fn execute_dry_run(source: &str, schema: &[CreateTableQuery]) -> Result<DryRunReport, Error> {
+    let query = parse_query(source)?;
+    let mut report = DryRunReport::default();
+
+    match &query {
+        Query::DataManipulationQuery(query) | Query::Explain(query) => {
+            let table = find_table(query, schema)?;
+            let plan = explain(query, table)?;
+
+            match plan.access_path {
+                AccessPath::FullScan => report.warnings.push("the statement reads every partition of the table".to_string()),
+                AccessPath::TokenRangeScan => report.warnings.push("the statement reads a range of partitions".to_string()),
+                AccessPath::PointRead | AccessPath::RangeScan => {}
+            }
+
+            for condition in &plan.filter_conditions {
+                report.warnings.push(format!("{} is checked against every row read", condition));
+            }
+
+            report.access_path = Some(plan.access_path);
+            add_partitions(&mut report, query, table)?;
+        }
+        Query::Transaction(statements) => {
+            let Some(first) = statements.first() else { return Ok(report) };
+            let table = find_table(first, schema)?;
+            plan_transaction(statements, table)?;
+
+            for statement in statements {
+                add_partitions(&mut report, statement, table)?;
+            }
+        }
+        Query::DataDefinitionQuery(_) | Query::SessionQuery(_) | Query::BulkQuery(_) | Query::MetaQuery(_) | Query::AdminQuery(_) => {}
+    }
+
+    Ok(report)
+}
+
+fn add_partitions(report: &mut DryRunReport, query: &DataManipulationQuery, table: &CreateTableQuery) -> Result<(), Error> {
+    for key in partition_keys(query, table) {
+        let values = key.iter()
+            .map(|expression| match expression {
+                Expression::Literal(value) => Some(value.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match values {
+            Some(values) => {
+                let token = partition_key_hash(table, &values)?;
+
+                if !report.partitions.contains(&token) {
+                    report.partitions.push(token);
+                }
+            }
+            None => {
+                let warning = "the partition is known only once the statement's parameters are bound".to_string();
+
+                if !report.warnings.contains(&warning) {
+                    report.warnings.push(warning);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::query::{DataDefinitionQuery, Value};
+    use crate::query_planner::plan::PlanningError;
+
+    fn schema() -> Vec<CreateTableQuery<'static>> {
+        let query = parse_query("CREATE TABLE analytics.user_sessions (user_id INT, timestamp TIMESTAMP, device_type TEXT, PRIMARY KEY ((user_id), timestamp))").unwrap();
+        let Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table)) = query else { panic!("expected a create table query") };
+
+        vec![table]
+    }
+
+    #[test]
+    fn test_execute_dry_run() {
+        let schema = schema();
+        let token = |user_id| partition_key_hash(&schema[0], &[Value::Integer(user_id)]).unwrap();
+
+        let params = vec![
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1",
+                DryRunReport { access_path: Some(AccessPath::RangeScan), partitions: vec![token(1)], warnings: vec![] },
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE 1 = user_id AND timestamp > 0",
+                DryRunReport { access_path: Some(AccessPath::RangeScan), partitions: vec![token(1)], warnings: vec![] },
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = ? AND timestamp < :until",
+                DryRunReport {
+                    access_path: Some(AccessPath::RangeScan),
+                    partitions: vec![],
+                    warnings: vec!["the partition is known only once the statement's parameters are bound".to_string()],
+                },
+            ),
+            (
+                "UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = :user_id AND timestamp = :timestamp",
+                DryRunReport {
+                    access_path: Some(AccessPath::PointRead),
+                    partitions: vec![],
+                    warnings: vec!["the partition is known only once the statement's parameters are bound".to_string()],
+                },
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE device_type = 'PHONE' ALLOW FILTERING",
+                DryRunReport {
+                    access_path: Some(AccessPath::FullScan),
+                    partitions: vec![],
+                    warnings: vec!["the statement reads every partition of the table".to_string(), "device_type = 'PHONE' is checked against every row read".to_string()],
+                },
+            ),
+            (
+                "INSERT INTO analytics.user_sessions (user_id, timestamp) VALUES (1, 0), (2, 0), (1, 1)",
+                DryRunReport { access_path: Some(AccessPath::PointRead), partitions: vec![token(1), token(2)], warnings: vec![] },
+            ),
+            (
+                "INSERT INTO user_sessions (user_id, timestamp) VALUES (?, ?)",
+                DryRunReport {
+                    access_path: Some(AccessPath::PointRead),
+                    partitions: vec![],
+                    warnings: vec!["the partition is known only once the statement's parameters are bound".to_string()],
+                },
+            ),
+            (
+                "BEGIN TRANSACTION INSERT INTO user_sessions (user_id, timestamp) VALUES (3, 0); DELETE FROM user_sessions WHERE user_id = 3 AND timestamp = 1; COMMIT",
+                DryRunReport { access_path: None, partitions: vec![token(3)], warnings: vec![] },
+            ),
+            (
+                "DROP TABLE user_sessions",
+                DryRunReport::default(),
+            ),
+        ];
+
+        for (source, expected) in params {
+            assert_eq!(execute_dry_run(source, &schema).unwrap(), expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_execute_dry_run_errors() {
+        let schema = schema();
+
+        assert!(matches!(execute_dry_run("SELECT * FROM users WHERE user_id = 1", &schema), Err(Error::Planning(PlanningError::TableNotInSchema(table))) if table == "users"));
+        assert!(matches!(execute_dry_run("SELECT * FROM other.user_sessions", &schema), Err(Error::Planning(PlanningError::TableNotInSchema(table))) if table == "other.user_sessions"));
+        assert!(matches!(execute_dry_run("SELECT * FROM user_sessions WHERE device_type = 'PHONE'", &schema), Err(Error::Planning(PlanningError::FilteringRequired))));
+        assert!(matches!(execute_dry_run("SELECT * FROM user_sessions WHERE missing = 1", &schema), Err(Error::Planning(PlanningError::UnknownColumn(_)))));
+        assert!(matches!(execute_dry_run("SELEC * FROM user_sessions", &schema), Err(Error::Parse(_))));
+    }
+}
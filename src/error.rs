@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter};
+use crate::codec::row::CodecError;
+use crate::query_parser::query::QueryParsingError;
+use crate::query_planner::plan::PlanningError;
+
+/// Any error a statement can fail with. Each kind maps to an error code of the native protocol,
+/// so a server can report it to clients without inspecting the message.
+#[derive(Debug)]
+pub enum Error {
+    Parse(QueryParsingError),
+    Planning(PlanningError),
+    Codec(CodecError),
+    Io(std::io::Error),
+}
+
+impl Error {
+    pub const SERVER_ERROR: u16 = 0x0000;
+    pub const SYNTAX_ERROR: u16 = 0x2000;
+    pub const INVALID: u16 = 0x2200;
+
+    pub fn code(&self) -> u16 {
+        match self {
+            Error::Parse(QueryParsingError::UnsupportedRequest(_) | QueryParsingError::QuerySyntaxError(_)) => Self::SYNTAX_ERROR,
+            Error::Parse(QueryParsingError::InvalidQuery(_) | QueryParsingError::InvalidParameters(_)) => Self::INVALID,
+            Error::Planning(_) | Error::Codec(_) => Self::INVALID,
+            Error::Io(_) => Self::SERVER_ERROR,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Planning(error) => write!(f, "{}", error),
+            Error::Codec(error) => write!(f, "{}", error),
+            Error::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(error) => Some(error),
+            Error::Planning(error) => Some(error),
+            Error::Codec(error) => Some(error),
+            Error::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<QueryParsingError> for Error {
+    fn from(error: QueryParsingError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<PlanningError> for Error {
+    fn from(error: PlanningError) -> Self {
+        Error::Planning(error)
+    }
+}
+
+impl From<CodecError> for Error {
+    fn from(error: CodecError) -> Self {
+        Error::Codec(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
@@ -0,0 +1,13 @@
+pub mod query_parser;
+mod query_planner;
+pub mod codec;
+pub mod partitioner;
+pub mod session;
+pub mod dry_run;
+pub mod migration;
+pub mod result;
+mod error;
+
+pub use error::Error;
+pub use query_planner::plan::PlanningError;
+pub use codec::row::CodecError;
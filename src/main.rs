@@ -1,5 +1,72 @@
-mod query_parser;
+use std::path::Path;
+use std::process::ExitCode;
+use uranus::migration::migrator::{dry_run, load_migrations};
+use uranus::session::Session;
 
-fn main() {
-    println!("Hello, world!");
+const USAGE: &str = "usage: uranus migrate --dry-run <directory>
+       uranus -e <statements> [--continue-on-error]
+       uranus -f <file> [--continue-on-error]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["migrate", "--dry-run", directory] => migrate_dry_run(Path::new(directory)),
+        ["-e", statements, options @ ..] if is_run_options(options) => run(statements, options.is_empty()),
+        ["-f", file, options @ ..] if is_run_options(options) => match std::fs::read_to_string(file) {
+            Ok(script) => run(&script, options.is_empty()),
+            Err(error) => {
+                eprintln!("cannot read {}: {}", file, error);
+                ExitCode::FAILURE
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn is_run_options(options: &[&str]) -> bool {
+    matches!(options, [] | ["--continue-on-error"])
+}
+
+fn migrate_dry_run(directory: &Path) -> ExitCode {
+    let result = load_migrations(directory).and_then(|migrations| {
+        dry_run(&migrations).map(|statements| (migrations.len(), statements))
+    });
+
+    match result {
+        Ok((migrations, statements)) => {
+            println!("{} migrations with {} statements are valid", migrations, statements);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints each statement of the script in its normalized form. The exit status is a failure
+/// if any statement is invalid and, unless errors are skipped, nothing after it is printed.
+fn run(script: &str, stop_on_error: bool) -> ExitCode {
+    let mut session = Session::new();
+    let mut status = ExitCode::SUCCESS;
+
+    for result in session.parse_script(script) {
+        match result {
+            Ok(query) => println!("{};", query),
+            Err(error) => {
+                eprintln!("{}", error);
+                status = ExitCode::FAILURE;
+
+                if stop_on_error {
+                    break;
+                }
+            }
+        }
+    }
+
+    status
 }
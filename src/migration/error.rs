@@ -0,0 +1,16 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::migration::migrator::MigrationError;
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::Io(error) => write!(f, "cannot read the migrations: {}", error),
+            MigrationError::InvalidFileName(file) => write!(f, "the migration {} must be named <version>_<description>.cql", file),
+            MigrationError::DuplicateVersion(version) => write!(f, "more than one migration has the version {}", version),
+            MigrationError::InvalidStatement { version, error } => write!(f, "the migration {} is invalid: {}", version, error),
+        }
+    }
+}
+
+impl Error for MigrationError {}
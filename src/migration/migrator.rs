@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+use crate::query_parser::parser::parse_script;
+use crate::query_parser::query::QueryParsingError;
+
+/// A script named `<version>_<description>.cql`. Migrations apply in the order of their versions.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Migration {
+    pub version: u64,
+    pub description: String,
+    pub source: String,
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Io(std::io::Error),
+    InvalidFileName(String),
+    DuplicateVersion(u64),
+    InvalidStatement { version: u64, error: QueryParsingError },
+}
+
+/// Reads the `.cql` files of a directory ordered by version. Files with other extensions are ignored.
+pub fn load_migrations(directory: &Path) -> Result<Vec<Migration>, MigrationError> {
+    let mut migrations = Vec::new();
+
+    for entry in fs::read_dir(directory).map_err(MigrationError::Io)? {
+        let path = entry.map_err(MigrationError::Io)?.path();
+
+        if path.extension().is_none_or(|extension| extension != "cql") {
+            continue;
+        }
+
+        let file_name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+
+        let (version, description) = parse_file_name(file_name)
+            .ok_or_else(|| MigrationError::InvalidFileName(path.display().to_string()))?;
+
+        let source = fs::read_to_string(&path).map_err(MigrationError::Io)?;
+        migrations.push(Migration { version, description: description.to_string(), source });
+    }
+
+    migrations.sort_by_key(|migration| migration.version);
+
+    if let Some(pair) = migrations.windows(2).find(|pair| pair[0].version == pair[1].version) {
+        return Err(MigrationError::DuplicateVersion(pair[0].version));
+    }
+
+    Ok(migrations)
+}
+
+/// Parses every statement of every migration without applying anything and returns the
+/// number of statements. Stops at the first migration that doesn't parse.
+pub fn dry_run(migrations: &[Migration]) -> Result<usize, MigrationError> {
+    let mut statements = 0;
+
+    for migration in migrations {
+        match parse_script(&migration.source) {
+            Ok(queries) => statements += queries.len(),
+            Err(error) => return Err(MigrationError::InvalidStatement { version: migration.version, error }),
+        }
+    }
+
+    Ok(statements)
+}
+
+fn parse_file_name(file_name: &str) -> Option<(u64, &str)> {
+    let (version, description) = file_name.split_once('_')?;
+
+    if version.is_empty() || !version.bytes().all(|byte| byte.is_ascii_digit()) || description.is_empty() {
+        return None;
+    }
+
+    Some((version.parse().ok()?, description))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_name() {
+        let params = vec![
+            ("001_create_keyspace", Some((1, "create_keyspace"))),
+            ("20241021_add_users", Some((20241021, "add_users"))),
+            ("create_keyspace", None),
+            ("001_", None),
+            ("v1_create_keyspace", None),
+            ("+1_create_keyspace", None),
+        ];
+
+        for (file_name, expected_result) in params {
+            assert_eq!(parse_file_name(file_name), expected_result, "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn test_load_and_dry_run() {
+        let directory = std::env::temp_dir().join(format!("uranus-migrations-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        fs::write(directory.join("002_create_users.cql"), "CREATE TABLE analytics.users (user_id UUID PRIMARY KEY);\nCREATE TABLE analytics.visits (user_id UUID PRIMARY KEY);").unwrap();
+        fs::write(directory.join("001_create_keyspace.cql"), "CREATE KEYSPACE analytics;").unwrap();
+        fs::write(directory.join("README.md"), "not a migration").unwrap();
+
+        let migrations = load_migrations(&directory).unwrap();
+        let versions: Vec<u64> = migrations.iter().map(|migration| migration.version).collect();
+
+        assert_eq!(versions, vec![1, 2]);
+        assert_eq!(dry_run(&migrations).unwrap(), 3);
+
+        fs::write(directory.join("003_broken.cql"), "CREATE TABLE users (user_id UUID PRIMARY);").unwrap();
+        let result = dry_run(&load_migrations(&directory).unwrap());
+
+        fs::write(directory.join("3_duplicate.cql"), "").unwrap();
+        let duplicate = load_migrations(&directory);
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(result, Err(MigrationError::InvalidStatement { version: 3, .. })));
+        assert!(matches!(duplicate, Err(MigrationError::DuplicateVersion(3))));
+    }
+}
@@ -0,0 +1,36 @@
+mod murmur3;
+mod ring;
+
+use crate::error::Error;
+use crate::query_parser::query::{CreateTableQuery, Value};
+use crate::query_planner::plan::PlanningError;
+pub use ring::{Token, TokenRing};
+
+/// Returns the token of the partition that the values of the table's partition key columns, given
+/// in their declared order, hash to. A client can route a request straight to the node owning it.
+pub fn partition_key_hash(table: &CreateTableQuery, values: &[Value]) -> Result<Token, Error> {
+    let partition_key = table.primary_key.partition_key.iter()
+        .map(|name| table.columns.iter().find(|column| column.name == *name).ok_or_else(|| PlanningError::UnknownColumn(name.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ring::partition_token(&partition_key, values)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::parser::parse_query;
+    use crate::query_parser::query::{DataDefinitionQuery, Query};
+
+    #[test]
+    fn test_partition_key_hash() {
+        let query = parse_query("CREATE TABLE user_sessions (user_id TEXT, session_id INT, timestamp TIMESTAMP, PRIMARY KEY ((user_id, session_id), timestamp))").unwrap();
+        let Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table)) = query else { panic!("expected a create table query") };
+
+        let token = partition_key_hash(&table, &[Value::String("hello".into()), Value::Integer(1)]).unwrap();
+        assert_eq!(partition_key_hash(&table, &[Value::String("hello".into()), Value::Integer(1)]).unwrap(), token);
+        assert_ne!(partition_key_hash(&table, &[Value::String("hello".into()), Value::Integer(2)]).unwrap(), token);
+
+        assert!(matches!(partition_key_hash(&table, &[Value::String("hello".into())]), Err(Error::Codec(_))));
+    }
+}
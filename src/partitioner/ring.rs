@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Unbounded};
+use crate::codec::row::{encode_value, CodecError};
+use crate::partitioner::murmur3::murmur3_x64_128;
+use crate::query_parser::query::{Column, Value};
+
+pub type Token = i64;
+
+/// Hashes a partition key onto the ring. A single-column key hashes its encoded value and
+/// a composite key hashes every component as `u16 length | bytes | 0`, so a component of a
+/// composite key can't be longer than `u16::MAX` bytes.
+pub(crate) fn partition_token(partition_key: &[&Column], values: &[Value]) -> Result<Token, CodecError> {
+    if partition_key.len() != values.len() {
+        return Err(CodecError::ColumnCountMismatch { expected: partition_key.len(), actual: values.len() });
+    }
+
+    let mut bytes = Vec::new();
+
+    for (column, value) in partition_key.iter().zip(values) {
+        let component = encode_value(column, value)?;
+
+        if partition_key.len() == 1 {
+            bytes = component;
+        } else {
+            let length = u16::try_from(component.len()).map_err(|_| CodecError::ComponentTooLarge(column.name.to_string()))?;
+            bytes.extend(length.to_be_bytes());
+            bytes.extend(component);
+            bytes.push(0);
+        }
+    }
+
+    Ok(token(&bytes))
+}
+
+/// Uses the first half of the Murmur3 digest as the token. `i64::MIN` is reserved as the
+/// ring's minimum token, so it is folded onto `i64::MAX`.
+pub(crate) fn token(bytes: &[u8]) -> Token {
+    match murmur3_x64_128(bytes, 0).0 as i64 {
+        i64::MIN => i64::MAX,
+        token => token,
+    }
+}
+
+/// Maps tokens to the nodes that own them. A node owns the range from the previous token on the
+/// ring, exclusive, up to its own token, inclusive; the lowest token also owns the wrap-around range.
+#[derive(Debug, Default)]
+pub struct TokenRing {
+    tokens: BTreeMap<Token, String>,
+}
+
+impl TokenRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a ring where the nodes split the token space into equal ranges.
+    pub fn balanced(nodes: &[&str]) -> Self {
+        let mut ring = Self::new();
+        let step = (u64::MAX / nodes.len().max(1) as u64) as i128;
+
+        for (index, node) in nodes.iter().enumerate() {
+            let token = i64::MIN as i128 + step * (index as i128 + 1);
+            ring.add_node(node, &[token as Token]);
+        }
+
+        ring
+    }
+
+    pub fn add_node(&mut self, node: &str, tokens: &[Token]) {
+        for token in tokens {
+            self.tokens.insert(*token, node.to_string());
+        }
+    }
+
+    pub fn remove_node(&mut self, node: &str) {
+        self.tokens.retain(|_, owner| owner != node);
+    }
+
+    pub fn owner(&self, token: Token) -> Option<&str> {
+        self.walk(token).next()
+    }
+
+    /// Returns the owner of the token followed by the next distinct nodes clockwise on the ring.
+    pub fn replicas(&self, token: Token, replication_factor: usize) -> Vec<&str> {
+        let mut replicas: Vec<&str> = Vec::new();
+
+        for node in self.walk(token).take(self.tokens.len()) {
+            if replicas.len() == replication_factor {
+                break;
+            }
+            if !replicas.contains(&node) {
+                replicas.push(node);
+            }
+        }
+
+        replicas
+    }
+
+    /// Returns the `(start, end]` token ranges owned by the node. The wrap-around range is
+    /// split at the end of the token space.
+    pub fn ranges(&self, node: &str) -> Vec<(Token, Token)> {
+        let mut ranges = Vec::new();
+        let last = match self.tokens.keys().next_back() {
+            Some(last) => *last,
+            None => return ranges,
+        };
+        let mut previous = None;
+
+        for (token, owner) in self.tokens.iter() {
+            if owner == node {
+                match previous {
+                    Some(previous) => ranges.push((previous, *token)),
+                    None => {
+                        ranges.push((i64::MIN, *token));
+                        if last != *token {
+                            ranges.push((last, i64::MAX));
+                        }
+                    }
+                }
+            }
+            previous = Some(*token);
+        }
+
+        ranges
+    }
+
+    fn walk(&self, token: Token) -> impl Iterator<Item = &str> {
+        let after = self.tokens.range(token..);
+        let wrapped = self.tokens.range((Unbounded, Excluded(token)));
+        after.chain(wrapped).map(|(_, node)| node.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::query::ColumnType;
+
+    fn ring() -> TokenRing {
+        let mut ring = TokenRing::new();
+        ring.add_node("node1", &[-100, 200]);
+        ring.add_node("node2", &[0]);
+        ring.add_node("node3", &[100]);
+        ring
+    }
+
+    #[test]
+    fn test_owner() {
+        let ring = ring();
+
+        let params = vec![(-500, "node1"), (-100, "node1"), (-99, "node2"), (0, "node2"), (50, "node3"), (150, "node1"), (201, "node1"), (i64::MAX, "node1")];
+
+        for (token, expected_result) in params {
+            assert_eq!(ring.owner(token), Some(expected_result), "{}", token);
+        }
+
+        assert_eq!(TokenRing::new().owner(0), None);
+    }
+
+    #[test]
+    fn test_replicas() {
+        let ring = ring();
+
+        assert_eq!(ring.replicas(50, 2), vec!["node3", "node1"]);
+        assert_eq!(ring.replicas(-50, 3), vec!["node2", "node3", "node1"]);
+        assert_eq!(ring.replicas(-50, 5), vec!["node2", "node3", "node1"]);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut ring = ring();
+
+        assert_eq!(ring.ranges("node1"), vec![(i64::MIN, -100), (200, i64::MAX), (100, 200)]);
+        assert_eq!(ring.ranges("node2"), vec![(-100, 0)]);
+
+        ring.remove_node("node1");
+
+        assert_eq!(ring.ranges("node1"), vec![]);
+        assert_eq!(ring.owner(150), Some("node2"));
+    }
+
+    #[test]
+    fn test_balanced() {
+        let ring = TokenRing::balanced(&["node1", "node2", "node3", "node4"]);
+        let mut counts = [0; 4];
+
+        for user_id in 0..4_000 {
+            let token = partition_token(&[&Column { name: "user_id".into(), column_type: ColumnType::Long }], &[Value::Integer(user_id)]).unwrap();
+            let node = ring.owner(token).unwrap();
+            counts[node[4..].parse::<usize>().unwrap() - 1] += 1;
+        }
+
+        assert!(counts.iter().all(|count| (800..1_200).contains(count)), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_partition_token() {
+        let user_id = Column { name: "user_id".into(), column_type: ColumnType::Text };
+        let session_id = Column { name: "session_id".into(), column_type: ColumnType::Int };
+        let hello = Value::String("hello".into());
+
+        assert_eq!(partition_token(&[&user_id], std::slice::from_ref(&hello)), Ok(0xcbd8_a7b3_41bd_9b02_u64 as i64));
+
+        let composite_key = [&user_id, &session_id];
+        let token = partition_token(&composite_key, &[Value::String("hello".into()), Value::Integer(1)]).unwrap();
+
+        assert_eq!(partition_token(&composite_key, &[Value::String("hello".into()), Value::Integer(1)]), Ok(token));
+        assert_ne!(partition_token(&composite_key, &[Value::String("hello".into()), Value::Integer(2)]), Ok(token));
+        assert_eq!(partition_token(&composite_key, &[hello]), Err(CodecError::ColumnCountMismatch { expected: 2, actual: 1 }));
+
+        let long = Value::String("x".repeat(usize::from(u16::MAX) + 1).into());
+        assert_eq!(partition_token(&composite_key, &[long, Value::Integer(1)]), Err(CodecError::ComponentTooLarge("user_id".to_string())));
+        assert!(partition_token(&[&user_id], &[Value::String("x".repeat(usize::from(u16::MAX) + 1).into())]).is_ok());
+    }
+}
@@ -1,9 +1,21 @@
 mod dml_parser;
 mod ddl_parser;
 mod common_parser;
-mod parser;
-mod query;
+mod expression_parser;
+mod session_parser;
+mod bulk_parser;
+mod meta_parser;
+mod admin_parser;
+pub(crate) mod parser;
+pub mod query;
 mod error;
-mod keyword;
-mod builder;
+pub(crate) mod keyword;
+pub mod builder;
+pub mod binder;
+pub mod dialect;
+pub mod fingerprint;
+mod formatter;
+pub(crate) mod timestamp;
+#[cfg(any(test, feature = "test-util"))]
+pub mod arbitrary;
 
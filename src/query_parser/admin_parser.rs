@@ -0,0 +1,56 @@
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::common_parser::{expect_end, parse_keyword, parse_table_name};
+use crate::query_parser::keyword::{COMPACT, FLUSH};
+use crate::query_parser::query::{AdminQuery, Query, QueryParsingError, TableMaintenanceQuery};
+
+pub(crate) fn parse_compact_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = parse_table_maintenance_query(source, COMPACT)?;
+    Ok(Query::AdminQuery(AdminQuery::Compact(query)))
+}
+
+pub(crate) fn parse_flush_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = parse_table_maintenance_query(source, FLUSH)?;
+    Ok(Query::AdminQuery(AdminQuery::Flush(query)))
+}
+
+fn parse_table_maintenance_query<'a>(source: &'a str, keyword: &'static str) -> Result<TableMaintenanceQuery<'a>, QueryParsingError> {
+    let query = match parse_keyword(keyword)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, &format!("expected '{}' statement", keyword), &[keyword]))
+    };
+
+    let (query, (keyspace, table)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse table name", &["table name"]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(TableMaintenanceQuery { keyspace, table })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::query_parser::parser::parse_query;
+    use super::*;
+
+    #[test]
+    fn test_parse_admin_queries() {
+        let params = vec![
+            (
+                "COMPACT analytics.user_sessions",
+                AdminQuery::Compact(TableMaintenanceQuery { keyspace: Some("analytics".into()), table: "user_sessions".into() }),
+            ),
+            (
+                "flush user_sessions",
+                AdminQuery::Flush(TableMaintenanceQuery { keyspace: None, table: "user_sessions".into() }),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(Query::AdminQuery(expected_result)));
+        }
+
+        assert!(parse_query("FLUSH").is_err());
+    }
+}
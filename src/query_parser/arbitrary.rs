@@ -0,0 +1,249 @@
+use std::borrow::Cow;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::strategy::LazyJust;
+use proptest::prelude::*;
+use crate::query_parser::keyword::is_reserved;
+use crate::query_parser::query::{ArithmeticOperator, Condition, Consistency, CreateKeyspaceQuery, DataDefinitionQuery, DataManipulationQuery, DeleteQuery, DropTableQuery, Expression, IfClause, InsertQuery, Operator, Query, ReplicationOptions, SelectQuery, Selector, SessionQuery, TracingQuery, UpdateQuery, UseQuery, Value};
+
+/// Words an unquoted identifier can't start with, since statements match their keywords by prefix.
+const RESERVED: [&str; 37] = [
+    "allow", "alter", "and", "as", "by", "create", "delete", "desc", "distinct", "drop", "exists", "false",
+    "filtering", "from", "frozen", "if", "insert", "into", "is", "json", "key", "keyspace", "not", "null",
+    "on", "off", "primary", "select", "set", "static", "table", "timestamp", "token", "true", "ttl",
+    "update", "using",
+];
+
+/// Generates statements that the parser produces, so `parse(format(query)) == query` holds for
+/// every generated query. Covers the DML statements, their expressions and conditions, and a few
+/// DDL and session statements.
+pub fn arb_query() -> impl Strategy<Value = Query<'static>> {
+    prop_oneof![
+        4 => arb_dml().prop_map(Query::DataManipulationQuery),
+        1 => arb_dml().prop_map(Query::Explain),
+        1 => (arb_identifier(), any::<bool>(), option::of(arb_replication()), option::of(any::<bool>())).prop_map(|(keyspace, if_not_exists, replication, durable_writes)| {
+            Query::DataDefinitionQuery(DataDefinitionQuery::CreateKeyspace(CreateKeyspaceQuery { keyspace, if_not_exists, replication, durable_writes }))
+        }),
+        1 => (option::of(arb_identifier()), arb_identifier(), any::<bool>()).prop_map(|(keyspace, table, if_exists)| {
+            Query::DataDefinitionQuery(DataDefinitionQuery::DropTable(DropTableQuery { keyspace, table, if_exists }))
+        }),
+        1 => arb_identifier().prop_map(|keyspace| Query::SessionQuery(SessionQuery::Use(UseQuery { keyspace }))),
+        1 => any::<bool>().prop_map(|enabled| Query::SessionQuery(SessionQuery::Tracing(TracingQuery { enabled }))),
+    ]
+}
+
+pub fn arb_dml() -> impl Strategy<Value = DataManipulationQuery<'static>> {
+    prop_oneof![
+        arb_select().prop_map(DataManipulationQuery::Select),
+        arb_insert().prop_map(DataManipulationQuery::Insert),
+        arb_update().prop_map(DataManipulationQuery::Update),
+        arb_delete().prop_map(DataManipulationQuery::Delete),
+    ]
+}
+
+pub fn arb_select() -> impl Strategy<Value = SelectQuery<'static>> {
+    let selector = (arb_expression(), option::of(arb_identifier())).prop_map(|(expression, alias)| Selector { expression, alias });
+
+    (
+        vec(selector, 0..4),
+        option::of(arb_identifier()),
+        arb_identifier(),
+        option::of(arb_identifier()),
+        vec(arb_condition(), 0..4),
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        option::of(arb_consistency()),
+        option::of(1..u32::MAX),
+    ).prop_map(|(columns, keyspace, table, table_alias, conditions, allow_filtering, json, distinct, consistency, per_partition_limit)| {
+        SelectQuery { columns, keyspace, table, table_alias, conditions, per_partition_limit, allow_filtering, json, distinct, consistency, serial_consistency: None }
+    })
+}
+
+pub fn arb_insert() -> impl Strategy<Value = InsertQuery<'static>> {
+    let values = (1..4usize).prop_flat_map(|width| (vec(arb_identifier(), width), vec(vec(arb_expression(), width), 1..3)));
+
+    (
+        values,
+        option::of(arb_identifier()),
+        arb_identifier(),
+        option::of(any::<u32>()),
+        option::of(0..i64::MAX),
+        any::<bool>(),
+        option::of(arb_consistency()),
+        option::of(arb_serial_consistency()),
+    ).prop_map(|((columns, values), keyspace, table, ttl, timestamp, if_not_exists, consistency, serial_consistency)| {
+        InsertQuery { columns, values, json: None, keyspace, table, ttl, timestamp, if_not_exists, consistency, serial_consistency }
+    })
+}
+
+pub fn arb_update() -> impl Strategy<Value = UpdateQuery<'static>> {
+    (
+        option::of(arb_identifier()),
+        arb_identifier(),
+        vec((arb_identifier(), arb_expression()), 1..4),
+        vec(arb_condition(), 1..4),
+        option::of(any::<u32>()),
+        option::of(0..i64::MAX),
+        option::of(arb_if_clause()),
+        option::of(arb_consistency()),
+        option::of(arb_serial_consistency()),
+    ).prop_map(|(keyspace, table, values, conditions, ttl, timestamp, if_clause, consistency, serial_consistency)| {
+        UpdateQuery { keyspace, table, values, conditions, ttl, timestamp, if_clause, consistency, serial_consistency }
+    })
+}
+
+pub fn arb_delete() -> impl Strategy<Value = DeleteQuery<'static>> {
+    (
+        vec(arb_identifier(), 0..3),
+        option::of(arb_identifier()),
+        arb_identifier(),
+        vec(arb_condition(), 1..4),
+        option::of(0..i64::MAX),
+        option::of(arb_if_clause()),
+        option::of(arb_consistency()),
+        option::of(arb_serial_consistency()),
+    ).prop_map(|(columns, keyspace, table, conditions, timestamp, if_clause, consistency, serial_consistency)| {
+        DeleteQuery { columns, keyspace, table, conditions, timestamp, if_clause, consistency, serial_consistency }
+    })
+}
+
+pub fn arb_consistency() -> impl Strategy<Value = Consistency> {
+    prop_oneof![
+        Just(Consistency::Any),
+        Just(Consistency::One),
+        Just(Consistency::Two),
+        Just(Consistency::Three),
+        Just(Consistency::Quorum),
+        Just(Consistency::All),
+        Just(Consistency::LocalQuorum),
+        Just(Consistency::EachQuorum),
+        Just(Consistency::Serial),
+        Just(Consistency::LocalSerial),
+        Just(Consistency::LocalOne),
+    ]
+}
+
+pub fn arb_serial_consistency() -> impl Strategy<Value = Consistency> {
+    prop_oneof![Just(Consistency::Serial), Just(Consistency::LocalSerial)]
+}
+
+pub fn arb_replication() -> impl Strategy<Value = ReplicationOptions<'static>> {
+    let datacenter = "[a-z][a-z0-9_-]{0,7}".prop_filter("class key", |name| name != "class").prop_map(Cow::Owned);
+
+    prop_oneof![
+        any::<u32>().prop_map(|replication_factor| ReplicationOptions::Simple { replication_factor }),
+        vec((datacenter, any::<u32>()), 0..3).prop_map(|datacenters| ReplicationOptions::NetworkTopology { datacenters }),
+    ]
+}
+
+pub fn arb_if_clause() -> impl Strategy<Value = IfClause<'static>> {
+    prop_oneof![
+        LazyJust::new(|| IfClause::Exists),
+        vec(arb_condition(), 1..3).prop_map(IfClause::Conditions),
+    ]
+}
+
+pub fn arb_condition() -> impl Strategy<Value = Condition<'static>> {
+    let operator = prop_oneof![
+        Just(Operator::Equals),
+        Just(Operator::NotEquals),
+        Just(Operator::Greater),
+        Just(Operator::GreaterOrEquals),
+        Just(Operator::Less),
+        Just(Operator::LessOrEquals),
+    ];
+
+    let null_check = prop_oneof![
+        Just(Operator::Is),
+        Just(Operator::IsNot),
+    ];
+
+    prop_oneof![
+        (arb_identifier(), operator, arb_expression()).prop_map(|(column, operator, right)| {
+            Condition::new(Expression::Column(column), operator, right)
+        }),
+        (arb_identifier(), null_check).prop_map(|(column, operator)| {
+            Condition::new(Expression::Column(column), operator, Expression::Literal(Value::Null))
+        }),
+    ]
+}
+
+pub fn arb_expression() -> impl Strategy<Value = Expression<'static>> {
+    let leaf = prop_oneof![
+        arb_identifier().prop_map(Expression::Column),
+        (arb_identifier(), arb_identifier()).prop_map(|(qualifier, column)| Expression::QualifiedColumn(qualifier, column)),
+        arb_value().prop_map(Expression::Literal),
+        LazyJust::new(|| Expression::Placeholder),
+        arb_identifier().prop_map(Expression::NamedPlaceholder),
+    ];
+
+    leaf.prop_recursive(3, 16, 3, |expression| {
+        let operator = prop_oneof![
+            Just(ArithmeticOperator::Add),
+            Just(ArithmeticOperator::Subtract),
+            Just(ArithmeticOperator::Multiply),
+            Just(ArithmeticOperator::Divide),
+            Just(ArithmeticOperator::Modulo),
+        ];
+
+        prop_oneof![
+            (expression.clone(), operator, expression.clone()).prop_map(|(left, operator, right)| {
+                Expression::Binary(Box::new(left), operator, Box::new(right))
+            }),
+            (arb_unquoted_identifier(), vec(expression.clone(), 0..3)).prop_map(|(name, arguments)| Expression::Function(name, arguments)),
+            vec(expression, 2..4).prop_map(Expression::Tuple),
+        ]
+    })
+}
+
+pub fn arb_value() -> impl Strategy<Value = Value<'static>> {
+    let scalar = prop_oneof![
+        any::<i64>().prop_map(Value::Integer),
+        any::<f64>().prop_filter("finite", |value| value.is_finite()).prop_map(Value::Float),
+        "\\PC{0,12}".prop_map(|string| Value::String(Cow::Owned(string))),
+        any::<bool>().prop_map(Value::Bool),
+        any::<u128>().prop_map(Value::Uuid),
+        (0..4_102_444_800_000i64).prop_map(Value::Timestamp),
+        Just(Value::Null),
+    ];
+
+    scalar.prop_recursive(2, 8, 3, |value| {
+        prop_oneof![
+            vec((arb_identifier(), value.clone()), 0..3).prop_map(Value::UserType),
+            vec(value.clone(), 0..3).prop_map(Value::List),
+            vec(value.clone(), 1..3).prop_map(Value::Set),
+            vec((value.clone(), value), 1..3).prop_map(Value::Map),
+        ]
+    })
+}
+
+/// Generates lowercase identifiers, which the formatter leaves unquoted, and mixed-case ones,
+/// which it quotes.
+pub fn arb_identifier() -> impl Strategy<Value = Cow<'static, str>> {
+    prop_oneof![
+        arb_unquoted_identifier(),
+        "[A-Z][A-Za-z0-9]{0,7}".prop_map(Cow::Owned),
+    ]
+}
+
+/// Function names are case-insensitive, so they are generated folded.
+fn arb_unquoted_identifier() -> impl Strategy<Value = Cow<'static, str>> {
+    "[a-z][a-z0-9_]{0,7}"
+        .prop_filter("reserved word", |identifier| !is_reserved(identifier) && !RESERVED.iter().any(|keyword| identifier.starts_with(keyword)))
+        .prop_map(Cow::Owned)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::parser::parse_query;
+
+    proptest! {
+        #[test]
+        fn test_format_parse_round_trip(query in arb_query()) {
+            let source = query.to_cql_string();
+            prop_assert_eq!(parse_query(&source), Ok(query), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::query_parser::parser::parse_query;
+use crate::query_parser::query::{BindError, Condition, DataManipulationQuery, Expression, IfClause, Query, QueryParsingError, Value};
+
+/// Parses a statement with `?` or `:name` placeholders and substitutes the parameters into the
+/// parsed query in the order the placeholders appear. Parameters never pass through the query text,
+/// so a string parameter always stays a single literal. Never panics on arbitrary input;
+/// malformed statements and parameters are reported as errors.
+pub fn parse_query_with_params<'a>(source: &'a str, params: &[Value<'a>]) -> Result<Query<'a>, QueryParsingError> {
+    let mut query = parse_query(source)?;
+    bind(&mut query, params)?;
+    Ok(query)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(params = params.len()), err))]
+pub(crate) fn bind<'a>(query: &mut Query<'a>, params: &[Value<'a>]) -> Result<(), BindError> {
+    let placeholders = collect_query(query, |expression| matches!(expression, Expression::Placeholder | Expression::NamedPlaceholder(_)));
+
+    if placeholders.len() != params.len() {
+        return Err(BindError::ArityMismatch { expected: placeholders.len(), actual: params.len() });
+    }
+
+    for (index, (placeholder, is_operand)) in placeholders.into_iter().enumerate() {
+        let param = &params[index];
+
+        if is_operand && !matches!(param, Value::Integer(_) | Value::Float(_) | Value::List(_) | Value::Set(_) | Value::Map(_)) {
+            return Err(BindError::NonNumericOperand { index });
+        }
+
+        *placeholder = Expression::Literal(param.clone());
+    }
+
+    Ok(())
+}
+
+/// Returns the literals of the query from left to right, so they can be replaced with placeholders.
+pub(crate) fn literals<'a, 'b>(query: &'a mut Query<'b>) -> Vec<&'a mut Expression<'b>> {
+    collect_query(query, |expression| matches!(expression, Expression::Literal(_)))
+        .into_iter()
+        .map(|(literal, _)| literal)
+        .collect()
+}
+
+type Collected<'a, 'b> = Vec<(&'a mut Expression<'b>, bool)>;
+
+fn collect_query<'a, 'b>(query: &'a mut Query<'b>, wanted: fn(&Expression) -> bool) -> Collected<'a, 'b> {
+    let mut placeholders = Vec::new();
+
+    match query {
+        Query::DataManipulationQuery(statement) | Query::Explain(statement) => collect_statement(statement, wanted, &mut placeholders),
+        Query::Transaction(statements) => {
+            for statement in statements {
+                collect_statement(statement, wanted, &mut placeholders);
+            }
+        }
+        _ => {}
+    }
+
+    placeholders
+}
+
+fn collect_statement<'a, 'b>(statement: &'a mut DataManipulationQuery<'b>, wanted: fn(&Expression) -> bool, placeholders: &mut Collected<'a, 'b>) {
+    match statement {
+        DataManipulationQuery::Select(query) => {
+            for column in query.columns.iter_mut() {
+                collect_expression(&mut column.expression, false, wanted, placeholders);
+            }
+            collect_conditions(&mut query.conditions, wanted, placeholders);
+        }
+        DataManipulationQuery::Insert(query) => {
+            for value in query.values.iter_mut().flatten() {
+                collect_expression(value, false, wanted, placeholders);
+            }
+        }
+        DataManipulationQuery::Update(query) => {
+            for (_, value) in query.values.iter_mut() {
+                collect_expression(value, false, wanted, placeholders);
+            }
+            collect_conditions(&mut query.conditions, wanted, placeholders);
+            if let Some(IfClause::Conditions(conditions)) = &mut query.if_clause {
+                collect_conditions(conditions, wanted, placeholders);
+            }
+        }
+        DataManipulationQuery::Delete(query) => {
+            collect_conditions(&mut query.conditions, wanted, placeholders);
+            if let Some(IfClause::Conditions(conditions)) = &mut query.if_clause {
+                collect_conditions(conditions, wanted, placeholders);
+            }
+        }
+    }
+}
+
+fn collect_conditions<'a, 'b>(conditions: &'a mut [Condition<'b>], wanted: fn(&Expression) -> bool, placeholders: &mut Collected<'a, 'b>) {
+    for condition in conditions.iter_mut() {
+        collect_expression(&mut condition.left, false, wanted, placeholders);
+        collect_expression(&mut condition.right, false, wanted, placeholders);
+    }
+}
+
+/// Records every wanted leaf expression from left to right, flagging the ones that are operands
+/// of an arithmetic operator since placeholders there only accept numbers and collections.
+fn collect_expression<'a, 'b>(expression: &'a mut Expression<'b>, is_operand: bool, wanted: fn(&Expression) -> bool, placeholders: &mut Collected<'a, 'b>) {
+    match expression {
+        Expression::Binary(left, _, right) => {
+            collect_expression(left, true, wanted, placeholders);
+            collect_expression(right, true, wanted, placeholders);
+        }
+        Expression::Function(_, arguments) | Expression::Tuple(arguments) => {
+            for argument in arguments.iter_mut() {
+                collect_expression(argument, false, wanted, placeholders);
+            }
+        }
+        _ if wanted(expression) => placeholders.push((expression, is_operand)),
+        Expression::Column(_) | Expression::QualifiedColumn(..) | Expression::Literal(_) | Expression::Placeholder | Expression::NamedPlaceholder(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::builder::{ConditionBuilder, SelectQueryBuilder, UpdateQueryBuilder};
+    use crate::query_parser::query::{ArithmeticOperator, Operator};
+
+    #[test]
+    fn test_parse_query_with_params() {
+        let query = "UPDATE user_sessions SET type = ?, visits = visits + ? WHERE user_id = ?";
+
+        let expected_result = UpdateQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .value(("type".to_string(), Value::String("mobile".into())))
+            .assignment("visits".to_string(), Expression::Binary(
+                Box::new(Expression::Column("visits".into())),
+                ArithmeticOperator::Add,
+                Box::new(Expression::Literal(Value::Integer(1))),
+            ))
+            .condition(ConditionBuilder::new()
+                .column("user_id".to_string())
+                .operator(Operator::Equals)
+                .value(Value::Integer(42))
+                .build().unwrap())
+            .build().unwrap();
+
+        let params = [Value::String("mobile".into()), Value::Integer(1), Value::Integer(42)];
+
+        assert_eq!(parse_query_with_params(query, &params), Ok(expected_result));
+
+        let named_query = "UPDATE user_sessions SET type = :type, visits = visits + :visits WHERE user_id = :user_id";
+        assert_eq!(parse_query_with_params(named_query, &params), parse_query_with_params(query, &params));
+    }
+
+    #[test]
+    fn test_parameter_stays_a_literal() {
+        let injection = "1; DROP TABLE user_sessions";
+
+        let expected_result = SelectQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .condition(ConditionBuilder::new()
+                .column("type".to_string())
+                .operator(Operator::Equals)
+                .value(Value::String(injection.into()))
+                .build().unwrap())
+            .build().unwrap();
+
+        let result = parse_query_with_params("SELECT * FROM user_sessions WHERE type = ?", &[Value::String(injection.into())]);
+
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn test_bind_errors() {
+        let params = vec![
+            ("SELECT * FROM user_sessions WHERE user_id = ?", vec![], BindError::ArityMismatch { expected: 1, actual: 0 }),
+            ("DELETE FROM user_sessions WHERE user_id = 1", vec![Value::Integer(1)], BindError::ArityMismatch { expected: 0, actual: 1 }),
+            ("DELETE FROM user_sessions WHERE user_id = 1 IF visits = ?", vec![], BindError::ArityMismatch { expected: 1, actual: 0 }),
+            ("BEGIN TRANSACTION INSERT INTO user_sessions (user_id) VALUES (?); DELETE FROM user_sessions WHERE user_id = ?; COMMIT", vec![Value::Integer(1)], BindError::ArityMismatch { expected: 2, actual: 1 }),
+            ("SELECT * FROM user_sessions WHERE visits = ? * 2", vec![Value::String("2".into())], BindError::NonNumericOperand { index: 0 }),
+        ];
+
+        for (query, values, expected_error) in params {
+            assert_eq!(parse_query_with_params(query, &values), Err(QueryParsingError::InvalidParameters(expected_error)));
+        }
+
+        let tags = Value::Set(vec![Value::String("vip".into())]);
+        assert!(parse_query_with_params("UPDATE users SET tags = tags + ? WHERE user_id = 1", &[tags]).is_ok());
+    }
+}
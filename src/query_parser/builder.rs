@@ -1,42 +1,75 @@
-use crate::query_parser::query::{Column, ColumnType, Condition, DataManipulationQuery, DeleteQuery, InsertQuery, Operator, Query, SelectQuery, UpdateQuery, Value};
+use std::borrow::Cow;
+use crate::query_parser::query::{BuilderError, Column, ColumnType, Condition, DataManipulationQuery, Consistency, DeleteQuery, Expression, IfClause, InsertQuery, Operator, Query, SelectQuery, Selector, UpdateQuery, Value};
 
-pub(crate) struct ColumnBuilder {
-    column_name: Option<String>,
-    column_type: Option<ColumnType>,
+#[derive(Default)]
+pub struct ColumnBuilder<'a> {
+    column_name: Option<Cow<'a, str>>,
+    column_type: Option<ColumnType<'a>>,
 }
 
-pub(crate) struct SelectQueryBuilder {
-    columns: Vec<String>,
-    table: Option<String>,
-    conditions: Vec<Condition>,
+#[derive(Default)]
+pub struct SelectQueryBuilder<'a> {
+    columns: Vec<Selector<'a>>,
+    keyspace: Option<Cow<'a, str>>,
+    table: Option<Cow<'a, str>>,
+    table_alias: Option<Cow<'a, str>>,
+    conditions: Vec<Condition<'a>>,
+    per_partition_limit: Option<u32>,
+    allow_filtering: bool,
+    json: bool,
+    distinct: bool,
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
 }
 
-pub(crate) struct InsertQueryBuilder {
-    columns: Vec<String>,
-    table: Option<String>,
-    values: Vec<Value>,
+#[derive(Default)]
+pub struct InsertQueryBuilder<'a> {
+    columns: Vec<Cow<'a, str>>,
+    keyspace: Option<Cow<'a, str>>,
+    table: Option<Cow<'a, str>>,
+    values: Vec<Vec<Expression<'a>>>,
+    json: Option<Cow<'a, str>>,
+    ttl: Option<u32>,
+    timestamp: Option<i64>,
+    if_not_exists: bool,
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
 }
 
-pub(crate) struct UpdateQueryBuilder {
-    table: Option<String>,
-    values: Vec<(String, Value)>,
-    conditions: Vec<Condition>,
+#[derive(Default)]
+pub struct UpdateQueryBuilder<'a> {
+    keyspace: Option<Cow<'a, str>>,
+    table: Option<Cow<'a, str>>,
+    values: Vec<(Cow<'a, str>, Expression<'a>)>,
+    conditions: Vec<Condition<'a>>,
+    ttl: Option<u32>,
+    timestamp: Option<i64>,
+    if_clause: Option<IfClause<'a>>,
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
 }
 
-pub(crate) struct DeleteQueryBuilder {
-    columns: Vec<String>,
-    table: Option<String>,
-    conditions: Vec<Condition>,
+#[derive(Default)]
+pub struct DeleteQueryBuilder<'a> {
+    columns: Vec<Cow<'a, str>>,
+    keyspace: Option<Cow<'a, str>>,
+    table: Option<Cow<'a, str>>,
+    conditions: Vec<Condition<'a>>,
+    timestamp: Option<i64>,
+    if_clause: Option<IfClause<'a>>,
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
 }
 
-pub(crate) struct ConditionBuilder {
-    column: Option<String>,
+#[derive(Default)]
+pub struct ConditionBuilder<'a> {
+    left: Option<Expression<'a>>,
     operator: Option<Operator>,
-    value: Option<Value>,
+    right: Option<Expression<'a>>,
 }
-impl ColumnBuilder {
+impl<'a> ColumnBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         ColumnBuilder {
             column_name: None,
             column_type: None,
@@ -44,261 +77,547 @@ impl ColumnBuilder {
     }
 
     #[inline]
-    pub(crate) fn name(mut self, name: String) -> Self {
-        self.column_name = Some(name);
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.column_name = Some(name.into());
         self
     }
 
     #[inline]
-    pub(crate) fn column_type(mut self, column_type: ColumnType) -> Self {
+    pub fn column_type(mut self, column_type: ColumnType<'a>) -> Self {
         self.column_type = Some(column_type);
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Column {
-        Column {
-            name: self.column_name.expect("column_name field doesn't set"),
-            column_type: self.column_type.expect("column_type field doesn't set"),
-        }
+    pub fn build(self) -> Result<Column<'a>, BuilderError> {
+        Ok(Column {
+            name: self.column_name.ok_or(BuilderError::MissingField("column_name"))?,
+            column_type: self.column_type.ok_or(BuilderError::MissingField("column_type"))?,
+        })
     }
 }
 
-impl SelectQueryBuilder {
+impl<'a> SelectQueryBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             columns: Vec::default(),
+            keyspace: None,
             table: None,
+            table_alias: None,
             conditions: Vec::default(),
+            per_partition_limit: None,
+            allow_filtering: false,
+            json: false,
+            distinct: false,
+            consistency: None,
+            serial_consistency: None,
         }
     }
 
     #[inline]
-    pub(crate) fn column(mut self, column: String) -> Self {
-        self.columns.push(column);
+    pub fn column(self, column: impl Into<Cow<'a, str>>) -> Self {
+        self.expression(Expression::Column(column.into()))
+    }
+
+    #[inline]
+    pub fn expression(mut self, expression: Expression<'a>) -> Self {
+        self.columns.push(Selector { expression, alias: None });
         self
     }
 
     #[inline]
-    pub(crate) fn columns(mut self, columns: Vec<String>) -> Self {
-        self.columns.extend(columns);
+    pub fn columns(mut self, columns: Vec<Expression<'a>>) -> Self {
+        self.columns.extend(columns.into_iter().map(|expression| Selector { expression, alias: None }));
+        self
+    }
+
+    #[inline]
+    pub fn aliased(mut self, expression: Expression<'a>, alias: impl Into<Cow<'a, str>>) -> Self {
+        self.columns.push(Selector { expression, alias: Some(alias.into()) });
+        self
+    }
+
+    #[inline]
+    pub fn selectors(mut self, selectors: Vec<Selector<'a>>) -> Self {
+        self.columns.extend(selectors);
+        self
+    }
+
+    #[inline]
+    pub fn keyspace(mut self, keyspace: impl Into<Cow<'a, str>>) -> Self {
+        self.keyspace = Some(keyspace.into());
+        self
+    }
+
+    #[inline]
+    pub fn table(mut self, table: impl Into<Cow<'a, str>>) -> Self {
+        self.table = Some(table.into());
         self
     }
 
     #[inline]
-    pub(crate) fn table(mut self, table: String) -> Self {
-        self.table = Some(table);
+    pub fn table_alias(mut self, table_alias: impl Into<Cow<'a, str>>) -> Self {
+        self.table_alias = Some(table_alias.into());
         self
     }
 
     #[inline]
-    pub(crate) fn condition(mut self, column: Condition) -> Self {
+    pub fn condition(mut self, column: Condition<'a>) -> Self {
         self.conditions.push(column);
         self
     }
 
     #[inline]
-    pub(crate) fn conditions(mut self, conditions: Vec<Condition>) -> Self {
+    pub fn conditions(mut self, conditions: Vec<Condition<'a>>) -> Self {
         self.conditions.extend(conditions);
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Query {
-        Query::DataManipulationQuery(DataManipulationQuery::Select(SelectQuery::new(
+    pub fn per_partition_limit(mut self, per_partition_limit: u32) -> Self {
+        self.per_partition_limit = Some(per_partition_limit);
+        self
+    }
+
+    #[inline]
+    pub fn allow_filtering(mut self) -> Self {
+        self.allow_filtering = true;
+        self
+    }
+
+    #[inline]
+    pub fn json(mut self) -> Self {
+        self.json = true;
+        self
+    }
+
+    #[inline]
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    #[inline]
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    #[inline]
+    pub fn serial_consistency(mut self, serial_consistency: Consistency) -> Self {
+        self.serial_consistency = Some(serial_consistency);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Result<Query<'a>, BuilderError> {
+        Ok(Query::DataManipulationQuery(DataManipulationQuery::Select(SelectQuery::new(
             self.columns,
-            self.table.expect("the table doesn't set"),
+            self.keyspace,
+            self.table.ok_or(BuilderError::MissingField("table"))?,
+            self.table_alias,
             self.conditions,
-        )))
+            self.per_partition_limit,
+            self.allow_filtering,
+            self.json,
+            self.distinct,
+            self.consistency,
+            self.serial_consistency,
+        ))))
     }
 }
 
-impl InsertQueryBuilder {
+impl<'a> InsertQueryBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             columns: Vec::default(),
+            keyspace: None,
             table: None,
             values: Vec::default(),
+            json: None,
+            ttl: None,
+            timestamp: None,
+            if_not_exists: false,
+            consistency: None,
+            serial_consistency: None,
         }
     }
 
     #[inline]
-    pub(crate) fn column(mut self, column: String) -> Self {
-        self.columns.push(column);
+    pub fn column(mut self, column: impl Into<Cow<'a, str>>) -> Self {
+        self.columns.push(column.into());
         self
     }
 
     #[inline]
-    pub(crate) fn columns(mut self, columns: Vec<String>) -> Self {
+    pub fn columns(mut self, columns: Vec<Cow<'a, str>>) -> Self {
         self.columns.extend(columns);
         self
     }
 
     #[inline]
-    pub(crate) fn table(mut self, table: String) -> Self {
-        self.table = Some(table);
+    pub fn keyspace(mut self, keyspace: impl Into<Cow<'a, str>>) -> Self {
+        self.keyspace = Some(keyspace.into());
+        self
+    }
+
+    #[inline]
+    pub fn table(mut self, table: impl Into<Cow<'a, str>>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    #[inline]
+    pub fn value(self, value: Value<'a>) -> Self {
+        self.expression(Expression::Literal(value))
+    }
+
+    #[inline]
+    pub fn expression(mut self, expression: Expression<'a>) -> Self {
+        match self.values.last_mut() {
+            Some(row) => row.push(expression),
+            None => self.values.push(vec![expression]),
+        }
         self
     }
 
     #[inline]
-    pub(crate) fn value(mut self, value: Value) -> Self {
-        self.values.push(value);
+    pub fn row(mut self, row: Vec<Value<'a>>) -> Self {
+        self.values.push(row.into_iter().map(Expression::Literal).collect());
         self
     }
 
     #[inline]
-    pub(crate) fn values(mut self, values: Vec<Value>) -> Self {
+    pub fn values(mut self, values: Vec<Vec<Expression<'a>>>) -> Self {
         self.values.extend(values);
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Query {
-        Query::DataManipulationQuery(DataManipulationQuery::Insert(InsertQuery::new(
+    pub fn json(mut self, json: impl Into<Cow<'a, str>>) -> Self {
+        self.json = Some(json.into());
+        self
+    }
+
+    #[inline]
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    #[inline]
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    #[inline]
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    #[inline]
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    #[inline]
+    pub fn serial_consistency(mut self, serial_consistency: Consistency) -> Self {
+        self.serial_consistency = Some(serial_consistency);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Result<Query<'a>, BuilderError> {
+        Ok(Query::DataManipulationQuery(DataManipulationQuery::Insert(InsertQuery::new(
             self.columns,
-            self.table.expect("the table doesn't set"),
+            self.keyspace,
+            self.table.ok_or(BuilderError::MissingField("table"))?,
             self.values,
-        )))
+            self.json,
+            self.ttl,
+            self.timestamp,
+            self.if_not_exists,
+            self.consistency,
+            self.serial_consistency,
+        ))))
     }
 }
 
-impl UpdateQueryBuilder {
+impl<'a> UpdateQueryBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             values: Vec::default(),
+            keyspace: None,
             table: None,
             conditions: Vec::default(),
+            ttl: None,
+            timestamp: None,
+            if_clause: None,
+            consistency: None,
+            serial_consistency: None,
         }
     }
 
     #[inline]
-    pub(crate) fn value(mut self, value: (String, Value)) -> Self {
-        self.values.push(value);
+    pub fn value(self, (column, value): (impl Into<Cow<'a, str>>, Value<'a>)) -> Self {
+        self.assignment(column, Expression::Literal(value))
+    }
+
+    #[inline]
+    pub fn assignment(mut self, column: impl Into<Cow<'a, str>>, expression: Expression<'a>) -> Self {
+        self.values.push((column.into(), expression));
         self
     }
 
     #[inline]
-    pub(crate) fn values(mut self, values: Vec<(String, Value)>) -> Self {
+    pub fn values(mut self, values: Vec<(Cow<'a, str>, Expression<'a>)>) -> Self {
         self.values.extend(values);
         self
     }
 
     #[inline]
-    pub(crate) fn table(mut self, table: String) -> Self {
-        self.table = Some(table);
+    pub fn keyspace(mut self, keyspace: impl Into<Cow<'a, str>>) -> Self {
+        self.keyspace = Some(keyspace.into());
         self
     }
 
     #[inline]
-    pub(crate) fn condition(mut self, column: Condition) -> Self {
+    pub fn table(mut self, table: impl Into<Cow<'a, str>>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    #[inline]
+    pub fn condition(mut self, column: Condition<'a>) -> Self {
         self.conditions.push(column);
         self
     }
 
     #[inline]
-    pub(crate) fn conditions(mut self, conditions: Vec<Condition>) -> Self {
+    pub fn conditions(mut self, conditions: Vec<Condition<'a>>) -> Self {
         self.conditions.extend(conditions);
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Query {
-        Query::DataManipulationQuery(DataManipulationQuery::Update(UpdateQuery::new(
-            self.table.expect("the table doesn't set"),
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    #[inline]
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    #[inline]
+    pub fn if_clause(mut self, if_clause: IfClause<'a>) -> Self {
+        self.if_clause = Some(if_clause);
+        self
+    }
+
+    #[inline]
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    #[inline]
+    pub fn serial_consistency(mut self, serial_consistency: Consistency) -> Self {
+        self.serial_consistency = Some(serial_consistency);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Result<Query<'a>, BuilderError> {
+        Ok(Query::DataManipulationQuery(DataManipulationQuery::Update(UpdateQuery::new(
+            self.keyspace,
+            self.table.ok_or(BuilderError::MissingField("table"))?,
             self.values,
             self.conditions,
-        )))
+            self.ttl,
+            self.timestamp,
+            self.if_clause,
+            self.consistency,
+            self.serial_consistency,
+        ))))
     }
 }
 
-impl DeleteQueryBuilder {
+impl<'a> DeleteQueryBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             columns: Vec::default(),
+            keyspace: None,
             table: None,
             conditions: Vec::default(),
+            timestamp: None,
+            if_clause: None,
+            consistency: None,
+            serial_consistency: None,
         }
     }
 
     #[inline]
-    pub(crate) fn column(mut self, column: String) -> Self {
-        self.columns.push(column);
+    pub fn column(mut self, column: impl Into<Cow<'a, str>>) -> Self {
+        self.columns.push(column.into());
         self
     }
 
     #[inline]
-    pub(crate) fn columns(mut self, columns: Vec<String>) -> Self {
+    pub fn columns(mut self, columns: Vec<Cow<'a, str>>) -> Self {
         self.columns.extend(columns);
         self
     }
 
     #[inline]
-    pub(crate) fn table(mut self, table: String) -> Self {
-        self.table = Some(table);
+    pub fn keyspace(mut self, keyspace: impl Into<Cow<'a, str>>) -> Self {
+        self.keyspace = Some(keyspace.into());
         self
     }
 
     #[inline]
-    pub(crate) fn condition(mut self, condition: Condition) -> Self {
+    pub fn table(mut self, table: impl Into<Cow<'a, str>>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    #[inline]
+    pub fn condition(mut self, condition: Condition<'a>) -> Self {
         self.conditions.push(condition);
         self
     }
 
     #[inline]
-    pub(crate) fn conditions(mut self, condition: Vec<Condition>) -> Self {
+    pub fn conditions(mut self, condition: Vec<Condition<'a>>) -> Self {
         self.conditions.extend(condition);
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Query {
-        Query::DataManipulationQuery(DataManipulationQuery::Delete(DeleteQuery::new(
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    #[inline]
+    pub fn if_clause(mut self, if_clause: IfClause<'a>) -> Self {
+        self.if_clause = Some(if_clause);
+        self
+    }
+
+    #[inline]
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    #[inline]
+    pub fn serial_consistency(mut self, serial_consistency: Consistency) -> Self {
+        self.serial_consistency = Some(serial_consistency);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Result<Query<'a>, BuilderError> {
+        Ok(Query::DataManipulationQuery(DataManipulationQuery::Delete(DeleteQuery::new(
             self.columns,
-            self.table.expect("the table doesn't set"),
+            self.keyspace,
+            self.table.ok_or(BuilderError::MissingField("table"))?,
             self.conditions,
-        )))
+            self.timestamp,
+            self.if_clause,
+            self.consistency,
+            self.serial_consistency,
+        ))))
     }
 }
 
-impl ConditionBuilder {
+impl<'a> ConditionBuilder<'a> {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            column: None,
+            left: None,
             operator: None,
-            value: None,
+            right: None,
         }
     }
 
     #[inline]
-    pub(crate) fn column(mut self, column: String) -> Self {
-        self.column = Some(column);
+    pub fn column(mut self, column: impl Into<Cow<'a, str>>) -> Self {
+        self.left = Some(Expression::Column(column.into()));
         self
     }
 
     #[inline]
-    pub(crate) fn operator(mut self, operator: Operator) -> Self {
+    pub fn left(mut self, left: Expression<'a>) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    #[inline]
+    pub fn operator(mut self, operator: Operator) -> Self {
         self.operator = Some(operator);
         self
     }
 
     #[inline]
-    pub(crate) fn value(mut self, value: Value) -> Self {
-        self.value = Some(value);
+    pub fn value(mut self, value: Value<'a>) -> Self {
+        self.right = Some(Expression::Literal(value));
         self
     }
 
     #[inline]
-    pub(crate) fn build(self) -> Condition {
-        Condition {
-            column: self.column.expect("the column doesn't set"),
-            operator: self.operator.expect("the operator doesn't set"),
-            value: self.value.expect("the value doesn't set"),
-        }
+    pub fn right(mut self, right: Expression<'a>) -> Self {
+        self.right = Some(right);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Result<Condition<'a>, BuilderError> {
+        Ok(Condition::new(
+            self.left.ok_or(BuilderError::MissingField("left"))?,
+            self.operator.ok_or(BuilderError::MissingField("operator"))?,
+            self.right.ok_or(BuilderError::MissingField("right"))?,
+        ))
     }
 }
 
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_reports_missing_fields() {
+        assert_eq!(SelectQueryBuilder::new().column("user_id".to_string()).build(), Err(BuilderError::MissingField("table")));
+        assert_eq!(InsertQueryBuilder::new().value(Value::Integer(1)).build(), Err(BuilderError::MissingField("table")));
+        assert_eq!(ConditionBuilder::new().column("user_id".to_string()).value(Value::Integer(1)).build(), Err(BuilderError::MissingField("operator")));
+        assert_eq!(ColumnBuilder::new().column_type(ColumnType::Uuid).build(), Err(BuilderError::MissingField("column_name")));
+    }
+
+    #[test]
+    fn test_build_renders_statement() {
+        let query = InsertQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .column("user_id".to_string())
+            .column("type".to_string())
+            .value(Value::Integer(1))
+            .value(Value::String("Robert'); DROP TABLE user_sessions; --".into()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query.to_cql_string(),
+            "INSERT INTO user_sessions (user_id, type) VALUES (1, 'Robert''); DROP TABLE user_sessions; --')"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::common_parser::{expect_end, fold_case, parse_identifier, parse_keyword, parse_string_literal, parse_table_name, parse_value, ws};
+use crate::query_parser::keyword::{AND, COPY, FROM, TO, WITH};
+use crate::query_parser::query::{BulkQuery, CopyQuery, Query, QueryParsingError, Value};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{map, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, preceded, separated_pair};
+use nom::IResult;
+
+enum CopyDirection {
+    From,
+    To,
+}
+
+pub(crate) fn parse_copy_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(COPY)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected the copy keyword", &[COPY]))
+    };
+
+    let (query, (keyspace, table)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the table name", &["table name"]))
+    };
+
+    let parsing_result = opt(ws(delimited(
+        tag("("),
+        separated_list1(ws(tag(",")), parse_identifier),
+        tag(")"),
+    )))(query);
+
+    let (query, columns) = match parsing_result {
+        Ok((query, columns)) => (query, columns.unwrap_or_default()),
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing column names", &["column name"]))
+    };
+
+    let parsing_result = alt((
+        map(parse_keyword(FROM), |_| CopyDirection::From),
+        map(parse_keyword(TO), |_| CopyDirection::To),
+    ))(query);
+
+    let (query, direction) = match parsing_result {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the copy direction", &[FROM, TO]))
+    };
+
+    let (query, file) = match parse_string_literal(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the file name", &["file name"]))
+    };
+
+    let (query, options) = match parse_copy_options(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing copy options", &[WITH, "option"]))
+    };
+
+    expect_end(source, query)?;
+
+    let copy_query = CopyQuery { keyspace, table, columns, file, options };
+
+    match direction {
+        CopyDirection::From => Ok(Query::BulkQuery(BulkQuery::CopyFrom(copy_query))),
+        CopyDirection::To => Ok(Query::BulkQuery(BulkQuery::CopyTo(copy_query))),
+    }
+}
+
+fn parse_copy_options(query: &str) -> IResult<&str, Vec<(Cow<'_, str>, Value<'_>)>> {
+    let options_parser = opt(preceded(
+        parse_keyword(WITH),
+        separated_list1(
+            parse_keyword(AND),
+            separated_pair(parse_identifier, ws(tag("=")), parse_value),
+        ),
+    ));
+
+    map(options_parser, |options| {
+        options
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| (fold_case(name), value))
+            .collect()
+    })(query)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::query_parser::parser::parse_query;
+    use super::*;
+
+    #[test]
+    fn test_parse_copy_query() {
+        let params = vec![
+            (
+                "COPY sensors.readings FROM 'readings.csv'",
+                Query::BulkQuery(BulkQuery::CopyFrom(CopyQuery {
+                    keyspace: Some("sensors".into()),
+                    table: "readings".into(),
+                    columns: Vec::new(),
+                    file: "readings.csv".into(),
+                    options: Vec::new(),
+                })),
+            ),
+            (
+                "COPY readings (sensor_id, value) TO 'export.csv' WITH HEADER = true AND DELIMITER = ';'",
+                Query::BulkQuery(BulkQuery::CopyTo(CopyQuery {
+                    keyspace: None,
+                    table: "readings".into(),
+                    columns: vec!["sensor_id".into(), "value".into()],
+                    file: "export.csv".into(),
+                    options: vec![
+                        ("header".into(), Value::Bool(true)),
+                        ("delimiter".into(), Value::String(";".into())),
+                    ],
+                })),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result));
+        }
+    }
+}
@@ -1,56 +1,495 @@
-use nom::IResult;
-use nom::sequence::{delimited, tuple};
-use nom::character::complete::{digit1, i64 as parse_i64, multispace0};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use nom::{Err as NomErr, IResult};
+use nom::error::{Error as NomError, ErrorKind};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::character::complete::{digit1, hex_digit1, multispace1, not_line_ending, one_of, satisfy};
 use nom::branch::alt;
-use nom::combinator::{map, map_res, opt, recognize};
-use nom::bytes::complete::{tag, tag_no_case, take_while1};
-use crate::query_parser::keyword::{FALSE, TRUE};
-use crate::query_parser::query::Value;
+use nom::combinator::{map, map_opt, map_res, not, opt, peek, recognize, verify};
+use nom::multi::{fold_many0, many0, separated_list0, separated_list1};
+use nom::bytes::complete::{tag, tag_no_case, take_until, take_while, take_while1, take_while_m_n};
+use crate::query_parser::dialect::Dialect;
+use crate::query_parser::keyword::{is_reserved, FALSE, NULL, TIMESTAMP, TRUE};
+use crate::query_parser::timestamp::parse_timestamp;
+use crate::query_parser::query::{QueryParsingError, SyntaxError, Value};
 
 pub(crate) fn parse_keyword<'a>(keyword: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
     ws(tag_no_case(keyword))
 }
 
-pub(crate) fn parse_value(input: &str) -> IResult<&str, Value> {
+pub(crate) fn parse_value(input: &str) -> IResult<&str, Value<'_>> {
     alt((
-        parse_float,
-        parse_integer,
+        parse_uuid,
+        parse_timestamp_literal,
+        parse_number,
         map(ws(tag_no_case(FALSE)), |_| Value::Bool(false)),
         map(ws(tag_no_case(TRUE)), |_| Value::Bool(true)),
-        parse_string
+        map(ws(tag_no_case(NULL)), |_| Value::Null),
+        parse_string,
+        parse_collection_literal,
+        parse_user_type_literal,
     ))(input)
 }
 
-pub(crate) fn parse_string(input: &str) -> IResult<&str, Value> {
-    let string_parser = ws(delimited(tag("'"), take_while1(|ch: char| ch != '\''), tag("'")));
-    map(string_parser, |string: &str| Value::String(string.to_string()))(input)
+/// Parses a collection literal: a list such as `[1, 2]`, a map such as `{'a': 1}` or a set such
+/// as `{'a', 'b'}`. Maps and sets have at least one element since `{}` is an empty user-defined type.
+pub(crate) fn parse_collection_literal(input: &str) -> IResult<&str, Value<'_>> {
+    let list = map(
+        delimited(ws(tag("[")), separated_list0(parse_comma, parse_value), ws(tag("]"))),
+        Value::List,
+    );
+
+    let map_literal = map(
+        delimited(ws(tag("{")), separated_list1(parse_comma, separated_pair(parse_value, ws(tag(":")), parse_value)), ws(tag("}"))),
+        Value::Map,
+    );
+
+    let set = map(
+        delimited(ws(tag("{")), separated_list1(parse_comma, parse_value), ws(tag("}"))),
+        Value::Set,
+    );
+
+    alt((list, map_literal, set))(input)
+}
+
+/// Parses a user-defined type value such as `{street: 'Main St', city: 'Springfield'}`.
+pub(crate) fn parse_user_type_literal(input: &str) -> IResult<&str, Value<'_>> {
+    map(
+        delimited(
+            ws(tag("{")),
+            separated_list0(parse_comma, separated_pair(parse_identifier, ws(tag(":")), parse_value)),
+            ws(tag("}")),
+        ),
+        Value::UserType,
+    )(input)
+}
+
+pub(crate) fn parse_string(input: &str) -> IResult<&str, Value<'_>> {
+    map(parse_string_literal, Value::String)(input)
+}
+
+enum StringFragment<'a> {
+    Literal(&'a str),
+    Escaped(char),
+}
+
+thread_local! {
+    /// Whether the string literals of the statement being parsed take backslash escapes.
+    static STRING_ESCAPES: Cell<bool> = const { Cell::new(true) };
+
+    /// The unquoted words of the statement being parsed that its dialect reserves, case-folded.
+    /// Outside a parse the keywords of the grammar are reserved.
+    static RESERVED_WORDS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
 }
 
-pub(crate) fn parse_float(input: &str) -> IResult<&str, Value> {
-    ws(map_res(
-        recognize(tuple((opt(tag("-")), digit1, tag("."), digit1))),
-        |s: &str| { s.parse::<f64>().map(Value::Float) },
+/// Runs a parse of a statement with the string escapes and reserved words of a dialect. Literals
+/// and identifiers are parsed deep inside the grammar, so they are set for the length of the parse
+/// instead of the dialect being passed down to them. `words` are the unquoted words of the statement.
+pub(crate) fn with_dialect<'a, T>(dialect: &dyn Dialect, words: impl IntoIterator<Item = &'a str>, parse: impl FnOnce() -> T) -> T {
+    let reserved = words.into_iter()
+        .filter(|word| dialect.is_reserved(word))
+        .map(|word| fold_case(Cow::Borrowed(word)).into_owned())
+        .collect();
+
+    let previous_escapes = STRING_ESCAPES.replace(dialect.allows_string_escapes());
+    let previous_reserved = RESERVED_WORDS.replace(Some(reserved));
+    let result = parse();
+    STRING_ESCAPES.set(previous_escapes);
+    RESERVED_WORDS.set(previous_reserved);
+    result
+}
+
+/// Whether an unquoted word is reserved by the dialect of the statement being parsed.
+pub(crate) fn is_reserved_word(word: &str) -> bool {
+    RESERVED_WORDS.with_borrow(|reserved| match reserved {
+        Some(reserved) => {
+            let word = fold_case(Cow::Borrowed(word));
+            reserved.iter().any(|reserved| *reserved == word)
+        }
+        None => is_reserved(word),
+    })
+}
+
+/// Parses a single-quoted string literal. A quote inside the literal is written either
+/// doubled (`'O''Brien'`) or backslash-escaped, and `\n`, `\t`, `\r`, `\\` and `\uXXXX` are
+/// unescaped as well. A backslash that starts no escape, as in `'C:\data'`, is kept as it is, and
+/// so is every backslash when the dialect has no escapes. Literals without escapes are borrowed
+/// from the input.
+pub(crate) fn parse_string_literal(input: &str) -> IResult<&str, Cow<'_, str>> {
+    let escapes = STRING_ESCAPES.get();
+    let is_plain = move |ch: char| ch != '\'' && !(escapes && ch == '\\');
+
+    let plain = delimited(
+        tag("'"),
+        take_while(is_plain),
+        terminated(tag("'"), not(peek(tag("'")))),
+    );
+
+    let fragment = alt((
+        map(take_while1(is_plain), StringFragment::Literal),
+        map(tag("''"), |_| StringFragment::Escaped('\'')),
+        map(preceded(tag("\\"), parse_escape), StringFragment::Escaped),
+        map(tag("\\"), StringFragment::Literal),
+    ));
+
+    let body = fold_many0(fragment, String::new, |mut string, fragment| {
+        match fragment {
+            StringFragment::Literal(literal) => string.push_str(literal),
+            StringFragment::Escaped(ch) => string.push(ch),
+        }
+        string
+    });
+
+    ws(alt((
+        map(plain, Cow::Borrowed),
+        map(delimited(tag("'"), body, tag("'")), Cow::Owned),
+    )))(input)
+}
+
+fn parse_escape(input: &str) -> IResult<&str, char> {
+    alt((
+        map(tag("n"), |_| '\n'),
+        map(tag("t"), |_| '\t'),
+        map(tag("r"), |_| '\r'),
+        map(tag("\\"), |_| '\\'),
+        map(tag("'"), |_| '\''),
+        map(tag("\""), |_| '"'),
+        map_opt(
+            preceded(tag("u"), take_while_m_n(4, 4, |ch: char| ch.is_ascii_hexdigit())),
+            |code: &str| u32::from_str_radix(code, 16).ok().and_then(char::from_u32),
+        ),
     ))(input)
 }
 
-pub(crate) fn parse_integer(input: &str) -> IResult<&str, Value> {
-    ws(map(parse_i64, Value::Integer))(input)
+/// Quotes a string so that `parse_string_literal` reads it back unchanged.
+pub(crate) fn quote_string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('\'');
+
+    for ch in value.chars() {
+        match ch {
+            '\'' => literal.push_str("''"),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            '\r' => literal.push_str("\\r"),
+            ch => literal.push(ch),
+        }
+    }
+
+    literal.push('\'');
+    literal
+}
+
+pub(crate) fn parse_map(input: &str) -> IResult<&str, Vec<(Cow<'_, str>, Value<'_>)>> {
+    delimited(
+        ws(tag("{")),
+        separated_list0(parse_comma, separated_pair(parse_string_literal, ws(tag(":")), parse_value)),
+        ws(tag("}")),
+    )(input)
+}
+
+pub(crate) fn parse_uuid(input: &str) -> IResult<&str, Value<'_>> {
+    let hex = |length| take_while_m_n(length, length, |ch: char| ch.is_ascii_hexdigit());
+
+    let uuid_parser = recognize(tuple((
+        hex(8), tag("-"), hex(4), tag("-"), hex(4), tag("-"), hex(4), tag("-"), hex(12),
+    )));
+
+    ws(map_res(uuid_parser, |s: &str| u128::from_str_radix(&s.replace('-', ""), 16).map(Value::Uuid)))(input)
+}
+
+/// The error kind that marks an invalid typed timestamp literal, so `syntax_error` can name it.
+/// nom itself never fails with `Fix`, so no other parser of the grammar reports it.
+pub(crate) const INVALID_TIMESTAMP: ErrorKind = ErrorKind::Fix;
+
+/// Parses a typed timestamp literal such as `TIMESTAMP '2024-10-21 08:30:00+02:00'`. Once the
+/// keyword and the string are read, an invalid date is a failure rather than a mismatch, so it
+/// isn't reparsed as a column named `timestamp`.
+pub(crate) fn parse_timestamp_literal(input: &str) -> IResult<&str, Value<'_>> {
+    let (literal_input, _) = parse_keyword(TIMESTAMP)(input)?;
+    let (rest, literal) = parse_string_literal(literal_input)?;
+
+    match parse_timestamp(&literal) {
+        Ok(timestamp) => Ok((rest, Value::Timestamp(timestamp))),
+        Err(_) => Err(NomErr::Failure(NomError::new(literal_input, INVALID_TIMESTAMP))),
+    }
+}
+
+/// Parses a numeric literal. A decimal with a fraction or an exponent, such as `1.5`, `.5`, `1e9`
+/// or `-2.5E-3`, is a float; any other decimal, or a `0x`-prefixed hexadecimal, is an integer.
+/// Digits may be separated with underscores. A literal that doesn't fit the `i64` or `f64` range is
+/// a failure rather than a mismatch, so it isn't reparsed as something else.
+pub(crate) fn parse_number(input: &str) -> IResult<&str, Value<'_>> {
+    ws(|input| {
+        let hex_digits = recognize(pair(hex_digit1, many0(pair(tag("_"), hex_digit1))));
+        let mantissa = alt((
+            recognize(pair(parse_digits, opt(pair(tag("."), parse_digits)))),
+            recognize(pair(tag("."), parse_digits)),
+        ));
+        let exponent = tuple((one_of("eE"), opt(one_of("+-")), digit1));
+
+        let (rest, (sign, (radix, digits))) = pair(
+            opt(tag("-")),
+            alt((
+                map(preceded(tag_no_case("0x"), hex_digits), |digits| (16, digits)),
+                map(recognize(pair(mantissa, opt(exponent))), |digits| (10, digits)),
+            )),
+        )(input)?;
+
+        let literal = format!("{}{}", sign.unwrap_or(""), digits.replace('_', ""));
+
+        let value = if radix == 10 && literal.contains(['.', 'e', 'E']) {
+            literal.parse::<f64>().ok().filter(|value| value.is_finite()).map(Value::Float)
+        } else {
+            i64::from_str_radix(&literal, radix).ok().map(Value::Integer)
+        };
+
+        value.map(|value| (rest, value)).ok_or_else(|| out_of_range(input))
+    })(input)
+}
+
+/// Digits with optional single underscores between them, such as `1_000_000`.
+fn parse_digits(input: &str) -> IResult<&str, &str> {
+    recognize(pair(digit1, many0(pair(tag("_"), digit1))))(input)
+}
+
+fn out_of_range(input: &str) -> NomErr<NomError<&str>> {
+    NomErr::Failure(NomError::new(input, ErrorKind::TooLarge))
+}
+
+/// Parses a name: a double-quoted identifier or an unquoted one that isn't a reserved keyword.
+pub(crate) fn parse_identifier(input: &str) -> IResult<&str, Cow<'_, str>> {
+    alt((parse_quoted_identifier, parse_unreserved_identifier))(input)
+}
+
+pub(crate) fn parse_unreserved_identifier(input: &str) -> IResult<&str, Cow<'_, str>> {
+    verify(parse_unquoted_identifier, |identifier: &str| !is_reserved_word(identifier))(input)
+}
+
+/// Parses a double-quoted identifier, keeping its case as written.
+pub(crate) fn parse_quoted_identifier(input: &str) -> IResult<&str, Cow<'_, str>> {
+    let identifier_parser = delimited(tag("\""), take_while1(|ch: char| ch != '"'), tag("\""));
+    ws(map(identifier_parser, |identifier| normalize_identifier(Cow::Borrowed(identifier))))(input)
+}
+
+/// Parses an unquoted identifier, which is case-insensitive and therefore folded to lower case.
+/// Letters may be followed by combining marks, so decomposed accented letters are accepted.
+pub(crate) fn parse_unquoted_identifier(input: &str) -> IResult<&str, Cow<'_, str>> {
+    let identifier_parser = recognize(pair(
+        satisfy(|ch: char| ch.is_alphabetic() || ch == '_'),
+        take_while(|ch: char| ch.is_alphanumeric() || ch == '_' || is_combining_mark(ch)),
+    ));
+    ws(map(identifier_parser, |identifier| fold_case(Cow::Borrowed(identifier))))(input)
+}
+
+/// Lowercases an identifier, keeping it as it is when it is lowercase already.
+pub(crate) fn fold_case(identifier: Cow<'_, str>) -> Cow<'_, str> {
+    if identifier.chars().all(|ch| ch.is_lowercase() || !ch.is_alphabetic()) {
+        normalize_identifier(identifier)
+    } else {
+        normalize_identifier(Cow::Owned(identifier.to_lowercase()))
+    }
+}
+
+/// Brings an identifier to NFC, so a name typed with a precomposed letter and the same name typed
+/// with a combining mark refer to the same object. String literals are data and are kept as written.
+pub(crate) fn normalize_identifier(identifier: Cow<'_, str>) -> Cow<'_, str> {
+    if is_nfc(&identifier) {
+        identifier
+    } else {
+        Cow::Owned(identifier.nfc().collect())
+    }
+}
+
+pub(crate) fn parse_table_name(input: &str) -> IResult<&str, (Option<Cow<'_, str>>, Cow<'_, str>)> {
+    alt((
+        map(separated_pair(parse_identifier, tag("."), parse_identifier), |(keyspace, table)| (Some(keyspace), table)),
+        map(parse_identifier, |table| (None, table)),
+    ))(input)
 }
 
-pub(crate) fn parse_identifier(input: &str) -> IResult<&str, String> {
-    let filter = |ch: char| -> bool {
-        ch.is_alphabetic() || ch == '_'
-    };
-    ws(map(take_while1(filter), String::from))(input)
+/// Parses a column name that may be qualified with its table, such as `user_sessions.user_id`.
+pub(crate) fn parse_column_name(input: &str) -> IResult<&str, (Option<Cow<'_, str>>, Cow<'_, str>)> {
+    alt((
+        map(separated_pair(parse_identifier, tag("."), parse_identifier), |(table, column)| (Some(table), column)),
+        map(parse_identifier, |column| (None, column)),
+    ))(input)
 }
 
 pub (crate) fn parse_comma(input: &str) -> IResult<&str, &str> {
     ws(tag(","))(input)
 }
 
-pub(crate) fn ws<'a, F: 'a, O>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+pub(crate) fn ws<'a, F, O>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
 where
-    F: FnMut(&'a str) -> IResult<&'a str, O>,
+    F: FnMut(&'a str) -> IResult<&'a str, O> + 'a,
 {
-    delimited(multispace0, f, multispace0)
-}
\ No newline at end of file
+    delimited(skip_trivia, f, skip_trivia)
+}
+
+/// Checks that only whitespace and comments follow the end of a statement, so input a statement
+/// parser stops before is reported rather than silently dropped.
+pub(crate) fn expect_end(source: &str, query: &str) -> Result<(), QueryParsingError> {
+    let rest = skip_trivia(query).map_or(query, |(rest, _)| rest);
+
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        let message = "unexpected input after the end of the statement".to_string();
+        Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - rest.len(), message, Vec::new())))
+    }
+}
+
+/// Skips whitespace together with `-- line` and `/* block */` comments.
+pub(crate) fn skip_trivia(input: &str) -> IResult<&str, ()> {
+    let line_comment = recognize(pair(tag("--"), not_line_ending));
+    let block_comment = recognize(tuple((tag("/*"), take_until("*/"), tag("*/"))));
+
+    map(many0(alt((multispace1, line_comment, block_comment))), |_| ())(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_string_literal() {
+        let params = vec![
+            ("'LAPTOP'", "LAPTOP"),
+            ("''", ""),
+            ("'O''Brien'", "O'Brien"),
+            ("'O\\'Brien'", "O'Brien"),
+            ("'first\\nsecond\\ttab'", "first\nsecond\ttab"),
+            ("'caf\\u00e9'", "caf\u{e9}"),
+            ("'C:\\\\data'", "C:\\data"),
+            ("'C:\\data'", "C:\\data"),
+            ("'\\uZZZZ'", "\\uZZZZ"),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_string_literal(input), Ok(("", Cow::from(expected_result))), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_numeric_literals() {
+        let params = vec![
+            ("42", Value::Integer(42)),
+            ("-42", Value::Integer(-42)),
+            ("1_000_000", Value::Integer(1_000_000)),
+            ("0xDEADBEEF", Value::Integer(0xDEADBEEF)),
+            ("0x7fff_ffff_ffff_ffff", Value::Integer(i64::MAX)),
+            ("-9223372036854775808", Value::Integer(i64::MIN)),
+            ("1.5", Value::Float(1.5)),
+            ("1e9", Value::Float(1e9)),
+            ("1.5E-3", Value::Float(1.5e-3)),
+            ("-2.5e+2", Value::Float(-250.0)),
+            ("1_000.000_1", Value::Float(1000.0001)),
+            (".5", Value::Float(0.5)),
+            ("-.5e1", Value::Float(-5.0)),
+            ("-0x10", Value::Integer(-16)),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_value(input), Ok(("", expected_result)), "{}", input);
+        }
+
+        let params = vec!["9223372036854775808", "0x1_0000_0000_0000_0000", "1e999"];
+
+        for input in params {
+            assert_eq!(parse_value(input), Err(out_of_range(input)), "{}", input);
+        }
+
+        assert_eq!(parse_value("1_"), Ok(("_", Value::Integer(1))));
+        assert_eq!(parse_value("1e"), Ok(("e", Value::Integer(1))));
+        assert_eq!(parse_value("5."), Ok((".", Value::Integer(5))));
+    }
+
+    proptest! {
+        #[test]
+        fn test_integer_round_trip(value in any::<i64>()) {
+            let hex = format!("{}0x{:x}", if value < 0 { "-" } else { "" }, value.unsigned_abs());
+
+            for literal in [value.to_string(), hex] {
+                prop_assert_eq!(parse_number(&literal), Ok(("", Value::Integer(value))), "{}", literal);
+            }
+        }
+
+        #[test]
+        fn test_float_round_trip(value in any::<f64>().prop_filter("finite", |value| value.is_finite())) {
+            for literal in [Value::Float(value).to_string(), format!("{:e}", value), format!("{:E}", value)] {
+                prop_assert_eq!(parse_number(&literal), Ok(("", Value::Float(value))), "{}", literal);
+            }
+        }
+
+        #[test]
+        fn test_separated_digits(value in 0..i64::MAX) {
+            let digits = value.to_string();
+            let separated: String = digits.chars().enumerate().flat_map(|(index, digit)| {
+                (index > 0 && (digits.len() - index) % 3 == 0).then_some('_').into_iter().chain(Some(digit))
+            }).collect();
+
+            prop_assert_eq!(parse_number(&separated), Ok(("", Value::Integer(value))), "{}", separated);
+        }
+    }
+
+    #[test]
+    fn test_borrows_from_source() {
+        let params = vec![
+            (parse_string_literal("'LAPTOP'"), true),
+            (parse_string_literal("'O''Brien'"), false),
+            (parse_identifier("user_id"), true),
+            (parse_identifier("\"UserId\""), true),
+            (parse_identifier("UserId"), false),
+        ];
+
+        for (result, expected_result) in params {
+            let (_, parsed) = result.unwrap();
+            assert_eq!(matches!(parsed, Cow::Borrowed(_)), expected_result, "{}", parsed);
+        }
+    }
+
+    #[test]
+    fn test_identifier_normalization() {
+        let params = vec![
+            ("caf\u{e9}", "caf\u{e9}"),
+            ("cafe\u{301}", "caf\u{e9}"),
+            ("CAF\u{c9}", "caf\u{e9}"),
+            ("\"Cafe\u{301}\"", "Caf\u{e9}"),
+            ("\u{3a3}\u{3a4}\u{391}", "\u{3c3}\u{3c4}\u{3b1}"),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_identifier(input), Ok(("", Cow::from(expected_result))), "{}", input);
+        }
+
+        assert_eq!(parse_string_literal("'cafe\u{301}'"), Ok(("", Cow::from("cafe\u{301}"))));
+    }
+
+    #[test]
+    fn test_string_literal_round_trip() {
+        let params = vec!["", "LAPTOP", "O'Brien", "''", "line\nbreak\r\n", "tab\there", "back\\slash", "caf\u{e9}"];
+
+        for value in params {
+            assert_eq!(parse_string_literal(&quote_string_literal(value)), Ok(("", Cow::from(value))), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_ws_skips_comments() {
+        let params = vec![
+            ("  user_id  ", ""),
+            ("-- leading comment\n user_id -- trailing comment", ""),
+            ("/* block */ user_id /* another\n block */ = 1", "= 1"),
+            ("user_id /* unterminated", "/* unterminated"),
+        ];
+
+        for (input, expected_rest) in params {
+            assert_eq!(parse_identifier(input), Ok((expected_rest, Cow::from("user_id"))), "{}", input);
+        }
+    }
+}
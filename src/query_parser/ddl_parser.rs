@@ -1,81 +1,483 @@
-use crate::query_parser::common_parser::{parse_comma, parse_identifier, parse_keyword, ws};
-use crate::query_parser::keyword::{ADD, ALTER_TABLE, BOOL, CREATE_TABLE, DOUBLE, DROP, DROP_TABLE, FLOAT, INT, LONG, PRIMARY_KEY, TEXT, TIMESTAMP, UUID};
-use crate::query_parser::query::{AddColumnCondition, AlterTableCondition, AlterTableQuery, Column, ColumnType, CreateTableQuery, DataDefinitionQuery, DropColumnCondition, DropTableQuery, PrimaryKey, Query, QueryParsingError};
+use std::borrow::Cow;
+use crate::query_parser::dialect::Dialect;
+use crate::query_parser::dml_parser::{parse_condition, parse_conditions};
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::common_parser::{expect_end, parse_comma, parse_identifier, parse_keyword, parse_map, parse_string_literal, parse_table_name, parse_value, ws};
+use crate::query_parser::expression_parser::parse_expression;
+use crate::query_parser::keyword::{ADD, ALTER, ALTER_TABLE, AND, AS, ASC, BOOL, CHECK, CLUSTERING_ORDER_BY, COMPACTION, COMPRESSION, COUNTER, CREATE_FUNCTION, CREATE_KEYSPACE, CREATE_MATERIALIZED_VIEW, CREATE_TABLE, CREATE_TYPE, DEFAULT, DEFAULT_TIME_TO_LIVE, DESC, DOUBLE, DROP, DROP_TABLE, DURABLE_WRITES, FALSE, FLOAT, FROM, FROZEN, GC_GRACE_SECONDS, IF_EXISTS, IF_NOT_EXISTS, INT, LIST, LONG, MAP, NOT_NULL, PRIMARY_KEY, REFERENCES, RENAME, REPLICATION, RETURNS, SELECT, SET, STATIC, TEXT, TIMESTAMP, TO, TRUE, TYPE, UUID, VALIDATE_REFERENCES, WITH};
+use crate::query_parser::query::{AddColumnCondition, AlterColumnTypeCondition, AlterTableCondition, AlterTableQuery, ClusteringOrder, Column, ColumnConstraint, ColumnType, CompactionOptions, CompactionStrategy, CompressionCodec, CompressionOptions, Condition, CreateFunctionQuery, CreateKeyspaceQuery, CreateMaterializedViewQuery, CreateTableQuery, CreateTypeQuery, DataDefinitionQuery, DropColumnCondition, DropTableQuery, Operator, PrimaryKey, Query, QueryParsingError, RenameColumnCondition, ReplicationOptions, SyntaxError, TableOptions, Value};
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::{map, opt};
+use nom::character::complete::u32 as parse_u32;
+use nom::combinator::{all_consuming, map, map_res, opt};
 use nom::multi::{separated_list0, separated_list1};
-use nom::sequence::{delimited, preceded, terminated, tuple};
+use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
 
-pub(crate) fn parse_create_table_query(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match ws(parse_keyword(CREATE_TABLE))(query) {
+pub(crate) fn parse_create_keyspace_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match ws(parse_keyword(CREATE_KEYSPACE))(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse statement 'CREATE TABLE'".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "cannot parse statement 'CREATE KEYSPACE'", &[CREATE_KEYSPACE]))
     };
 
-    let (query, table) = match parse_identifier(query) {
+    let (query, if_not_exists) = match opt(parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF NOT EXISTS' modifier", &[IF_NOT_EXISTS]))
+    };
+
+    let (query, keyspace) = match parse_identifier(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse keyspace name", &["keyspace name"]))
+    };
+
+    let mut statement = CreateKeyspaceQuery { keyspace, if_not_exists, replication: None, durable_writes: None };
+
+    let query = match parse_keyword(WITH)(query) {
+        Ok((query, _)) => query,
+        Err(_) => {
+            expect_end(source, query)?;
+            return Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateKeyspace(statement)));
+        }
+    };
+
+    let (query, options) = match separated_list1(parse_keyword(AND), parse_keyspace_option)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse the keyspace options", &[REPLICATION, DURABLE_WRITES]))
+    };
+
+    expect_end(source, query)?;
+
+    for option in options {
+        match option {
+            KeyspaceOption::Replication(replication) => statement.replication = Some(replication),
+            KeyspaceOption::DurableWrites(enabled) => statement.durable_writes = Some(enabled),
+        }
+    }
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateKeyspace(statement)))
+}
+
+enum KeyspaceOption<'a> {
+    Replication(ReplicationOptions<'a>),
+    DurableWrites(bool),
+}
+
+fn parse_keyspace_option(query: &str) -> IResult<&str, KeyspaceOption<'_>> {
+    let replication = map(
+        preceded(
+            tuple((parse_keyword(REPLICATION), ws(tag("=")))),
+            map_res(parse_map, to_replication_options),
+        ),
+        KeyspaceOption::Replication,
+    );
+
+    let durable_writes = map(
+        preceded(
+            tuple((parse_keyword(DURABLE_WRITES), ws(tag("=")))),
+            alt((map(parse_keyword(TRUE), |_| true), map(parse_keyword(FALSE), |_| false))),
+        ),
+        KeyspaceOption::DurableWrites,
+    );
+
+    alt((replication, durable_writes))(query)
+}
+
+/// Accepts the strategy classes by their short and fully qualified names. Replication factors
+/// may be written as numbers or, as cqlsh prints them, as strings.
+fn to_replication_options<'a>(entries: Vec<(Cow<'a, str>, Value<'a>)>) -> Result<ReplicationOptions<'a>, String> {
+    let mut class = None;
+    let mut factors = Vec::new();
+
+    for (key, value) in entries {
+        match (key.as_ref(), value) {
+            ("class", Value::String(name)) => class = Some(name),
+            (_, Value::Integer(factor)) => factors.push((key, u32::try_from(factor).map_err(|_| format!("invalid replication factor {}", factor))?)),
+            (_, Value::String(factor)) => factors.push((key, factor.parse().map_err(|_| format!("invalid replication factor '{}'", factor))?)),
+            (key, _) => return Err(format!("invalid replication option {}", key)),
+        }
+    }
+
+    let class = class.ok_or_else(|| "the replication class isn't set".to_string())?;
+    let name = class.strip_prefix("org.apache.cassandra.locator.").unwrap_or(&class);
+
+    if name.eq_ignore_ascii_case("SimpleStrategy") {
+        match factors.as_slice() {
+            [(key, replication_factor)] if key == "replication_factor" => Ok(ReplicationOptions::Simple { replication_factor: *replication_factor }),
+            _ => Err("SimpleStrategy takes a single replication_factor option".to_string()),
+        }
+    } else if name.eq_ignore_ascii_case("NetworkTopologyStrategy") {
+        match factors.iter().find(|(key, _)| key == "replication_factor") {
+            Some(_) => Err("NetworkTopologyStrategy takes a replication factor per datacenter".to_string()),
+            None => Ok(ReplicationOptions::NetworkTopology { datacenters: factors }),
+        }
+    } else {
+        Err(format!("unsupported replication class {}", class))
+    }
+}
+
+pub(crate) fn parse_create_table_query<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let query = match ws(parse_keyword(CREATE_TABLE))(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse statement 'CREATE TABLE'", &[CREATE_TABLE]))
+    };
+
+    let (query, if_not_exists) = match opt(parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF NOT EXISTS' modifier", &[IF_NOT_EXISTS]))
+    };
+
+    let (query, (keyspace, table)) = match parse_table_name(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "cannot parse table name", &["table name"]))
     };
 
-    let (columns, primary_key) = if is_single_pk(query) {
+    let columns_offset = source.len() - query.len();
+
+    let (query, (definitions, primary_key)) = if is_single_pk(query) {
         match delimited(ws(tag("(")), parse_create_table_with_single_pk, ws(tag(")")))(query) {
-            Ok((_, (columns, primary_key))) => (columns, primary_key),
-            Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse the column definition with a simple primary key".to_string(), query.to_string()))
+            Ok(result) => result,
+            Err(error) => return Err(syntax_error(source, error, "cannot parse the column definition with a simple primary key", &["column definition"]))
         }
     } else {
         match delimited(ws(tag("(")), parse_create_table_with_composite_pk, ws(tag(")")))(query) {
-            Ok((_, (columns, primary_key))) => (columns, primary_key),
-            Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse the column definition with a composite primary key".to_string(), query.to_string()))
+            Ok(result) => result,
+            Err(error) => return Err(syntax_error(source, error, "cannot parse the column definition with a composite primary key", &["column definition", PRIMARY_KEY]))
         }
     };
 
+    let static_columns: Vec<Cow<str>> = definitions.iter().filter(|definition| definition.is_static).map(|definition| definition.column.name.clone()).collect();
+
+    if !has_valid_static_columns(&static_columns, &primary_key) {
+        let message = "static columns require clustering columns and can't be a part of the primary key".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, vec!["column definition".to_string()])));
+    }
+
+    if let Some(definition) = definitions.iter().find(|definition| has_constraints(definition) && !dialect.allows_column_constraints()) {
+        let message = format!("the dialect doesn't allow constraints on the column {}", definition.column.name);
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, Vec::new())));
+    }
+
+    if let Some(definition) = definitions.iter().find(|definition| !has_valid_default(definition, &primary_key)) {
+        let message = format!("the default value of the column {} must match its type and can't be set on a primary key or counter column", definition.column.name);
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, vec![DEFAULT.to_string()])));
+    }
+
+    if let Some(definition) = definitions.iter().find(|definition| !has_valid_check(definition)) {
+        let message = format!("the check of the column {} must refer to that column and no other", definition.column.name);
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, vec![CHECK.to_string()])));
+    }
+
+    let mut columns = Vec::with_capacity(definitions.len());
+    let mut defaults = Vec::new();
+    let mut constraints = Vec::new();
+
+    for definition in definitions {
+        let name = &definition.column.name;
+
+        if let Some(default) = definition.default {
+            defaults.push((name.clone(), default));
+        }
+
+        if definition.not_null {
+            constraints.push(ColumnConstraint::NotNull(name.clone()));
+        }
+
+        if let Some(check) = definition.check {
+            constraints.push(ColumnConstraint::Check(name.clone(), check));
+        }
+
+        if let Some((keyspace, table)) = definition.references {
+            constraints.push(ColumnConstraint::References { column: name.clone(), keyspace, table });
+        }
+
+        columns.push(definition.column);
+    }
+
+    if !has_valid_counters(&columns, &primary_key) {
+        let message = "counter columns can't be a part of the primary key or be mixed with other regular columns".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, vec!["column definition".to_string()])));
+    }
+
+    if !has_valid_collections(&columns, &primary_key) {
+        let message = "collection columns can't be a part of the primary key".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, columns_offset, message, vec!["column definition".to_string()])));
+    }
+
+    let (rest, options) = match parse_table_options(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse the table options", &["table option"]))
+    };
+
+    expect_end(source, rest)?;
+
+    let is_clustering_prefix = options.clustering_order.len() <= primary_key.clustering_key.len()
+        && options.clustering_order.iter().zip(&primary_key.clustering_key).all(|((column, _), key)| column == key);
+
+    if !is_clustering_prefix {
+        let offset = source.len() - query.len();
+        let message = "the clustering order must list the clustering columns in the primary key order".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, offset, message, vec![CLUSTERING_ORDER_BY.to_string()])));
+    }
+
     Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(CreateTableQuery {
+        keyspace,
         table,
         primary_key,
         columns,
+        static_columns,
+        defaults,
+        constraints,
+        if_not_exists,
+        options,
+    })))
+}
+
+pub(crate) fn parse_create_materialized_view_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(CREATE_MATERIALIZED_VIEW)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse statement 'CREATE MATERIALIZED VIEW'", &[CREATE_MATERIALIZED_VIEW]))
+    };
+
+    let (query, if_not_exists) = match opt(parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF NOT EXISTS' modifier", &[IF_NOT_EXISTS]))
+    };
+
+    let (query, (keyspace, view)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse view name", &["view name"]))
+    };
+
+    let query = match tuple((parse_keyword(AS), parse_keyword(SELECT)))(query) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'AS SELECT'", &[AS]))
+    };
+
+    let (query, columns) = match alt((map(ws(tag("*")), |_| Vec::new()), separated_list1(parse_comma, parse_identifier)))(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the column names or *", &["*", "column name"]))
+    };
+
+    let query = match parse_keyword(FROM)(query) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected the from keyword", &[FROM]))
+    };
+
+    let (query, (base_keyspace, base_table)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse base table name", &["table name"]))
+    };
+
+    let (query, conditions) = match parse_conditions(true)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing where condition", &["condition"]))
+    };
+
+    let key_offset = source.len() - query.len();
+
+    let (query, primary_key) = match preceded(parse_keyword(PRIMARY_KEY), parse_composite_pk)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the primary key of the view", &[PRIMARY_KEY]))
+    };
+
+    let (query, options) = match parse_table_options(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse the view options", &["table option"]))
+    };
+
+    expect_end(source, query)?;
+
+    let unrestricted_column = primary_key.partition_key.iter().chain(&primary_key.clustering_key).find(|column| {
+        !conditions.iter().any(|condition| condition.operator == Operator::IsNot && condition.restricted_column() == Some(column.as_ref()))
+            || !(columns.is_empty() || columns.contains(column))
+    });
+
+    if let Some(column) = unrestricted_column {
+        let message = format!("the primary key column {} of the view must be selected and restricted by IS NOT NULL", column);
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, key_offset, message, vec![PRIMARY_KEY.to_string()])));
+    }
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateMaterializedView(CreateMaterializedViewQuery {
+        keyspace,
+        view,
+        if_not_exists,
+        columns,
+        base_keyspace,
+        base_table,
+        conditions,
+        primary_key,
+        options,
+    })))
+}
+
+pub(crate) fn parse_create_function_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(CREATE_FUNCTION)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse statement 'CREATE FUNCTION'", &[CREATE_FUNCTION]))
+    };
+
+    let (query, if_not_exists) = match opt(parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF NOT EXISTS' modifier", &[IF_NOT_EXISTS]))
+    };
+
+    let (query, (keyspace, function)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse function name", &["function name"]))
+    };
+
+    let argument = map(tuple((parse_identifier, parse_column_type)), |(name, column_type)| Column { name, column_type });
+
+    let (query, arguments) = match delimited(ws(tag("(")), separated_list0(parse_comma, argument), ws(tag(")")))(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse the function arguments", &["argument definition"]))
+    };
+
+    let (query, return_type) = match preceded(parse_keyword(RETURNS), parse_column_type)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the return type", &[RETURNS]))
+    };
+
+    let query = match parse_keyword(AS)(query) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected the function body", &[AS]))
+    };
+
+    let body_offset = source.len() - query.len();
+
+    let (query, body) = match parse_string_literal(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected the function body", &["string literal"]))
+    };
+
+    expect_end(source, query)?;
+
+    if let Err(message) = validate_function_body(&body, &arguments) {
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, body_offset, message, vec!["expression".to_string()])));
+    }
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateFunction(CreateFunctionQuery {
+        keyspace,
+        function,
+        if_not_exists,
+        arguments,
+        return_type,
+        body,
     })))
 }
 
-pub(crate) fn parse_alter_table_query(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match ws(parse_keyword(ALTER_TABLE))(query) {
+pub(crate) fn parse_create_type_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(CREATE_TYPE)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse statement 'CREATE TYPE'", &[CREATE_TYPE]))
+    };
+
+    let (query, if_not_exists) = match opt(parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF NOT EXISTS' modifier", &[IF_NOT_EXISTS]))
+    };
+
+    let (query, (keyspace, name)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse type name", &["type name"]))
+    };
+
+    let fields_offset = source.len() - query.len();
+
+    let field = map(tuple((parse_identifier, parse_column_type)), |(name, column_type)| Column { name, column_type });
+
+    let (query, fields) = match delimited(ws(tag("(")), separated_list1(parse_comma, field), ws(tag(")")))(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse the type fields", &["field definition"]))
+    };
+
+    expect_end(source, query)?;
+
+    if let Err(message) = validate_type_fields(&fields) {
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, fields_offset, message, vec!["field definition".to_string()])));
+    }
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateType(CreateTypeQuery { keyspace, name, if_not_exists, fields })))
+}
+
+pub(crate) fn parse_alter_table_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match ws(parse_keyword(ALTER_TABLE))(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected 'DROP TABLE' statement".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected 'ALTER TABLE' statement", &[ALTER_TABLE]))
     };
 
-    let (query, table) = match parse_identifier(query) {
+    let (query, (keyspace, table)) = match parse_table_name(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "cannot parse table name", &["table name"]))
     };
 
-    let conditions = match parse_alter_table_condition(query) {
-        Ok((_, conditions)) => conditions,
-        Err(_) => todo!()
+    let (query, conditions) = match parse_alter_table_condition(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse alter table condition", &[ADD, DROP, RENAME, ALTER]))
     };
 
-    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::AlterTable(AlterTableQuery { table, conditions })))
+    expect_end(source, query)?;
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::AlterTable(AlterTableQuery { keyspace, table, conditions })))
 }
 
-pub(crate) fn parse_drop_table_query(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match ws(parse_keyword(DROP_TABLE))(query) {
+pub(crate) fn parse_drop_table_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match ws(parse_keyword(DROP_TABLE))(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected 'DROP TABLE' statement".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected 'DROP TABLE' statement", &[DROP_TABLE]))
+    };
+
+    let (query, if_exists) = match opt(parse_keyword(IF_EXISTS))(query) {
+        Ok((query, if_exists)) => (query, if_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "cannot parse 'IF EXISTS' modifier", &[IF_EXISTS]))
     };
 
-    let (_, table) = match parse_identifier(query) {
+    let (query, (keyspace, table)) = match parse_table_name(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("cannot parse table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "cannot parse table name", &["table name"]))
     };
 
-    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::DropTable(DropTableQuery { table })))
+    expect_end(source, query)?;
+
+    Ok(Query::DataDefinitionQuery(DataDefinitionQuery::DropTable(DropTableQuery { keyspace, table, if_exists })))
+}
+
+/// The body is an expression that may only refer to the arguments of the function.
+fn validate_function_body(body: &str, arguments: &[Column]) -> Result<(), String> {
+    let expression = match all_consuming(parse_expression)(body) {
+        Ok((_, expression)) => expression,
+        Err(_) => return Err("the function body must be an expression".to_string()),
+    };
+
+    if expression.has_placeholders() {
+        return Err("the function body can't contain placeholders".to_string());
+    }
+
+    match expression.columns().into_iter().find(|column| !arguments.iter().any(|argument| argument.name == *column)) {
+        Some(column) => Err(format!("the function body refers to {}, which isn't an argument", column)),
+        None => Ok(()),
+    }
+}
+
+fn validate_type_fields(fields: &[Column]) -> Result<(), String> {
+    for (index, field) in fields.iter().enumerate() {
+        if field.column_type == ColumnType::Counter {
+            return Err(format!("the field {} can't be a counter", field.name));
+        }
+
+        if fields[..index].iter().any(|other| other.name == field.name) {
+            return Err(format!("the field {} is defined more than once", field.name));
+        }
+    }
+
+    Ok(())
 }
 
 fn is_single_pk(query: &str) -> bool {
     tuple((tag("("), parse_identifier, parse_column_type, ws(tag(PRIMARY_KEY))))(query).is_ok()
 }
 
-fn parse_create_table_with_single_pk(query: &str) -> IResult<&str, (Vec<Column>, PrimaryKey)> {
+fn parse_create_table_with_single_pk(query: &str) -> IResult<&str, (Vec<ColumnDefinition<'_>>, PrimaryKey<'_>)> {
     let (query, (first_column, primary_key)) = map(
         tuple((parse_identifier, parse_column_type, parse_keyword(PRIMARY_KEY), opt(parse_comma))),
         |(column_name, column_type, _, _)| {
@@ -89,17 +491,11 @@ fn parse_create_table_with_single_pk(query: &str) -> IResult<&str, (Vec<Column>,
                 clustering_key: vec![],
             };
 
-            (column, primary_key)
+            (ColumnDefinition { column, is_static: false, default: None, not_null: false, check: None, references: None }, primary_key)
         },
     )(query)?;
 
-    let (query, mut other_columns) = separated_list0(
-        parse_comma,
-        map(
-            tuple((parse_identifier, parse_column_type)),
-            |(column_name, column_type)| Column { name: column_name, column_type },
-        ),
-    )(query)?;
+    let (query, mut other_columns) = separated_list0(parse_comma, parse_column_definition)(query)?;
 
     let mut columns = Vec::new();
     columns.push(first_column);
@@ -108,32 +504,55 @@ fn parse_create_table_with_single_pk(query: &str) -> IResult<&str, (Vec<Column>,
     Ok((query, (columns, primary_key)))
 }
 
-fn parse_create_table_with_composite_pk(query: &str) -> IResult<&str, (Vec<Column>, PrimaryKey)> {
-    let (query, columns) = terminated(
-        separated_list1(
-            parse_comma,
-            map(
-                tuple((parse_identifier, parse_column_type)),
-                |(column_name, column_type)| Column { name: column_name, column_type },
-            ),
-        ),
-        ws(tag(",")),
-    )(query)?;
+fn parse_create_table_with_composite_pk(query: &str) -> IResult<&str, (Vec<ColumnDefinition<'_>>, PrimaryKey<'_>)> {
+    let (query, columns) = terminated(separated_list1(parse_comma, parse_column_definition), ws(tag(",")))(query)?;
 
     let (query, _) = parse_keyword(PRIMARY_KEY)(query)?;
     let (query, primary_key) = parse_composite_pk(query)?;
     Ok((query, (columns, primary_key)))
 }
 
-fn parse_composite_pk(query: &str) -> IResult<&str, PrimaryKey> {
+/// A column with the modifiers declared after its type.
+struct ColumnDefinition<'a> {
+    column: Column<'a>,
+    is_static: bool,
+    default: Option<Value<'a>>,
+    not_null: bool,
+    check: Option<Condition<'a>>,
+    references: Option<(Option<Cow<'a, str>>, Cow<'a, str>)>,
+}
+
+fn parse_column_definition(query: &str) -> IResult<&str, ColumnDefinition<'_>> {
+    map(
+        tuple((
+            parse_identifier,
+            parse_column_type,
+            opt(parse_keyword(STATIC)),
+            opt(preceded(parse_keyword(DEFAULT), parse_value)),
+            opt(parse_keyword(NOT_NULL)),
+            opt(preceded(parse_keyword(CHECK), delimited(ws(tag("(")), parse_condition(true), ws(tag(")"))))),
+            opt(preceded(parse_keyword(REFERENCES), parse_table_name)),
+        )),
+        |(name, column_type, is_static, default, not_null, check, references)| ColumnDefinition {
+            column: Column { name, column_type },
+            is_static: is_static.is_some(),
+            default,
+            not_null: not_null.is_some(),
+            check,
+            references,
+        },
+    )(query)
+}
+
+fn parse_composite_pk(query: &str) -> IResult<&str, PrimaryKey<'_>> {
     delimited(
         ws(tag("(")),
         alt((
             map(tuple((
                 delimited(ws(tag("(")), separated_list1(parse_comma, map(parse_identifier, |column_name| column_name)), ws(tag(")"))),
-                preceded(ws(tag(",")), separated_list0(parse_comma, map(parse_identifier, |column_name| column_name))))
+                opt(preceded(ws(tag(",")), separated_list0(parse_comma, map(parse_identifier, |column_name| column_name)))))
             ),
-                |(partition_key, clustering_key), | PrimaryKey { partition_key, clustering_key },
+                |(partition_key, clustering_key), | PrimaryKey { partition_key, clustering_key: clustering_key.unwrap_or_default() },
             ),
             map(
                 separated_list1(parse_comma, map(parse_identifier, |column_name| column_name)),
@@ -150,14 +569,217 @@ fn parse_composite_pk(query: &str) -> IResult<&str, PrimaryKey> {
     )(query)
 }
 
-fn parse_alter_table_condition(query: &str) -> IResult<&str, Vec<AlterTableCondition>> {
+/// A table either has no counters or has only counters outside of its primary key.
+fn has_valid_counters(columns: &[Column], primary_key: &PrimaryKey) -> bool {
+    let is_key = |column: &Column| primary_key.partition_key.contains(&column.name) || primary_key.clustering_key.contains(&column.name);
+    let is_counter = |column: &Column| column.column_type == ColumnType::Counter;
+
+    if !columns.iter().any(is_counter) {
+        return true;
+    }
+
+    columns.iter().all(|column| is_key(column) != is_counter(column))
+}
+
+/// Collections are updated element by element, which a key column can't be.
+fn has_valid_collections(columns: &[Column], primary_key: &PrimaryKey) -> bool {
+    columns.iter()
+        .filter(|column| matches!(column.column_type, ColumnType::List(_) | ColumnType::Set(_) | ColumnType::Map(..)))
+        .all(|column| !primary_key.partition_key.contains(&column.name) && !primary_key.clustering_key.contains(&column.name))
+}
+
+/// A static column belongs to a partition, so the table needs clustering columns to
+/// share it between and the column can't be a part of the key itself.
+fn has_valid_static_columns(static_columns: &[Cow<str>], primary_key: &PrimaryKey) -> bool {
+    if static_columns.is_empty() {
+        return true;
+    }
+
+    !primary_key.clustering_key.is_empty()
+        && static_columns.iter().all(|column| !primary_key.partition_key.contains(column) && !primary_key.clustering_key.contains(column))
+}
+
+fn has_constraints(definition: &ColumnDefinition) -> bool {
+    definition.default.is_some() || definition.not_null || definition.check.is_some() || definition.references.is_some()
+}
+
+/// A default fills in a regular column, so it has to be a value of the column type
+/// and counters, which only change by increments, can't have one.
+fn has_valid_default(definition: &ColumnDefinition, primary_key: &PrimaryKey) -> bool {
+    let column = &definition.column;
+
+    match &definition.default {
+        None => true,
+        Some(default) => {
+            column.column_type != ColumnType::Counter
+                && !primary_key.partition_key.contains(&column.name)
+                && !primary_key.clustering_key.contains(&column.name)
+                && column.column_type.accepts(default)
+        }
+    }
+}
+
+/// A check is evaluated against the row being written, so it can only refer to its own column.
+fn has_valid_check(definition: &ColumnDefinition) -> bool {
+    match &definition.check {
+        None => true,
+        Some(check) => {
+            let columns = check.columns();
+
+            !columns.is_empty()
+                && columns.iter().all(|column| *column == definition.column.name)
+                && !check.left.has_placeholders()
+                && !check.right.has_placeholders()
+        }
+    }
+}
+
+enum TableOption<'a> {
+    Compression(CompressionOptions),
+    Compaction(CompactionOptions<'a>),
+    DefaultTimeToLive(u32),
+    GcGraceSeconds(u32),
+    ValidateReferences(bool),
+    ClusteringOrder(Vec<(Cow<'a, str>, ClusteringOrder)>),
+}
+
+fn parse_table_options(query: &str) -> IResult<&str, TableOptions<'_>> {
+    let mut options = TableOptions::default();
+
+    let query = match parse_keyword(WITH)(query) {
+        Ok((query, _)) => query,
+        Err(_) => return Ok((query, options))
+    };
+
+    let (query, parsed_options) = separated_list1(parse_keyword(AND), parse_table_option)(query)?;
+
+    for option in parsed_options {
+        match option {
+            TableOption::Compression(compression) => options.compression = Some(compression),
+            TableOption::Compaction(compaction) => options.compaction = Some(compaction),
+            TableOption::DefaultTimeToLive(seconds) => options.default_time_to_live = Some(seconds),
+            TableOption::GcGraceSeconds(seconds) => options.gc_grace_seconds = Some(seconds),
+            TableOption::ValidateReferences(enabled) => options.validate_references = Some(enabled),
+            TableOption::ClusteringOrder(clustering_order) => options.clustering_order = clustering_order,
+        }
+    }
+
+    Ok((query, options))
+}
+
+fn parse_table_option(query: &str) -> IResult<&str, TableOption<'_>> {
+    let compression = map(
+        preceded(
+            tuple((parse_keyword(COMPRESSION), ws(tag("=")))),
+            map_res(parse_map, to_compression_options),
+        ),
+        TableOption::Compression,
+    );
+
+    let compaction = map(
+        preceded(
+            tuple((parse_keyword(COMPACTION), ws(tag("=")))),
+            map_res(parse_map, to_compaction_options),
+        ),
+        TableOption::Compaction,
+    );
+
+    let default_time_to_live = map(
+        preceded(tuple((parse_keyword(DEFAULT_TIME_TO_LIVE), ws(tag("=")))), ws(parse_u32)),
+        TableOption::DefaultTimeToLive,
+    );
+
+    let gc_grace_seconds = map(
+        preceded(tuple((parse_keyword(GC_GRACE_SECONDS), ws(tag("=")))), ws(parse_u32)),
+        TableOption::GcGraceSeconds,
+    );
+
+    let validate_references = map(
+        preceded(
+            tuple((parse_keyword(VALIDATE_REFERENCES), ws(tag("=")))),
+            alt((map(parse_keyword(TRUE), |_| true), map(parse_keyword(FALSE), |_| false))),
+        ),
+        TableOption::ValidateReferences,
+    );
+
+    let order = alt((
+        map(parse_keyword(ASC), |_| ClusteringOrder::Asc),
+        map(parse_keyword(DESC), |_| ClusteringOrder::Desc),
+    ));
+
+    let clustering_order = map(
+        preceded(
+            parse_keyword(CLUSTERING_ORDER_BY),
+            delimited(ws(tag("(")), separated_list1(ws(tag(",")), tuple((parse_identifier, order))), ws(tag(")"))),
+        ),
+        TableOption::ClusteringOrder,
+    );
+
+    alt((compression, compaction, default_time_to_live, gc_grace_seconds, validate_references, clustering_order))(query)
+}
+
+fn to_compression_options(entries: Vec<(Cow<'_, str>, Value<'_>)>) -> Result<CompressionOptions, String> {
+    let mut codec = None;
+    let mut chunk_length_in_kb = None;
+
+    for (key, value) in entries {
+        match (key.as_ref(), value) {
+            ("class", Value::String(class)) => {
+                codec = Some(match class.to_ascii_lowercase().as_str() {
+                    "lz4" | "lz4compressor" => CompressionCodec::Lz4,
+                    "snappy" | "snappycompressor" => CompressionCodec::Snappy,
+                    "zstd" | "zstdcompressor" => CompressionCodec::Zstd,
+                    _ => return Err(format!("unsupported compression class {}", class)),
+                });
+            }
+            ("enabled", Value::Bool(false)) => codec = Some(CompressionCodec::None),
+            ("enabled", Value::Bool(true)) => {}
+            ("chunk_length_in_kb", Value::Integer(length)) => {
+                chunk_length_in_kb = Some(u32::try_from(length).map_err(|_| format!("invalid chunk length {}", length))?);
+            }
+            (key, _) => return Err(format!("unsupported compression option {}", key)),
+        }
+    }
+
+    match codec {
+        Some(codec) => Ok(CompressionOptions { codec, chunk_length_in_kb }),
+        None => Err("the compression class isn't set".to_string()),
+    }
+}
+
+/// Keeps the options other than the class as they are, since each strategy has its own set.
+fn to_compaction_options<'a>(entries: Vec<(Cow<'a, str>, Value<'a>)>) -> Result<CompactionOptions<'a>, String> {
+    let mut strategy = None;
+    let mut options = Vec::new();
+
+    for (key, value) in entries {
+        match (key.as_ref(), value) {
+            ("class", Value::String(class)) => {
+                strategy = Some(match class.to_ascii_lowercase().as_str() {
+                    "sizetieredcompactionstrategy" => CompactionStrategy::SizeTiered,
+                    "leveledcompactionstrategy" => CompactionStrategy::Leveled,
+                    "timewindowcompactionstrategy" => CompactionStrategy::TimeWindow,
+                    _ => return Err(format!("unsupported compaction class {}", class)),
+                });
+            }
+            (_, value) => options.push((key, value)),
+        }
+    }
+
+    match strategy {
+        Some(strategy) => Ok(CompactionOptions { strategy, options }),
+        None => Err("the compaction class isn't set".to_string()),
+    }
+}
+
+fn parse_alter_table_condition(query: &str) -> IResult<&str, Vec<AlterTableCondition<'_>>> {
     map(
-        separated_list0(ws(tag(",")), alt((parse_add_column, parse_drop_column))),
+        separated_list0(ws(tag(",")), alt((parse_add_column, parse_drop_column, parse_rename_column, parse_alter_column_type))),
         |conditions| conditions.into_iter().flatten().collect(),
     )(query)
 }
 
-fn parse_add_column(query: &str) -> IResult<&str, Vec<AlterTableCondition>> {
+fn parse_add_column(query: &str) -> IResult<&str, Vec<AlterTableCondition<'_>>> {
     let single_add_parser = map(
         tuple((parse_identifier, parse_column_type)), |(column_name, column_type)| {
             vec![AlterTableCondition::AddColumn(AddColumnCondition { column_name, column_type })]
@@ -179,7 +801,7 @@ fn parse_add_column(query: &str) -> IResult<&str, Vec<AlterTableCondition>> {
     preceded(ws(tag_no_case(ADD)), alt((single_add_parser, multi_add_parser)))(query)
 }
 
-fn parse_drop_column(query: &str) -> IResult<&str, Vec<AlterTableCondition>> {
+fn parse_drop_column(query: &str) -> IResult<&str, Vec<AlterTableCondition<'_>>> {
     let single_delete_parser = map(
         parse_identifier,
         |column_name| vec![AlterTableCondition::DropColumn(DropColumnCondition { column_name })],
@@ -197,8 +819,35 @@ fn parse_drop_column(query: &str) -> IResult<&str, Vec<AlterTableCondition>> {
     preceded(ws(tag_no_case(DROP)), alt((single_delete_parser, multi_delete_parser)))(query)
 }
 
-fn parse_column_type(query: &str) -> IResult<&str, ColumnType> {
+fn parse_rename_column(query: &str) -> IResult<&str, Vec<AlterTableCondition<'_>>> {
+    map(
+        preceded(
+            parse_keyword(RENAME),
+            tuple((parse_identifier, preceded(parse_keyword(TO), parse_identifier))),
+        ),
+        |(old_name, new_name)| vec![AlterTableCondition::RenameColumn(RenameColumnCondition { old_name, new_name })],
+    )(query)
+}
+
+fn parse_alter_column_type(query: &str) -> IResult<&str, Vec<AlterTableCondition<'_>>> {
+    map(
+        preceded(
+            parse_keyword(ALTER),
+            tuple((parse_identifier, preceded(parse_keyword(TYPE), parse_column_type))),
+        ),
+        |(column_name, column_type)| vec![AlterTableCondition::AlterColumnType(AlterColumnTypeCondition { column_name, column_type })],
+    )(query)
+}
+
+fn parse_column_type(query: &str) -> IResult<&str, ColumnType<'_>> {
     alt((
+        map(preceded(parse_keyword(FROZEN), delimited(ws(tag("<")), parse_identifier, ws(tag(">")))), ColumnType::Frozen),
+        map(preceded(parse_keyword(LIST), delimited(ws(tag("<")), parse_column_type, ws(tag(">")))), |element| ColumnType::List(Box::new(element))),
+        map(preceded(parse_keyword(SET), delimited(ws(tag("<")), parse_column_type, ws(tag(">")))), |element| ColumnType::Set(Box::new(element))),
+        map(
+            preceded(parse_keyword(MAP), delimited(ws(tag("<")), separated_pair(parse_column_type, parse_comma, parse_column_type), ws(tag(">")))),
+            |(key, value)| ColumnType::Map(Box::new(key), Box::new(value)),
+        ),
         map(parse_keyword(UUID), |_| ColumnType::Uuid),
         map(parse_keyword(INT), |_| ColumnType::Int),
         map(parse_keyword(LONG), |_| ColumnType::Long),
@@ -207,12 +856,14 @@ fn parse_column_type(query: &str) -> IResult<&str, ColumnType> {
         map(parse_keyword(TIMESTAMP), |_| ColumnType::Timestamp),
         map(parse_keyword(TEXT), |_| ColumnType::Text),
         map(parse_keyword(BOOL), |_| ColumnType::Bool),
+        map(parse_keyword(COUNTER), |_| ColumnType::Counter),
     ))(query)
 }
 
 #[cfg(test)]
 mod test {
     use crate::query_parser::parser::parse_query;
+    use crate::query_parser::query::Expression;
     use super::*;
 
     #[test]
@@ -221,123 +872,300 @@ mod test {
             (
                 "CREATE TABLE products (title TEXT PRIMARY KEY, price DOUBLE, quantity INT)",
                 CreateTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     primary_key: PrimaryKey {
-                        partition_key: vec!["title".to_string()],
+                        partition_key: vec!["title".into()],
                         clustering_key: vec![]
                     },
                     columns: vec![
                         Column {
-                            name: "title".to_string(),
+                            name: "title".into(),
                             column_type: ColumnType::Text,
                         },
                         Column {
-                            name: "price".to_string(),
+                            name: "price".into(),
                             column_type: ColumnType::Double,
                         },
                         Column {
-                            name: "quantity".to_string(),
+                            name: "quantity".into(),
                             column_type: ColumnType::Int,
                         }
                     ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions::default(),
+                }
+            ),
+            (
+                "CREATE TABLE Sensors (sensor1 UUID PRIMARY KEY, \"Reading\" DOUBLE)",
+                CreateTableQuery {
+                    keyspace: None,
+                    table: "sensors".into(),
+                    primary_key: PrimaryKey {
+                        partition_key: vec!["sensor1".into()],
+                        clustering_key: vec![]
+                    },
+                    columns: vec![
+                        Column {
+                            name: "sensor1".into(),
+                            column_type: ColumnType::Uuid,
+                        },
+                        Column {
+                            name: "Reading".into(),
+                            column_type: ColumnType::Double,
+                        }
+                    ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions::default(),
                 }
             ),
             (
                 "CREATE TABLE products (title TEXT, price DOUBLE, quantity INT, PRIMARY KEY (title))",
                 CreateTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     primary_key: PrimaryKey {
-                        partition_key: vec!["title".to_string()],
+                        partition_key: vec!["title".into()],
                         clustering_key: vec![]
                     },
                     columns: vec![
                         Column {
-                            name: "title".to_string(),
+                            name: "title".into(),
                             column_type: ColumnType::Text,
                         },
                         Column {
-                            name: "price".to_string(),
+                            name: "price".into(),
                             column_type: ColumnType::Double,
                         },
                         Column {
-                            name: "quantity".to_string(),
+                            name: "quantity".into(),
                             column_type: ColumnType::Int,
                         }
                     ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions::default(),
                 }
             ),
             (
                 "CREATE TABLE user_sessions (user_id UUID, session_id UUID, timestamp TIMESTAMP, device_type TEXT, PRIMARY KEY ((user_id, session_id), timestamp))",
                 CreateTableQuery {
-                    table: "user_sessions".to_string(),
+                    keyspace: None,
+                    table: "user_sessions".into(),
                     primary_key: PrimaryKey {
                         partition_key: vec![
-                            "user_id".to_string(),
-                            "session_id".to_string(),
+                            "user_id".into(),
+                            "session_id".into(),
                         ],
                         clustering_key: vec![
-                            "timestamp".to_string()
+                            "timestamp".into()
                         ]
                     },
                     columns: vec![
                         Column {
-                            name: "user_id".to_string(),
+                            name: "user_id".into(),
                             column_type: ColumnType::Uuid,
                         },
                         Column {
-                            name: "session_id".to_string(),
+                            name: "session_id".into(),
                             column_type: ColumnType::Uuid,
                         },
                         Column {
-                            name: "timestamp".to_string(),
+                            name: "timestamp".into(),
                             column_type: ColumnType::Timestamp,
                         },
                         Column {
-                            name: "device_type".to_string(),
+                            name: "device_type".into(),
                             column_type: ColumnType::Text,
                         },
                     ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions::default(),
                 }
             ),
             (
                 "CREATE TABLE posts (user_id UUID, blog_id UUID, post_id UUID, created_at TIMESTAMP, content TEXT, seen LONG, PRIMARY KEY (user_id, blog_id, post_id))",
                 CreateTableQuery {
-                    table: "posts".to_string(),
+                    keyspace: None,
+                    table: "posts".into(),
                     primary_key: PrimaryKey {
                         partition_key: vec![
-                            "user_id".to_string()
+                            "user_id".into()
                         ],
                         clustering_key: vec![
-                            "blog_id".to_string(),
-                            "post_id".to_string(),
+                            "blog_id".into(),
+                            "post_id".into(),
                         ]
                     },
                     columns: vec![
                         Column {
-                            name: "user_id".to_string(),
+                            name: "user_id".into(),
                             column_type: ColumnType::Uuid,
                         },
                         Column {
-                            name: "blog_id".to_string(),
+                            name: "blog_id".into(),
                             column_type: ColumnType::Uuid,
                         },
                         Column {
-                            name: "post_id".to_string(),
+                            name: "post_id".into(),
                             column_type: ColumnType::Uuid,
                         },
                         Column {
-                            name: "created_at".to_string(),
+                            name: "created_at".into(),
                             column_type: ColumnType::Timestamp,
                         },
                         Column {
-                            name: "content".to_string(),
+                            name: "content".into(),
                             column_type: ColumnType::Text,
                         },
                         Column {
-                            name: "seen".to_string(),
+                            name: "seen".into(),
                             column_type: ColumnType::Long,
                         },
                     ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions::default(),
+                }),
+            (
+                "CREATE TABLE IF NOT EXISTS products (title TEXT PRIMARY KEY, price DOUBLE)",
+                CreateTableQuery {
+                    keyspace: None,
+                    table: "products".into(),
+                    primary_key: PrimaryKey {
+                        partition_key: vec!["title".into()],
+                        clustering_key: vec![]
+                    },
+                    columns: vec![
+                        Column {
+                            name: "title".into(),
+                            column_type: ColumnType::Text,
+                        },
+                        Column {
+                            name: "price".into(),
+                            column_type: ColumnType::Double,
+                        },
+                    ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: true,
+                    options: TableOptions::default(),
+                }),
+            (
+                "CREATE TABLE readings (sensor_id UUID PRIMARY KEY, value DOUBLE) WITH compression = {'class': 'LZ4Compressor', 'chunk_length_in_kb': 64}",
+                CreateTableQuery {
+                    keyspace: None,
+                    table: "readings".into(),
+                    primary_key: PrimaryKey {
+                        partition_key: vec!["sensor_id".into()],
+                        clustering_key: vec![]
+                    },
+                    columns: vec![
+                        Column {
+                            name: "sensor_id".into(),
+                            column_type: ColumnType::Uuid,
+                        },
+                        Column {
+                            name: "value".into(),
+                            column_type: ColumnType::Double,
+                        },
+                    ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions {
+                        compression: Some(CompressionOptions {
+                            codec: CompressionCodec::Lz4,
+                            chunk_length_in_kb: Some(64),
+                        }),
+                        ..TableOptions::default()
+                    },
+                }),
+            (
+                "CREATE TABLE readings (sensor_id UUID PRIMARY KEY, value DOUBLE) WITH compression = {'enabled': false}",
+                CreateTableQuery {
+                    keyspace: None,
+                    table: "readings".into(),
+                    primary_key: PrimaryKey {
+                        partition_key: vec!["sensor_id".into()],
+                        clustering_key: vec![]
+                    },
+                    columns: vec![
+                        Column {
+                            name: "sensor_id".into(),
+                            column_type: ColumnType::Uuid,
+                        },
+                        Column {
+                            name: "value".into(),
+                            column_type: ColumnType::Double,
+                        },
+                    ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions {
+                        compression: Some(CompressionOptions {
+                            codec: CompressionCodec::None,
+                            chunk_length_in_kb: None,
+                        }),
+                        ..TableOptions::default()
+                    },
+                }),
+            (
+                "CREATE TABLE readings (sensor_id UUID, day TIMESTAMP, time TIMESTAMP, value DOUBLE, PRIMARY KEY (sensor_id, day, time)) WITH CLUSTERING ORDER BY (day DESC, time asc) AND compression = {'enabled': false}",
+                CreateTableQuery {
+                    keyspace: None,
+                    table: "readings".into(),
+                    primary_key: PrimaryKey {
+                        partition_key: vec!["sensor_id".into()],
+                        clustering_key: vec!["day".into(), "time".into()]
+                    },
+                    columns: vec![
+                        Column {
+                            name: "sensor_id".into(),
+                            column_type: ColumnType::Uuid,
+                        },
+                        Column {
+                            name: "day".into(),
+                            column_type: ColumnType::Timestamp,
+                        },
+                        Column {
+                            name: "time".into(),
+                            column_type: ColumnType::Timestamp,
+                        },
+                        Column {
+                            name: "value".into(),
+                            column_type: ColumnType::Double,
+                        },
+                    ],
+                    static_columns: vec![],
+                    defaults: vec![],
+                    constraints: vec![],
+                    if_not_exists: false,
+                    options: TableOptions {
+                        compression: Some(CompressionOptions {
+                            codec: CompressionCodec::None,
+                            chunk_length_in_kb: None,
+                        }),
+                        clustering_order: vec![("day".into(), ClusteringOrder::Desc), ("time".into(), ClusteringOrder::Asc)],
+                        ..TableOptions::default()
+                    },
                 })
         ];
 
@@ -346,16 +1174,244 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_create_table_with_storage_options() {
+        let query = "CREATE TABLE readings (sensor_id UUID PRIMARY KEY) WITH compaction = {'class': 'LeveledCompactionStrategy', 'sstable_size_in_mb': 160} AND default_time_to_live = 86400 AND gc_grace_seconds = 3600";
+
+        let options = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table.options,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        let expected_result = TableOptions {
+            compaction: Some(CompactionOptions {
+                strategy: CompactionStrategy::Leveled,
+                options: vec![("sstable_size_in_mb".into(), Value::Integer(160))],
+            }),
+            default_time_to_live: Some(86400),
+            gc_grace_seconds: Some(3600),
+            ..TableOptions::default()
+        };
+
+        assert_eq!(options, expected_result);
+        assert!(parse_query("CREATE TABLE readings (sensor_id UUID PRIMARY KEY) WITH compaction = {'min_threshold': 4}").is_err());
+        assert!(parse_query("CREATE TABLE readings (sensor_id UUID PRIMARY KEY) WITH gc_grace_seconds = -1").is_err());
+    }
+
+    #[test]
+    fn test_create_table_with_counters() {
+        let query = "CREATE TABLE page_views (page TEXT, day TIMESTAMP, hits COUNTER, PRIMARY KEY (page, day))";
+
+        let columns = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table.columns,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        assert_eq!(columns[2], Column { name: "hits".into(), column_type: ColumnType::Counter });
+
+        let params = vec![
+            "CREATE TABLE page_views (page TEXT PRIMARY KEY, hits COUNTER, title TEXT)",
+            "CREATE TABLE page_views (page COUNTER PRIMARY KEY, hits COUNTER)",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_collections() {
+        let query = "CREATE TABLE users (user_id UUID PRIMARY KEY, emails LIST<TEXT>, tags set<text>, visits MAP<TIMESTAMP, LIST<INT>>)";
+
+        let columns = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table.columns,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        assert_eq!(columns[1].column_type, ColumnType::List(Box::new(ColumnType::Text)));
+        assert_eq!(columns[2].column_type, ColumnType::Set(Box::new(ColumnType::Text)));
+        assert_eq!(columns[3].column_type, ColumnType::Map(Box::new(ColumnType::Timestamp), Box::new(ColumnType::List(Box::new(ColumnType::Int)))));
+
+        let params = vec![
+            "CREATE TABLE users (tags SET<TEXT> PRIMARY KEY, name TEXT)",
+            "CREATE TABLE users (user_id UUID, tags SET<TEXT>, PRIMARY KEY (user_id, tags))",
+            "CREATE TABLE users (user_id UUID PRIMARY KEY, visits MAP<TEXT>)",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_static_columns() {
+        let query = "CREATE TABLE orders (customer_id UUID, order_id UUID, email TEXT STATIC, total DOUBLE, PRIMARY KEY (customer_id, order_id))";
+
+        let table = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        assert_eq!(table.static_columns, vec![Cow::from("email")]);
+        assert_eq!(table.columns[2], Column { name: "email".into(), column_type: ColumnType::Text });
+
+        let params = vec![
+            "CREATE TABLE orders (customer_id UUID PRIMARY KEY, email TEXT STATIC)",
+            "CREATE TABLE orders (customer_id UUID, email TEXT STATIC, PRIMARY KEY ((customer_id)))",
+            "CREATE TABLE orders (customer_id UUID, order_id UUID STATIC, PRIMARY KEY (customer_id, order_id))",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_defaults() {
+        let query = "CREATE TABLE products (shop_id UUID, product_id UUID, price DOUBLE DEFAULT 0.0, stock INT STATIC DEFAULT 0, name TEXT, PRIMARY KEY (shop_id, product_id))";
+
+        let table = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        assert_eq!(table.defaults, vec![(Cow::from("price"), Value::Float(0.0)), (Cow::from("stock"), Value::Integer(0))]);
+        assert_eq!(table.static_columns, vec![Cow::from("stock")]);
+
+        let params = vec![
+            "CREATE TABLE products (product_id UUID, price DOUBLE DEFAULT 'free', PRIMARY KEY (product_id))",
+            "CREATE TABLE products (product_id UUID, stock INT DEFAULT 3000000000, PRIMARY KEY (product_id))",
+            "CREATE TABLE products (product_id UUID DEFAULT 1, price DOUBLE, PRIMARY KEY (product_id))",
+            "CREATE TABLE page_views (page TEXT, hits COUNTER DEFAULT 0, PRIMARY KEY (page))",
+            "CREATE TABLE products (product_id UUID, tags LIST<TEXT> DEFAULT ['new'], PRIMARY KEY (product_id))",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_constraints() {
+        let query = "CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE NOT NULL CHECK (price > 0.0), name TEXT NOT NULL)";
+
+        let table = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        let check = Condition::new(Expression::Column("price".into()), Operator::Greater, Expression::Literal(Value::Float(0.0)));
+
+        assert_eq!(table.constraints, vec![
+            ColumnConstraint::NotNull("price".into()),
+            ColumnConstraint::Check("price".into(), check),
+            ColumnConstraint::NotNull("name".into()),
+        ]);
+
+        let params = vec![
+            "CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE CHECK (stock > 0), stock INT)",
+            "CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE CHECK (price > ?))",
+            "CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE CHECK (1 > 0))",
+            "CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE CHECK price > 0)",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_references() {
+        let query = "CREATE TABLE orders (order_id UUID PRIMARY KEY, customer_id UUID REFERENCES shop.customers, coupon TEXT REFERENCES coupons) WITH validate_references = true";
+
+        let table = match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table,
+            result => panic!("expected a create table query, got {:?}", result),
+        };
+
+        assert_eq!(table.constraints, vec![
+            ColumnConstraint::References { column: "customer_id".into(), keyspace: Some("shop".into()), table: "customers".into() },
+            ColumnConstraint::References { column: "coupon".into(), keyspace: None, table: "coupons".into() },
+        ]);
+        assert_eq!(table.options.validate_references, Some(true));
+
+        assert!(parse_query("CREATE TABLE orders (order_id UUID PRIMARY KEY, customer_id UUID REFERENCES)").is_err());
+        assert!(parse_query("CREATE TABLE orders (order_id UUID PRIMARY KEY) WITH validate_references = 1").is_err());
+    }
+
+    #[test]
+    fn test_create_materialized_view() {
+        let query = r#"
+        CREATE MATERIALIZED VIEW IF NOT EXISTS analytics.sessions_by_device AS
+        SELECT user_id, device_type, timestamp FROM user_sessions
+        WHERE device_type IS NOT NULL AND user_id IS NOT NULL
+        PRIMARY KEY (device_type, user_id)
+        WITH CLUSTERING ORDER BY (user_id DESC)
+        "#;
+
+        let condition = |column: &'static str| Condition::new(Expression::Column(column.into()), Operator::IsNot, Expression::Literal(Value::Null));
+
+        let expected_result = CreateMaterializedViewQuery {
+            keyspace: Some("analytics".into()),
+            view: "sessions_by_device".into(),
+            if_not_exists: true,
+            columns: vec!["user_id".into(), "device_type".into(), "timestamp".into()],
+            base_keyspace: None,
+            base_table: "user_sessions".into(),
+            conditions: vec![condition("device_type"), condition("user_id")],
+            primary_key: PrimaryKey {
+                partition_key: vec!["device_type".into()],
+                clustering_key: vec!["user_id".into()],
+            },
+            options: TableOptions {
+                clustering_order: vec![("user_id".into(), ClusteringOrder::Desc)],
+                ..TableOptions::default()
+            },
+        };
+
+        assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateMaterializedView(expected_result))));
+
+        let params = vec![
+            "CREATE MATERIALIZED VIEW v AS SELECT * FROM t WHERE a IS NOT NULL PRIMARY KEY (a, b)",
+            "CREATE MATERIALIZED VIEW v AS SELECT a FROM t WHERE a IS NOT NULL AND b IS NOT NULL PRIMARY KEY (a, b)",
+            "CREATE MATERIALIZED VIEW v AS SELECT * FROM t WHERE a IS NOT NULL",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_invalid_clustering_order() {
+        let params = vec![
+            "CREATE TABLE readings (sensor_id UUID, day TIMESTAMP, time TIMESTAMP, PRIMARY KEY (sensor_id, day, time)) WITH CLUSTERING ORDER BY (time DESC)",
+            "CREATE TABLE readings (sensor_id UUID, day TIMESTAMP, PRIMARY KEY (sensor_id, day)) WITH CLUSTERING ORDER BY (sensor_id DESC)",
+            "CREATE TABLE readings (sensor_id UUID, day TIMESTAMP, PRIMARY KEY (sensor_id, day)) WITH CLUSTERING ORDER BY (day SIDEWAYS)",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_unsupported_compression() {
+        let query = "CREATE TABLE readings (sensor_id UUID PRIMARY KEY) WITH compression = {'class': 'BrotliCompressor'}";
+        assert!(parse_query(query).is_err());
+    }
+
     #[test]
     fn test_alter_table() {
         let params = vec![
             (
                 "ALTER TABLE products ADD description TEXT",
                 AlterTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     conditions: vec![
                         AlterTableCondition::AddColumn(AddColumnCondition {
-                            column_name: "description".to_string(),
+                            column_name: "description".into(),
                             column_type: ColumnType::Text,
                         })
                     ]
@@ -364,14 +1420,15 @@ mod test {
             (
                 "ALTER TABLE products ADD (description TEXT, price DOUBLE)",
                 AlterTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     conditions: vec![
                         AlterTableCondition::AddColumn(AddColumnCondition {
-                            column_name: "description".to_string(),
+                            column_name: "description".into(),
                             column_type: ColumnType::Text,
                         }),
                         AlterTableCondition::AddColumn(AddColumnCondition {
-                            column_name: "price".to_string(),
+                            column_name: "price".into(),
                             column_type: ColumnType::Double,
                         })
                     ]
@@ -380,10 +1437,11 @@ mod test {
             (
                 "ALTER TABLE products DROP description",
                 AlterTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     conditions: vec![
                         AlterTableCondition::DropColumn(DropColumnCondition {
-                            column_name: "description".to_string()
+                            column_name: "description".into()
                         })
                     ]
                 }
@@ -391,13 +1449,31 @@ mod test {
             (
                 "ALTER TABLE products DROP (description, price)",
                 AlterTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     conditions: vec![
                         AlterTableCondition::DropColumn(DropColumnCondition {
-                            column_name: "description".to_string(),
+                            column_name: "description".into(),
                         }),
                         AlterTableCondition::DropColumn(DropColumnCondition {
-                            column_name: "price".to_string(),
+                            column_name: "price".into(),
+                        })
+                    ]
+                }
+            ),
+            (
+                "ALTER TABLE products RENAME description TO summary, ALTER price TYPE DOUBLE",
+                AlterTableQuery {
+                    keyspace: None,
+                    table: "products".into(),
+                    conditions: vec![
+                        AlterTableCondition::RenameColumn(RenameColumnCondition {
+                            old_name: "description".into(),
+                            new_name: "summary".into(),
+                        }),
+                        AlterTableCondition::AlterColumnType(AlterColumnTypeCondition {
+                            column_name: "price".into(),
+                            column_type: ColumnType::Double,
                         })
                     ]
                 }
@@ -405,14 +1481,15 @@ mod test {
             (
                 "ALTER TABLE products ADD description TEXT, DROP crated_at",
                 AlterTableQuery {
-                    table: "products".to_string(),
+                    keyspace: None,
+                    table: "products".into(),
                     conditions: vec![
                         AlterTableCondition::AddColumn(AddColumnCondition {
-                            column_name: "description".to_string(),
+                            column_name: "description".into(),
                             column_type: ColumnType::Text,
                         }),
                         AlterTableCondition::DropColumn(DropColumnCondition {
-                            column_name: "crated_at".to_string(),
+                            column_name: "crated_at".into(),
                         })
                     ]
                 }
@@ -424,10 +1501,135 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_create_keyspace() {
+        let params = vec![
+            (
+                "CREATE KEYSPACE analytics",
+                CreateKeyspaceQuery { keyspace: "analytics".into(), if_not_exists: false, replication: None, durable_writes: None }
+            ),
+            (
+                "CREATE KEYSPACE IF NOT EXISTS analytics",
+                CreateKeyspaceQuery { keyspace: "analytics".into(), if_not_exists: true, replication: None, durable_writes: None }
+            ),
+            (
+                "CREATE KEYSPACE analytics WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 3}",
+                CreateKeyspaceQuery {
+                    keyspace: "analytics".into(),
+                    if_not_exists: false,
+                    replication: Some(ReplicationOptions::Simple { replication_factor: 3 }),
+                    durable_writes: None,
+                }
+            ),
+            (
+                "CREATE KEYSPACE analytics WITH REPLICATION = {'class': 'org.apache.cassandra.locator.NetworkTopologyStrategy', 'dc1': '3', 'dc2': 2} AND durable_writes = false",
+                CreateKeyspaceQuery {
+                    keyspace: "analytics".into(),
+                    if_not_exists: false,
+                    replication: Some(ReplicationOptions::NetworkTopology { datacenters: vec![("dc1".into(), 3), ("dc2".into(), 2)] }),
+                    durable_writes: Some(false),
+                }
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateKeyspace(expected_result))));
+        }
+
+        let params = vec![
+            "CREATE KEYSPACE analytics WITH replication = {'replication_factor': 3}",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'SimpleStrategy'}",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'SimpleStrategy', 'replication_factor': -1}",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'NetworkTopologyStrategy', 'replication_factor': 3}",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'LocalStrategy'}",
+            "CREATE KEYSPACE analytics WITH durable_writes = 1",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
     #[test]
     fn test_drop_table() {
-        let query = "DROP TABLE persons";
-        let expected_result = DropTableQuery { table: "persons".to_string() };
-        assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::DropTable(expected_result))));
+        let params = vec![
+            (
+                "DROP TABLE persons",
+                DropTableQuery { keyspace: None, table: "persons".into(), if_exists: false }
+            ),
+            (
+                "DROP TABLE IF EXISTS hr.persons",
+                DropTableQuery { keyspace: Some("hr".into()), table: "persons".into(), if_exists: true }
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::DropTable(expected_result))));
+        }
+    }
+
+    #[test]
+    fn test_create_function() {
+        let query = "CREATE FUNCTION IF NOT EXISTS shop.total (price DOUBLE, quantity INT) RETURNS DOUBLE AS 'price * quantity'";
+
+        let expected_result = CreateFunctionQuery {
+            keyspace: Some("shop".into()),
+            function: "total".into(),
+            if_not_exists: true,
+            arguments: vec![
+                Column { name: "price".into(), column_type: ColumnType::Double },
+                Column { name: "quantity".into(), column_type: ColumnType::Int },
+            ],
+            return_type: ColumnType::Double,
+            body: "price * quantity".into(),
+        };
+
+        assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateFunction(expected_result))));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_create_function_with_invalid_body() {
+        let params = vec![
+            "CREATE FUNCTION total (price DOUBLE) RETURNS DOUBLE AS 'price * quantity'",
+            "CREATE FUNCTION total (price DOUBLE) RETURNS DOUBLE AS 'SELECT price FROM products'",
+            "CREATE FUNCTION total (price DOUBLE) RETURNS DOUBLE AS 'price * ?'",
+            "CREATE FUNCTION total (price DOUBLE) RETURNS DOUBLE",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_type() {
+        let query = "CREATE TYPE IF NOT EXISTS shop.address (street TEXT, city TEXT, zip FROZEN<Zip>)";
+
+        let expected_result = CreateTypeQuery {
+            keyspace: Some("shop".into()),
+            name: "address".into(),
+            if_not_exists: true,
+            fields: vec![
+                Column { name: "street".into(), column_type: ColumnType::Text },
+                Column { name: "city".into(), column_type: ColumnType::Text },
+                Column { name: "zip".into(), column_type: ColumnType::Frozen("zip".into()) },
+            ],
+        };
+
+        assert_eq!(parse_query(query), Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateType(expected_result))));
+    }
+
+    #[test]
+    fn test_create_type_with_invalid_fields() {
+        let params = vec![
+            "CREATE TYPE address ()",
+            "CREATE TYPE address (street TEXT, street TEXT)",
+            "CREATE TYPE address (visits COUNTER)",
+            "CREATE TYPE address (street FROZEN)",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::Debug;
+use crate::query_parser::keyword;
+use crate::query_parser::parser::parse_query_with_dialect;
+use crate::query_parser::query::{Query, QueryParsingError, QueryType};
+
+/// Parses a statement in the given dialect. Never panics on arbitrary input; malformed statements
+/// and statements the dialect doesn't support are reported as errors.
+pub fn parse_with_dialect<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    parse_query_with_dialect(source, dialect)
+}
+
+/// Decides which parts of the grammar the parser accepts, so one grammar serves both the
+/// lenient dialect uranus parses by default and a strict, Cassandra-compatible one.
+pub trait Dialect: Debug {
+    /// Whether statements of the type are accepted at all. Rejected statements are reported
+    /// as unsupported requests.
+    fn supports(&self, _query_type: QueryType) -> bool {
+        true
+    }
+
+    /// Whether an unquoted word is reserved, so it names something only when double-quoted. The
+    /// word is passed as written. Defaults to the keywords the grammar gives a meaning of their
+    /// own; a dialect may reserve more, but freeing one of those, such as `FROM`, can change how
+    /// statements parse.
+    fn is_reserved(&self, word: &str) -> bool {
+        keyword::is_reserved(word)
+    }
+
+    /// Whether `DELETE col, ... FROM` may name the columns to delete instead of whole rows.
+    fn allows_delete_columns(&self) -> bool {
+        true
+    }
+
+    /// Whether unquoted identifiers may contain letters outside ASCII.
+    fn allows_unicode_identifiers(&self) -> bool {
+        true
+    }
+
+    /// Whether `INSERT ... VALUES` may list several rows.
+    fn allows_multi_row_insert(&self) -> bool {
+        true
+    }
+
+    /// Whether column definitions may carry `DEFAULT`, `NOT NULL`, `CHECK` and `REFERENCES`.
+    fn allows_column_constraints(&self) -> bool {
+        true
+    }
+
+    /// Whether reads and writes may compare with `IS NULL` and `IS NOT NULL`. Materialized views
+    /// always may, since CQL requires `IS NOT NULL` on the columns of their primary key.
+    fn allows_null_conditions(&self) -> bool {
+        true
+    }
+
+    /// Whether `USING` may set the consistency level of a statement.
+    fn allows_using_consistency(&self) -> bool {
+        true
+    }
+
+    /// Whether a backslash in a string literal starts an escape such as `\n`. Otherwise it is an
+    /// ordinary character, as in CQL.
+    fn allows_string_escapes(&self) -> bool {
+        true
+    }
+}
+
+/// Accepts everything the parser understands, including the statements uranus adds on top of CQL.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Lenient;
+
+impl Dialect for Lenient {}
+
+/// Keeps to what Cassandra accepts. The statements uranus adds on top of CQL and the shell commands
+/// of cqlsh are rejected, and so are multi-row inserts, column constraints, `IS [NOT] NULL` outside
+/// materialized views and `USING CONSISTENCY`. Unquoted identifiers are ASCII and a backslash in a
+/// string literal is an ordinary character.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Strict;
+
+impl Dialect for Strict {
+    fn supports(&self, query_type: QueryType) -> bool {
+        !matches!(
+            query_type,
+            QueryType::Explain | QueryType::Transaction | QueryType::Tracing | QueryType::Copy | QueryType::ShowTables | QueryType::Compact | QueryType::Flush
+                | QueryType::DescribeTable | QueryType::DescribeKeyspace
+        )
+    }
+
+    fn allows_unicode_identifiers(&self) -> bool {
+        false
+    }
+
+    fn allows_multi_row_insert(&self) -> bool {
+        false
+    }
+
+    fn allows_column_constraints(&self) -> bool {
+        false
+    }
+
+    fn allows_null_conditions(&self) -> bool {
+        false
+    }
+
+    fn allows_using_consistency(&self) -> bool {
+        false
+    }
+
+    fn allows_string_escapes(&self) -> bool {
+        false
+    }
+}
@@ -1,209 +1,580 @@
-use crate::query_parser::builder::{ConditionBuilder, DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder};
+use crate::query_parser::builder::{DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder};
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::dialect::Dialect;
 use crate::query_parser::common_parser;
-use crate::query_parser::common_parser::parse_value;
+use crate::query_parser::expression_parser::parse_expression;
 use crate::query_parser::keyword::*;
-use crate::query_parser::query::{Condition, DataManipulationQuery, Operator, Query, QueryParsingError, UpdateQuery, Value};
+use std::borrow::Cow;
+use crate::query_parser::query::{Condition, Consistency, DataManipulationQuery, Expression, IfClause, Operator, Query, QueryParsingError, Selector, SyntaxError, UpdateQuery, Value};
 use common_parser::ws;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::combinator::map;
+use nom::bytes::complete::{tag, tag_no_case};
+use nom::character::complete::{i64 as parse_i64, satisfy, u32 as parse_u32};
+use nom::combinator::{fail, map, not, opt, peek, rest_len, verify};
 use nom::multi::separated_list1;
-use nom::sequence::{delimited, tuple};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::IResult;
 
-pub(crate) fn parse_select_query(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match common_parser::parse_keyword(SELECT)(query) {
+pub(crate) fn parse_select_query<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let query = match common_parser::parse_keyword(SELECT)(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the select keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the select keyword", &[SELECT]))
     };
 
-    let parsing_result: IResult<&str, Vec<String>> = alt((
+    let (query, json) = match opt(parse_word(JSON))(query) {
+        Ok((query, json)) => (query, json.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the json keyword", &[JSON]))
+    };
+
+    let (query, distinct) = match opt(parse_word(DISTINCT))(query) {
+        Ok((query, distinct)) => (query, distinct.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the distinct keyword", &[DISTINCT]))
+    };
+
+    let parsing_result: IResult<&str, Vec<Selector<'_>>> = alt((
         map(
             ws(tag("*")),
             |_| Vec::new(),
         ),
         separated_list1(
             ws(tag(",")),
-            common_parser::parse_identifier,
+            parse_selector,
         )
     ))(query);
 
     let (query, columns) = match parsing_result {
         Ok((query, columns)) => (query, columns),
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the column names or *".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the column names or *", &["*", "expression"]))
     };
 
     let query = match common_parser::parse_keyword(FROM)(query) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the from keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the from keyword", &[FROM]))
     };
 
-    let (query, table) = match common_parser::parse_identifier(query) {
+    let (query, (keyspace, table)) = match common_parser::parse_table_name(query) {
         Ok((query, table)) => (query, table),
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the table name", &["table name"]))
     };
 
-    let conditions = match parse_conditions(query) {
-        Ok((_, conditions)) => conditions,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing where condition".to_string(), query.to_string()))
+    let (query, table_alias) = match opt(parse_table_alias)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the table alias", &["alias"]))
     };
 
-    Ok(SelectQueryBuilder::new()
-        .columns(columns)
+    let (query, using) = match parse_using(fail, dialect)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the using clause", &[CONSISTENCY, SERIAL_CONSISTENCY]))
+    };
+
+    let (query, conditions) = match parse_conditions(dialect.allows_null_conditions())(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing where condition", &["condition"]))
+    };
+
+    let (query, per_partition_limit) = match parse_per_partition_limit(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected a positive per partition limit", &["limit"]))
+    };
+
+    let (query, allow_filtering) = match opt(common_parser::parse_keyword(ALLOW_FILTERING))(query) {
+        Ok((query, allow_filtering)) => (query, allow_filtering.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing allow filtering", &[ALLOW_FILTERING]))
+    };
+
+    common_parser::expect_end(source, query)?;
+
+    let mut builder = SelectQueryBuilder::new()
+        .selectors(columns)
         .table(table)
-        .conditions(conditions)
-        .build())
+        .conditions(conditions);
+
+    if let Some(keyspace) = keyspace {
+        builder = builder.keyspace(keyspace);
+    }
+
+    if let Some(table_alias) = table_alias {
+        builder = builder.table_alias(table_alias);
+    }
+
+    if let Some(per_partition_limit) = per_partition_limit {
+        builder = builder.per_partition_limit(per_partition_limit);
+    }
+
+    if allow_filtering {
+        builder = builder.allow_filtering();
+    }
+
+    if json {
+        builder = builder.json();
+    }
+
+    if distinct {
+        builder = builder.distinct();
+    }
+
+    if let Some(consistency) = using.consistency {
+        builder = builder.consistency(consistency);
+    }
+
+    if let Some(serial_consistency) = using.serial_consistency {
+        builder = builder.serial_consistency(serial_consistency);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Matches the keyword only as a whole word, so that it isn't taken for the start of an identifier.
+fn parse_word<'a>(keyword: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    ws(terminated(
+        tag_no_case(keyword),
+        not(peek(satisfy(|ch: char| ch.is_alphanumeric() || ch == '_'))),
+    ))
 }
 
-fn parse_conditions(query: &str) -> IResult<&str, Vec<Condition>> {
-    match common_parser::parse_keyword(WHERE)(query) {
-        Ok((query, _)) => separated_list1(common_parser::parse_keyword(AND), parse_condition)(query),
+fn parse_selector(query: &str) -> IResult<&str, Selector<'_>> {
+    let (query, expression) = parse_expression(query)?;
+    let (query, alias) = opt(preceded(parse_word(AS), common_parser::parse_identifier))(query)?;
+
+    Ok((query, Selector { expression, alias }))
+}
+
+/// Parses `AS alias` or a bare alias. A bare alias can't be `PER`, which may follow the table
+/// name without being reserved.
+fn parse_table_alias(query: &str) -> IResult<&str, Cow<'_, str>> {
+    let bare_alias = verify(common_parser::parse_unreserved_identifier, |alias: &str| !alias.eq_ignore_ascii_case(PER));
+
+    alt((
+        preceded(parse_word(AS), common_parser::parse_identifier),
+        common_parser::parse_quoted_identifier,
+        bare_alias,
+    ))(query)
+}
+
+/// Parses `WHERE condition [AND condition ...]`, taking `IS [NOT] NULL` conditions only when
+/// `null_conditions` is set. Without `WHERE` there are no conditions.
+pub(crate) fn parse_conditions<'a>(null_conditions: bool) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Condition<'a>>> {
+    move |query| match common_parser::parse_keyword(WHERE)(query) {
+        Ok((query, _)) => separated_list1(common_parser::parse_keyword(AND), parse_condition(null_conditions))(query),
         Err(_) => Ok((query, Vec::new()))
     }
 }
 
-pub(crate) fn parse_condition(query: &str) -> IResult<&str, Condition> {
-    let (query, column) = common_parser::parse_identifier(query)?;
+/// Parses `PER PARTITION LIMIT n`, where the limit is positive.
+fn parse_per_partition_limit(query: &str) -> IResult<&str, Option<u32>> {
+    match common_parser::parse_keyword(PER_PARTITION_LIMIT)(query) {
+        Ok((query, _)) => map(ws(verify(parse_u32, |limit| *limit > 0)), Some)(query),
+        Err(_) => Ok((query, None))
+    }
+}
+
+fn parse_if_clause<'a>(null_conditions: bool) -> impl FnMut(&'a str) -> IResult<&'a str, Option<IfClause<'a>>> {
+    move |query| {
+        if let Ok((query, _)) = common_parser::parse_keyword(IF_EXISTS)(query) {
+            return Ok((query, Some(IfClause::Exists)));
+        }
+
+        match common_parser::parse_keyword(IF)(query) {
+            Ok((query, _)) => map(
+                separated_list1(common_parser::parse_keyword(AND), parse_condition(null_conditions)),
+                |conditions| Some(IfClause::Conditions(conditions)),
+            )(query),
+            Err(_) => Ok((query, None))
+        }
+    }
+}
+
+enum UsingOption {
+    Ttl(u32),
+    Timestamp(i64),
+    Consistency(Consistency),
+    SerialConsistency(Consistency),
+}
+
+#[derive(Default)]
+struct UsingClause {
+    ttl: Option<u32>,
+    timestamp: Option<i64>,
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
+}
+
+/// Drops the qualifiers of the written columns, each of which must name the statement's table.
+/// A mismatch is reported at the start of the column list.
+fn unqualify<'a>(source: &str, columns_at: &str, columns: Vec<(Option<Cow<'a, str>>, Cow<'a, str>)>, table: &str) -> Result<Vec<Cow<'a, str>>, QueryParsingError> {
+    columns.into_iter()
+        .map(|(qualifier, column)| match qualifier {
+            Some(qualifier) if qualifier != table => {
+                let message = format!("the column {}.{} doesn't belong to the table {}", qualifier, column, table);
+                Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - columns_at.len(), message, vec![table.to_string()])))
+            }
+            _ => Ok(column),
+        })
+        .collect()
+}
+
+/// Parses `USING option [AND option ...]` taking the options the statement accepts, along with
+/// consistency levels when the dialect allows them. Without `USING` every option is unset.
+fn parse_using<'a, F>(mut option: F, dialect: &dyn Dialect) -> impl FnMut(&'a str) -> IResult<&'a str, UsingClause>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, UsingOption>,
+{
+    let consistency = dialect.allows_using_consistency();
+
+    move |query| {
+        let query = match common_parser::parse_keyword(USING)(query) {
+            Ok((query, _)) => query,
+            Err(_) => return Ok((query, UsingClause::default()))
+        };
+
+        let mut option = |query| if consistency {
+            alt((&mut option, parse_consistency_option))(query)
+        } else {
+            option(query)
+        };
+
+        let (query, options) = separated_list1(common_parser::parse_keyword(AND), &mut option)(query)?;
+        let mut using = UsingClause::default();
+
+        for option in options {
+            match option {
+                UsingOption::Ttl(value) => using.ttl = Some(value),
+                UsingOption::Timestamp(value) => using.timestamp = Some(value),
+                UsingOption::Consistency(value) => using.consistency = Some(value),
+                UsingOption::SerialConsistency(value) => using.serial_consistency = Some(value),
+            }
+        }
+
+        Ok((query, using))
+    }
+}
+
+fn parse_ttl(query: &str) -> IResult<&str, UsingOption> {
+    map(preceded(common_parser::parse_keyword(TTL), ws(parse_u32)), UsingOption::Ttl)(query)
+}
 
-    let (query, operator) = alt((
-        map(ws(tag(GREATER_OR_EQUALS)), |_| Operator::GreaterOrEquals),
-        map(ws(tag(LESS_OR_EQUALS)), |_| Operator::LessOrEquals),
-        map(ws(tag(GREATER)), |_| Operator::Greater),
-        map(ws(tag(LESS)), |_| Operator::Less),
-        map(ws(tag(EQUALS)), |_| Operator::Equals),
-        map(ws(tag(NOT_EQUALS)), |_| Operator::NotEquals),
-    ))(query)?;
+fn parse_timestamp(query: &str) -> IResult<&str, UsingOption> {
+    map(preceded(common_parser::parse_keyword(TIMESTAMP), ws(parse_i64)), UsingOption::Timestamp)(query)
+}
 
-    let (query, value) = parse_value(query)?;
+/// Parses `CONSISTENCY level` or `SERIAL CONSISTENCY level`, where the serial level is either
+/// `SERIAL` or `LOCAL_SERIAL`.
+fn parse_consistency_option(query: &str) -> IResult<&str, UsingOption> {
+    let serial = verify(parse_consistency, |level| matches!(level, Consistency::Serial | Consistency::LocalSerial));
 
-    let condition = ConditionBuilder::new()
-        .column(column)
-        .operator(operator)
-        .value(value)
-        .build();
+    alt((
+        map(preceded(common_parser::parse_keyword(SERIAL_CONSISTENCY), serial), UsingOption::SerialConsistency),
+        map(preceded(common_parser::parse_keyword(CONSISTENCY), parse_consistency), UsingOption::Consistency),
+    ))(query)
+}
 
-    Ok((query, condition))
+fn parse_consistency(query: &str) -> IResult<&str, Consistency> {
+    alt((
+        map(parse_word(ANY), |_| Consistency::Any),
+        map(parse_word(ONE), |_| Consistency::One),
+        map(parse_word(TWO), |_| Consistency::Two),
+        map(parse_word(THREE), |_| Consistency::Three),
+        map(parse_word(QUORUM), |_| Consistency::Quorum),
+        map(parse_word(ALL), |_| Consistency::All),
+        map(parse_word(LOCAL_QUORUM), |_| Consistency::LocalQuorum),
+        map(parse_word(EACH_QUORUM), |_| Consistency::EachQuorum),
+        map(parse_word(SERIAL), |_| Consistency::Serial),
+        map(parse_word(LOCAL_SERIAL), |_| Consistency::LocalSerial),
+        map(parse_word(LOCAL_ONE), |_| Consistency::LocalOne),
+    ))(query)
 }
 
-pub(crate) fn parse_insert(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match common_parser::parse_keyword(INSERT_INTO)(query) {
+/// Parses a comparison, or an `IS [NOT] NULL` test when `null_conditions` is set.
+pub(crate) fn parse_condition<'a>(null_conditions: bool) -> impl FnMut(&'a str) -> IResult<&'a str, Condition<'a>> {
+    move |query| {
+        let (query, left) = parse_expression(query)?;
+
+        if null_conditions {
+            let mut is_null = map(
+                tuple((common_parser::parse_keyword(IS), opt(common_parser::parse_keyword(NOT)), common_parser::parse_keyword(NULL))),
+                |(_, not, _)| if not.is_some() { Operator::IsNot } else { Operator::Is },
+            );
+
+            if let Ok((query, operator)) = is_null(query) {
+                return Ok((query, Condition::new(left, operator, Expression::Literal(Value::Null))));
+            }
+        }
+
+        let (query, operator) = alt((
+            map(ws(tag(GREATER_OR_EQUALS)), |_| Operator::GreaterOrEquals),
+            map(ws(tag(LESS_OR_EQUALS)), |_| Operator::LessOrEquals),
+            map(ws(tag(GREATER)), |_| Operator::Greater),
+            map(ws(tag(LESS)), |_| Operator::Less),
+            map(ws(tag(EQUALS)), |_| Operator::Equals),
+            map(ws(tag(NOT_EQUALS)), |_| Operator::NotEquals),
+        ))(query)?;
+
+        let (query, right) = parse_expression(query)?;
+
+        Ok((query, Condition::new(left, operator, right)))
+    }
+}
+
+pub(crate) fn parse_insert<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let query = match common_parser::parse_keyword(INSERT_INTO)(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the insert into keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the insert into keyword", &[INSERT_INTO]))
     };
 
-    let (query, table) = match common_parser::parse_identifier(query) {
+    let (query, (keyspace, table)) = match common_parser::parse_table_name(query) {
         Ok((query, table)) => (query, table),
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected the table name", &["table name"]))
     };
 
-    let parsing_result = ws(delimited(
-        tag("("),
-        separated_list1(ws(tag(",")), common_parser::parse_identifier),
-        tag(")"),
-    ))(query);
-
-    let (query, columns) = match parsing_result {
+    let (query, json) = match opt(preceded(common_parser::parse_keyword(JSON), common_parser::parse_string_literal))(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing column names".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the json payload", &[JSON]))
     };
 
-    let query = match common_parser::parse_keyword(VALUES)(query) {
-        Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected the values keyword".to_string(), query.to_string()))
+    let (query, columns, values) = if json.is_some() {
+        (query, Vec::new(), Vec::new())
+    } else {
+        let parsing_result = ws(delimited(
+            tag("("),
+            separated_list1(ws(tag(",")), common_parser::parse_column_name),
+            tag(")"),
+        ))(query);
+
+        let (query, columns) = match parsing_result {
+            Ok((rest, columns)) => (rest, unqualify(source, query, columns, &table)?),
+            Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing column names", &["(", "column name"]))
+        };
+
+        let query = match common_parser::parse_keyword(VALUES)(query) {
+            Ok((query, _)) => query,
+            Err(error) => return Err(syntax_error(source, error, "expected the values keyword", &[VALUES]))
+        };
+
+        let parsing_result = separated_list1(
+            ws(tag(",")),
+            ws(pair(
+                rest_len,
+                delimited(
+                    tag("("),
+                    separated_list1(ws(tag(",")), parse_expression),
+                    tag(")"),
+                ),
+            )),
+        )(query);
+
+        let (query, rows) = match parsing_result {
+            Ok(result) => result,
+            Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing values", &["(", "value"]))
+        };
+
+        let mut values = Vec::with_capacity(rows.len());
+
+        for (index, (row_len, row)) in rows.into_iter().enumerate() {
+            if index > 0 && !dialect.allows_multi_row_insert() {
+                let message = "the dialect doesn't allow inserting several rows at once".to_string();
+                return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - row_len, message, Vec::new())));
+            }
+
+            if row.len() != columns.len() {
+                let message = "expected one value per column".to_string();
+                return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - row_len, message, Vec::new())));
+            }
+
+            values.push(row);
+        }
+
+        (query, columns, values)
     };
 
-    let parsing_result = ws(delimited(
-        tag("("),
-        separated_list1(ws(tag(",")), common_parser::parse_value),
-        tag(")"),
-    ))(query);
+    let (query, if_not_exists) = match opt(common_parser::parse_keyword(IF_NOT_EXISTS))(query) {
+        Ok((query, if_not_exists)) => (query, if_not_exists.is_some()),
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing if not exists", &[IF_NOT_EXISTS]))
+    };
 
-    let (_, values) = match parsing_result {
+    let (query, using) = match parse_using(alt((parse_ttl, parse_timestamp)), dialect)(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing values".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the using clause", &[TTL, TIMESTAMP, CONSISTENCY, SERIAL_CONSISTENCY]))
     };
 
-    Ok(InsertQueryBuilder::new()
+    common_parser::expect_end(source, query)?;
+
+    let mut builder = InsertQueryBuilder::new()
         .columns(columns)
         .table(table)
-        .values(values)
-        .build())
+        .values(values);
+
+    if let Some(json) = json {
+        builder = builder.json(json);
+    }
+
+    if let Some(keyspace) = keyspace {
+        builder = builder.keyspace(keyspace);
+    }
+
+    if let Some(ttl) = using.ttl {
+        builder = builder.ttl(ttl);
+    }
+
+    if let Some(timestamp) = using.timestamp {
+        builder = builder.timestamp(timestamp);
+    }
+
+    if let Some(consistency) = using.consistency {
+        builder = builder.consistency(consistency);
+    }
+
+    if let Some(serial_consistency) = using.serial_consistency {
+        builder = builder.serial_consistency(serial_consistency);
+    }
+
+    if if_not_exists {
+        builder = builder.if_not_exists();
+    }
+
+    Ok(builder.build()?)
 }
 
-pub(crate) fn parse_update(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match common_parser::parse_keyword(UPDATE)(query) {
+pub(crate) fn parse_update<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let query = match common_parser::parse_keyword(UPDATE)(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing update keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing update keyword", &[UPDATE]))
     };
 
-    let (query, table) = match common_parser::parse_identifier(query) {
+    let (query, (keyspace, table)) = match common_parser::parse_table_name(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing the table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the table name", &["table name"]))
+    };
+
+    let (query, using) = match parse_using(alt((parse_ttl, parse_timestamp)), dialect)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the using clause", &[TTL, TIMESTAMP, CONSISTENCY, SERIAL_CONSISTENCY]))
     };
 
     let query = match common_parser::parse_keyword(SET)(query) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("expected set keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "expected set keyword", &[SET]))
     };
 
     let (query, values) = match separated_list1(
         ws(tag(",")),
         map(
-            tuple((common_parser::parse_identifier, ws(tag("=")), common_parser::parse_value)),
+            tuple((common_parser::parse_column_name, ws(tag("=")), parse_expression)),
             |(column, _, value)| (column, value),
         ),
     )(query) {
+        Ok((rest, values)) => {
+            let (columns, values): (Vec<_>, Vec<_>) = values.into_iter().unzip();
+            (rest, unqualify(source, query, columns, &table)?.into_iter().zip(values).collect())
+        }
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing values", &["column = value"]))
+    };
+
+    let (query, conditions) = match parse_conditions(dialect.allows_null_conditions())(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing values".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing where condition", &["condition"]))
     };
 
-    let conditions = match parse_conditions(query) {
-        Ok((_, conditions)) => conditions,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing where condition".to_string(), query.to_string()))
+    let (query, if_clause) = match parse_if_clause(dialect.allows_null_conditions())(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing if condition", &[IF_EXISTS, "condition"]))
     };
 
-    Ok(Query::DataManipulationQuery(DataManipulationQuery::Update(UpdateQuery::new(table, values, conditions))))
+    common_parser::expect_end(source, query)?;
+
+    Ok(Query::DataManipulationQuery(DataManipulationQuery::Update(UpdateQuery::new(
+        keyspace,
+        table,
+        values,
+        conditions,
+        using.ttl,
+        using.timestamp,
+        if_clause,
+        using.consistency,
+        using.serial_consistency,
+    ))))
 }
 
-pub(crate) fn parse_delete(query: &str) -> Result<Query, QueryParsingError> {
-    let query = match common_parser::parse_keyword(DELETE)(query) {
+pub(crate) fn parse_delete<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let query = match common_parser::parse_keyword(DELETE)(source) {
         Ok((query, _)) => query,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing delete keyword".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing delete keyword", &[DELETE]))
     };
 
+    let columns_at = query;
     let (query, columns) = match common_parser::parse_keyword(FROM)(query) {
         Ok((query, _)) => (query, Vec::new()),
-        Err(_) => match separated_list1(ws(tag(",")), common_parser::parse_identifier)(query) {
+        Err(_) => match separated_list1(ws(tag(",")), common_parser::parse_column_name)(query) {
             Ok((query, columns)) => match common_parser::parse_keyword(FROM)(query) {
                 Ok((query, _)) => (query, columns),
-                Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing from keyword".to_string(), query.to_string()))
+                Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing from keyword", &[FROM]))
             },
-            Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing the columns".to_string(), query.to_string()))
+            Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the columns", &[FROM, "column name"]))
         }
     };
 
-    let (query, table) = match common_parser::parse_identifier(query) {
+    let (query, (keyspace, table)) = match common_parser::parse_table_name(query) {
         Ok(result) => result,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing the table name".to_string(), query.to_string()))
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the table name", &["table name"]))
     };
 
-    let conditions = match parse_conditions(query) {
-        Ok((_, conditions)) => conditions,
-        Err(_) => return Err(QueryParsingError::QuerySyntaxError("an error occurred while parsing where condition".to_string(), query.to_string()))
+    if !columns.is_empty() && !dialect.allows_delete_columns() {
+        let message = "the dialect doesn't allow deleting single columns".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - columns_at.len(), message, vec![FROM.to_string()])));
+    }
+
+    let columns = unqualify(source, columns_at, columns, &table)?;
+
+    let (query, using) = match parse_using(parse_timestamp, dialect)(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing the using clause", &[TIMESTAMP, CONSISTENCY, SERIAL_CONSISTENCY]))
     };
 
-    Ok(DeleteQueryBuilder::new()
+    let (query, conditions) = match parse_conditions(dialect.allows_null_conditions())(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing where condition", &["condition"]))
+    };
+
+    let (query, if_clause) = match parse_if_clause(dialect.allows_null_conditions())(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "an error occurred while parsing if condition", &[IF_EXISTS, "condition"]))
+    };
+
+    common_parser::expect_end(source, query)?;
+
+    let mut builder = DeleteQueryBuilder::new()
         .columns(columns)
         .table(table)
-        .conditions(conditions)
-        .build())
+        .conditions(conditions);
+
+    if let Some(keyspace) = keyspace {
+        builder = builder.keyspace(keyspace);
+    }
+
+    if let Some(timestamp) = using.timestamp {
+        builder = builder.timestamp(timestamp);
+    }
+
+    if let Some(if_clause) = if_clause {
+        builder = builder.if_clause(if_clause);
+    }
+
+    if let Some(consistency) = using.consistency {
+        builder = builder.consistency(consistency);
+    }
+
+    if let Some(serial_consistency) = using.serial_consistency {
+        builder = builder.serial_consistency(serial_consistency);
+    }
+
+    Ok(builder.build()?)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::query_parser::builder::UpdateQueryBuilder;
+    use crate::query_parser::builder::{ConditionBuilder, UpdateQueryBuilder};
     use crate::query_parser::parser::parse_query;
+    use crate::query_parser::query::{ArithmeticOperator, Value};
 
     #[test]
     fn test_parse_select() {
@@ -215,7 +586,7 @@ mod test {
                 "#,
                 SelectQueryBuilder::new()
                     .table("user_sessions".to_string())
-                    .build()
+                    .build().unwrap()
             ),
             (
                 r#"
@@ -229,7 +600,7 @@ mod test {
                     .column("device_type".to_string())
                     .column("timestamp".to_string())
                     .table("user_sessions".to_string())
-                    .build()
+                    .build().unwrap()
             ),
             (
                 r#"
@@ -249,19 +620,48 @@ mod test {
                     .condition(ConditionBuilder::new()
                         .column("user_id".to_string())
                         .operator(Operator::Equals)
-                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".to_string()))
-                        .build())
+                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+                        .build().unwrap())
                     .condition(ConditionBuilder::new()
                         .column("timestamp".to_string())
                         .operator(Operator::GreaterOrEquals)
-                        .value(Value::String("2024-10-21 00:00:00".to_string()))
-                        .build())
+                        .value(Value::String("2024-10-21 00:00:00".into()))
+                        .build().unwrap())
                     .condition(ConditionBuilder::new()
                         .column("timestamp".to_string())
                         .operator(Operator::LessOrEquals)
-                        .value(Value::String("2024-11-01 00:00:00".to_string()))
-                        .build())
-                    .build()
+                        .value(Value::String("2024-11-01 00:00:00".into()))
+                        .build().unwrap())
+                    .build().unwrap()
+            ),
+            (
+                r#"
+                SELECT user_id
+                FROM user_sessions
+                WHERE device_type = 'PHONE'
+                ALLOW FILTERING
+                "#,
+                SelectQueryBuilder::new()
+                    .column("user_id".to_string())
+                    .table("user_sessions".to_string())
+                    .condition(ConditionBuilder::new()
+                        .column("device_type".to_string())
+                        .operator(Operator::Equals)
+                        .value(Value::String("PHONE".into()))
+                        .build().unwrap())
+                    .allow_filtering()
+                    .build().unwrap()
+            ),
+            (
+                r#"
+                SELECT user_id
+                FROM analytics.user_sessions
+                "#,
+                SelectQueryBuilder::new()
+                    .column("user_id".to_string())
+                    .keyspace("analytics".to_string())
+                    .table("user_sessions".to_string())
+                    .build().unwrap()
             )
         ];
 
@@ -284,11 +684,371 @@ mod test {
             .column("timestamp".to_string())
             .table("user_sessions".to_string())
             .value(Value::Integer(12345))
-            .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".to_string()))
-            .value(Value::String("LOG_IN".to_string()))
-            .value(Value::String("PHONE".to_string()))
-            .value(Value::String("2024-11-01 00:00:00".to_string()))
-            .build();
+            .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+            .value(Value::String("LOG_IN".into()))
+            .value(Value::String("PHONE".into()))
+            .value(Value::String("2024-11-01 00:00:00".into()))
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_insert_query_with_ttl() {
+        let query = r#"
+        INSERT INTO user_sessions (user_id, session_id)
+        VALUES (12345, '3e3be9fb-5888-4b0e-8f22-287b7d90a32f')
+        USING TTL 3600"#;
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("session_id".to_string())
+            .table("user_sessions".to_string())
+            .value(Value::Integer(12345))
+            .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+            .ttl(3600)
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_multi_row_insert_query() {
+        let query = r#"
+        INSERT INTO user_sessions (user_id, type)
+        VALUES (12345, 'LAPTOP'), (12346, 'PHONE'),
+               (12347, 'TABLET')"#;
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("type".to_string())
+            .table("user_sessions".to_string())
+            .row(vec![Value::Integer(12345), Value::String("LAPTOP".into())])
+            .row(vec![Value::Integer(12346), Value::String("PHONE".into())])
+            .row(vec![Value::Integer(12347), Value::String("TABLET".into())])
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_reject_insert_value_count_mismatch() {
+        let params = vec![
+            ("INSERT INTO user_sessions (user_id, type) VALUES (1)", 50),
+            ("INSERT INTO user_sessions (user_id) VALUES (1, 2), (3)", 44),
+            ("INSERT INTO user_sessions (user_id, type) VALUES (1, 'PHONE'), (2)", 64),
+        ];
+
+        for (query, column) in params {
+            let Err(QueryParsingError::QuerySyntaxError(error)) = parse_query(query) else { panic!("expected a syntax error for {}", query) };
+            assert_eq!((error.column, error.message.as_str()), (column, "expected one value per column"), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_parse_select_with_expressions() {
+        let query = "SELECT price * quantity, upper(name) FROM products WHERE price * 1.2 > 100 ALLOW FILTERING";
+
+        let expected_result = SelectQueryBuilder::new()
+            .expression(Expression::Binary(
+                Box::new(Expression::Column("price".into())),
+                ArithmeticOperator::Multiply,
+                Box::new(Expression::Column("quantity".into())),
+            ))
+            .expression(Expression::Function("upper".into(), vec![Expression::Column("name".into())]))
+            .table("products".to_string())
+            .condition(ConditionBuilder::new()
+                .left(Expression::Binary(
+                    Box::new(Expression::Column("price".into())),
+                    ArithmeticOperator::Multiply,
+                    Box::new(Expression::Literal(Value::Float(1.2))),
+                ))
+                .operator(Operator::Greater)
+                .value(Value::Integer(100))
+                .build().unwrap())
+            .allow_filtering()
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_select_with_aliases() {
+        let expected_result = SelectQueryBuilder::new()
+            .aliased(Expression::Column("temperature".into()), "temp")
+            .table("sensors")
+            .table_alias("s")
+            .condition(ConditionBuilder::new()
+                .left(Expression::QualifiedColumn("s".into(), "temperature".into()))
+                .operator(Operator::Greater)
+                .value(Value::Integer(20))
+                .build().unwrap())
+            .build().unwrap();
+
+        let params = vec![
+            "SELECT temperature AS temp FROM sensors s WHERE s.temperature > 20",
+            "SELECT temperature as temp FROM sensors AS s WHERE s . temperature > 20",
+        ];
+
+        for query in params {
+            assert_eq!(parse_query(query).as_ref(), Ok(&expected_result), "{}", query);
+        }
+
+        let expected_result = SelectQueryBuilder::new()
+            .column("assets")
+            .table("sensors")
+            .condition(ConditionBuilder::new()
+                .column("type")
+                .operator(Operator::Equals)
+                .value(Value::Integer(1))
+                .build().unwrap())
+            .allow_filtering()
+            .build().unwrap();
+
+        assert_eq!(parse_query("SELECT assets FROM sensors WHERE type = 1 ALLOW FILTERING"), Ok(expected_result));
+        assert!(parse_query("SELECT temperature temp FROM sensors").is_err());
+    }
+
+    #[test]
+    fn test_parse_select_distinct() {
+        let params = vec![
+            (
+                "SELECT DISTINCT sensor_id FROM readings",
+                SelectQueryBuilder::new().column("sensor_id").table("readings").distinct().build().unwrap(),
+            ),
+            (
+                "select json distinct sensor_id, site from readings",
+                SelectQueryBuilder::new().column("sensor_id").column("site").table("readings").json().distinct().build().unwrap(),
+            ),
+            (
+                "SELECT distinct_sensors FROM readings",
+                SelectQueryBuilder::new().column("distinct_sensors").table("readings").build().unwrap(),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_parse_select_per_partition_limit() {
+        let params = vec![
+            (
+                "SELECT * FROM readings PER PARTITION LIMIT 1",
+                SelectQueryBuilder::new().table("readings").per_partition_limit(1).build().unwrap(),
+            ),
+            (
+                "select * from readings where sensor_id = 1 per partition limit 10 allow filtering",
+                SelectQueryBuilder::new()
+                    .table("readings")
+                    .condition(Condition::new(Expression::Column("sensor_id".into()), Operator::Equals, Expression::Literal(Value::Integer(1))))
+                    .per_partition_limit(10)
+                    .allow_filtering()
+                    .build()
+                    .unwrap(),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result), "{}", query);
+        }
+
+        assert!(parse_query("SELECT * FROM readings PER PARTITION LIMIT 0").is_err());
+        assert!(parse_query("SELECT * FROM readings PER PARTITION LIMIT").is_err());
+    }
+
+    #[test]
+    fn test_parse_null_conditions() {
+        let query = "SELECT * FROM user_sessions WHERE device_type IS NULL AND type is not null AND country != NULL ALLOW FILTERING";
+
+        let expected_result = SelectQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .condition(ConditionBuilder::new()
+                .column("device_type".to_string())
+                .operator(Operator::Is)
+                .value(Value::Null)
+                .build().unwrap())
+            .condition(ConditionBuilder::new()
+                .column("type".to_string())
+                .operator(Operator::IsNot)
+                .value(Value::Null)
+                .build().unwrap())
+            .condition(ConditionBuilder::new()
+                .column("country".to_string())
+                .operator(Operator::NotEquals)
+                .value(Value::Null)
+                .build().unwrap())
+            .allow_filtering()
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+        assert!(parse_query("SELECT * FROM user_sessions WHERE device_type IS 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_json_queries() {
+        let params = vec![
+            (
+                r#"INSERT INTO user_sessions JSON '{"user_id": 12345, "type": "LAPTOP"}' USING TTL 3600"#,
+                InsertQueryBuilder::new()
+                    .table("user_sessions".to_string())
+                    .json(r#"{"user_id": 12345, "type": "LAPTOP"}"#.to_string())
+                    .ttl(3600)
+                    .build().unwrap(),
+            ),
+            (
+                "SELECT JSON * FROM user_sessions WHERE user_id = 12345",
+                SelectQueryBuilder::new()
+                    .table("user_sessions".to_string())
+                    .condition(ConditionBuilder::new()
+                        .column("user_id".to_string())
+                        .operator(Operator::Equals)
+                        .value(Value::Integer(12345))
+                        .build().unwrap())
+                    .json()
+                    .build().unwrap(),
+            ),
+            (
+                "SELECT json_payload FROM user_sessions",
+                SelectQueryBuilder::new()
+                    .column("json_payload".to_string())
+                    .table("user_sessions".to_string())
+                    .build().unwrap(),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result));
+        }
+    }
+
+    #[test]
+    fn test_parse_insert_query_with_uuid_values() {
+        let query = r#"
+        INSERT INTO user_sessions (user_id, session_id, created_at)
+        VALUES (3e3be9fb-5888-4b0e-8f22-287b7d90a32f, uuid(), toTimestamp(now()))"#;
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("session_id".to_string())
+            .column("created_at".to_string())
+            .table("user_sessions".to_string())
+            .value(Value::Uuid(0x3e3be9fb_5888_4b0e_8f22_287b7d90a32f))
+            .expression(Expression::Function("uuid".into(), Vec::new()))
+            .expression(Expression::Function("totimestamp".into(), vec![Expression::Function("now".into(), Vec::new())]))
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_insert_query_with_user_type_value() {
+        let query = "INSERT INTO users (user_id, address) VALUES (1, {street: 'Main St', \"ZIP\": {code: 12345}})";
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("address".to_string())
+            .table("users".to_string())
+            .value(Value::Integer(1))
+            .value(Value::UserType(vec![
+                ("street".into(), Value::String("Main St".into())),
+                ("ZIP".into(), Value::UserType(vec![("code".into(), Value::Integer(12345))])),
+            ]))
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_collection_updates() {
+        let tags = || Box::new(Expression::Column("tags".into()));
+
+        let params = vec![
+            (
+                "UPDATE users SET tags = tags + {'new', 'vip'} WHERE user_id = 1",
+                Expression::Binary(tags(), ArithmeticOperator::Add, Box::new(Expression::Literal(Value::Set(vec![Value::String("new".into()), Value::String("vip".into())])))),
+            ),
+            (
+                "UPDATE users SET tags = tags - {'new'} WHERE user_id = 1",
+                Expression::Binary(tags(), ArithmeticOperator::Subtract, Box::new(Expression::Literal(Value::Set(vec![Value::String("new".into())])))),
+            ),
+            (
+                "UPDATE users SET tags = ['first'] + tags WHERE user_id = 1",
+                Expression::Binary(Box::new(Expression::Literal(Value::List(vec![Value::String("first".into())]))), ArithmeticOperator::Add, tags()),
+            ),
+            (
+                "UPDATE users SET tags = tags + {'theme': 'dark', 'lang': NULL} WHERE user_id = 1",
+                Expression::Binary(tags(), ArithmeticOperator::Add, Box::new(Expression::Literal(Value::Map(vec![
+                    (Value::String("theme".into()), Value::String("dark".into())),
+                    (Value::String("lang".into()), Value::Null),
+                ])))),
+            ),
+        ];
+
+        for (query, expected_assignment) in params {
+            let expected_result = UpdateQueryBuilder::new()
+                .table("users".to_string())
+                .assignment("tags".to_string(), expected_assignment)
+                .condition(ConditionBuilder::new()
+                    .column("user_id".to_string())
+                    .operator(Operator::Equals)
+                    .value(Value::Integer(1))
+                    .build().unwrap())
+                .build().unwrap();
+
+            assert_eq!(parse_query(query), Ok(expected_result), "{}", query);
+        }
+
+        let query = "INSERT INTO users (user_id, emails) VALUES (1, [])";
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("emails".to_string())
+            .table("users".to_string())
+            .value(Value::Integer(1))
+            .value(Value::List(Vec::new()))
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_insert_query_with_timestamp() {
+        let query = r#"
+        INSERT INTO user_sessions (user_id, session_id)
+        VALUES (12345, '3e3be9fb-5888-4b0e-8f22-287b7d90a32f')
+        USING TTL 3600 AND TIMESTAMP 1699000000000"#;
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("session_id".to_string())
+            .table("user_sessions".to_string())
+            .value(Value::Integer(12345))
+            .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+            .ttl(3600)
+            .timestamp(1699000000000)
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_insert_query_if_not_exists() {
+        let query = r#"
+        INSERT INTO user_sessions (user_id, session_id)
+        VALUES (12345, '3e3be9fb-5888-4b0e-8f22-287b7d90a32f')
+        IF NOT EXISTS
+        USING TTL 3600"#;
+
+        let expected_result = InsertQueryBuilder::new()
+            .column("user_id".to_string())
+            .column("session_id".to_string())
+            .table("user_sessions".to_string())
+            .value(Value::Integer(12345))
+            .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+            .ttl(3600)
+            .if_not_exists()
+            .build().unwrap();
 
         assert_eq!(parse_query(query), Ok(expected_result));
     }
@@ -305,23 +1065,150 @@ mod test {
 
         let expected_result = UpdateQueryBuilder::new()
             .table("user_sessions".to_string())
-            .value(("type".to_string(), Value::String("LAPTOP".to_string())))
-            .value(("timestamp".to_string(), Value::String("2024-11-08 00:00:00".to_string())))
+            .value(("type".to_string(), Value::String("LAPTOP".into())))
+            .value(("timestamp".to_string(), Value::String("2024-11-08 00:00:00".into())))
             .condition(ConditionBuilder::new()
                 .column("user_id".to_string())
                 .operator(Operator::Equals)
                 .value(Value::Integer(12345))
-                .build())
+                .build().unwrap())
             .condition(ConditionBuilder::new()
                 .column("session_id".to_string())
                 .operator(Operator::Equals)
-                .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".to_string()))
-                .build())
-            .build();
+                .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+                .build().unwrap())
+            .build().unwrap();
 
         assert_eq!(parse_query(query), Ok(expected_result));
     }
 
+    #[test]
+    fn test_parse_update_request_with_using_clause() {
+        let query = r#"
+        UPDATE user_sessions USING TIMESTAMP 1699000000000 AND TTL 86400
+        SET type = 'LAPTOP'
+        WHERE user_id = 12345"#;
+
+        let expected_result = UpdateQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .value(("type".to_string(), Value::String("LAPTOP".into())))
+            .condition(ConditionBuilder::new()
+                .column("user_id".to_string())
+                .operator(Operator::Equals)
+                .value(Value::Integer(12345))
+                .build().unwrap())
+            .ttl(86400)
+            .timestamp(1699000000000)
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_consistency() {
+        let params = vec![
+            (
+                "SELECT * FROM user_sessions USING CONSISTENCY local_quorum WHERE user_id = 1",
+                SelectQueryBuilder::new()
+                    .table("user_sessions")
+                    .condition(ConditionBuilder::new().column("user_id").operator(Operator::Equals).value(Value::Integer(1)).build().unwrap())
+                    .consistency(Consistency::LocalQuorum)
+                    .build().unwrap()
+            ),
+            (
+                "INSERT INTO user_sessions (user_id) VALUES (1) IF NOT EXISTS USING TTL 60 AND CONSISTENCY ONE AND SERIAL CONSISTENCY LOCAL_SERIAL",
+                InsertQueryBuilder::new()
+                    .column("user_id")
+                    .table("user_sessions")
+                    .value(Value::Integer(1))
+                    .if_not_exists()
+                    .ttl(60)
+                    .consistency(Consistency::One)
+                    .serial_consistency(Consistency::LocalSerial)
+                    .build().unwrap()
+            ),
+            (
+                "DELETE FROM user_sessions USING CONSISTENCY ALL WHERE user_id = 1",
+                DeleteQueryBuilder::new()
+                    .table("user_sessions")
+                    .condition(ConditionBuilder::new().column("user_id").operator(Operator::Equals).value(Value::Integer(1)).build().unwrap())
+                    .consistency(Consistency::All)
+                    .build().unwrap()
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result), "{}", query);
+        }
+
+        let params = vec![
+            "SELECT * FROM user_sessions USING TTL 60",
+            "SELECT * FROM user_sessions USING CONSISTENCY QUORUMS",
+            "UPDATE user_sessions USING SERIAL CONSISTENCY ONE SET type = 'PHONE' WHERE user_id = 1",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_parse_conditional_update_request() {
+        let params = vec![
+            (
+                r#"
+                UPDATE user_sessions
+                SET type = 'LAPTOP'
+                WHERE user_id = 12345
+                IF EXISTS
+                "#,
+                UpdateQueryBuilder::new()
+                    .table("user_sessions".to_string())
+                    .value(("type".to_string(), Value::String("LAPTOP".into())))
+                    .condition(ConditionBuilder::new()
+                        .column("user_id".to_string())
+                        .operator(Operator::Equals)
+                        .value(Value::Integer(12345))
+                        .build().unwrap())
+                    .if_clause(IfClause::Exists)
+                    .build().unwrap()
+            ),
+            (
+                r#"
+                UPDATE user_sessions
+                SET type = 'LAPTOP'
+                WHERE user_id = 12345
+                IF type = 'PHONE' AND device_type != 'TABLET'
+                "#,
+                UpdateQueryBuilder::new()
+                    .table("user_sessions".to_string())
+                    .value(("type".to_string(), Value::String("LAPTOP".into())))
+                    .condition(ConditionBuilder::new()
+                        .column("user_id".to_string())
+                        .operator(Operator::Equals)
+                        .value(Value::Integer(12345))
+                        .build().unwrap())
+                    .if_clause(IfClause::Conditions(vec![
+                        ConditionBuilder::new()
+                            .column("type".to_string())
+                            .operator(Operator::Equals)
+                            .value(Value::String("PHONE".into()))
+                            .build().unwrap(),
+                        ConditionBuilder::new()
+                            .column("device_type".to_string())
+                            .operator(Operator::NotEquals)
+                            .value(Value::String("TABLET".into()))
+                            .build().unwrap(),
+                    ]))
+                    .build().unwrap()
+            )
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result));
+        }
+    }
+
     #[test]
     fn test_parse_delete() {
         let params = vec![
@@ -337,13 +1224,13 @@ mod test {
                         .column("user_id".to_string())
                         .operator(Operator::Equals)
                         .value(Value::Integer(12345))
-                        .build())
+                        .build().unwrap())
                     .condition(ConditionBuilder::new()
                         .column("session_id".to_string())
                         .operator(Operator::Equals)
-                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".to_string()))
-                        .build())
-                    .build()
+                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+                        .build().unwrap())
+                    .build().unwrap()
             ),
             (
                 r#"
@@ -360,13 +1247,13 @@ mod test {
                         .column("user_id".to_string())
                         .operator(Operator::Equals)
                         .value(Value::Integer(12345))
-                        .build())
+                        .build().unwrap())
                     .condition(ConditionBuilder::new()
                         .column("session_id".to_string())
                         .operator(Operator::Equals)
-                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".to_string()))
-                        .build())
-                    .build()
+                        .value(Value::String("3e3be9fb-5888-4b0e-8f22-287b7d90a32f".into()))
+                        .build().unwrap())
+                    .build().unwrap()
             )
         ];
 
@@ -374,4 +1261,118 @@ mod test {
             assert_eq!(parse_query(query), Ok(expected_result));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_conditional_delete() {
+        let condition = |column: &'static str, value: Value<'static>| ConditionBuilder::new()
+            .column(column)
+            .operator(Operator::Equals)
+            .value(value)
+            .build().unwrap();
+
+        let params = vec![
+            (
+                "DELETE FROM user_sessions WHERE user_id = 1 IF EXISTS",
+                DeleteQueryBuilder::new()
+                    .table("user_sessions")
+                    .condition(condition("user_id", Value::Integer(1)))
+                    .if_clause(IfClause::Exists)
+                    .build().unwrap()
+            ),
+            (
+                "DELETE type FROM user_sessions WHERE user_id = 1 IF type = 'PHONE' AND visits = 3",
+                DeleteQueryBuilder::new()
+                    .column("type")
+                    .table("user_sessions")
+                    .condition(condition("user_id", Value::Integer(1)))
+                    .if_clause(IfClause::Conditions(vec![
+                        condition("type", Value::String("PHONE".into())),
+                        condition("visits", Value::Integer(3)),
+                    ]))
+                    .build().unwrap()
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(expected_result));
+        }
+    }
+
+    #[test]
+    fn test_parse_delete_with_timestamp() {
+        let query = r#"
+        DELETE FROM user_sessions
+        USING TIMESTAMP 1699000000000
+        WHERE user_id = 12345"#;
+
+        let expected_result = DeleteQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .condition(ConditionBuilder::new()
+                .column("user_id".to_string())
+                .operator(Operator::Equals)
+                .value(Value::Integer(12345))
+                .build().unwrap())
+            .timestamp(1699000000000)
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+        assert!(parse_query("DELETE FROM user_sessions USING TTL 3600 WHERE user_id = 12345").is_err());
+    }
+
+    #[test]
+    fn test_parse_qualified_columns() {
+        let params = vec![
+            (
+                "INSERT INTO analytics.user_sessions (user_sessions.user_id, device_type) VALUES (1, 'PHONE')",
+                "INSERT INTO analytics.user_sessions (user_id, device_type) VALUES (1, 'PHONE')",
+            ),
+            (
+                "UPDATE user_sessions SET user_sessions.visits = user_sessions.visits + 1 WHERE user_sessions.user_id = 1",
+                "UPDATE user_sessions SET visits = user_sessions.visits + 1 WHERE user_sessions.user_id = 1",
+            ),
+            (
+                "DELETE user_sessions.device_type FROM analytics.user_sessions WHERE user_id = 1",
+                "DELETE device_type FROM analytics.user_sessions WHERE user_id = 1",
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), parse_query(expected_result), "{}", query);
+        }
+
+        let params = vec![
+            ("INSERT INTO user_sessions (devices.user_id) VALUES (1)", 27),
+            ("UPDATE user_sessions SET devices.visits = 1 WHERE user_id = 1", 26),
+            ("DELETE devices.device_type FROM user_sessions WHERE user_id = 1", 8),
+        ];
+
+        for (query, expected_column) in params {
+            match parse_query(query) {
+                Err(QueryParsingError::QuerySyntaxError(error)) => assert_eq!(error.column, expected_column, "{}", query),
+                result => panic!("expected a syntax error, got {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_reject_trailing_input() {
+        let params = vec![
+            ("SELECT * FROM readings PER PARTITION LIMIT 1 LIMIT 10", "LIMIT 10"),
+            ("SELECT * FROM readings WHERE sensor_id = 1 ORDER BY reading_time DESC", "ORDER BY"),
+            ("SELECT * FROM readings WHERE sensor_id = 1 garbage", "garbage"),
+            ("SELECT * FROM readings ALLOW FILTERING PER PARTITION LIMIT 1", "PER PARTITION"),
+            ("INSERT INTO user_sessions (user_id, timestamp) VALUES (1, 2) USING TTL 60 IF NOT EXISTS", "IF NOT EXISTS"),
+            ("UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 IF device_type = 'TABLET' junk", "junk"),
+            ("DELETE FROM user_sessions WHERE user_id = 1 IF EXISTS AND device_type = 'PHONE'", "AND device_type"),
+        ];
+
+        for (query, rest) in params {
+            match parse_query(query) {
+                Err(QueryParsingError::QuerySyntaxError(error)) => assert_eq!(error.offset, query.find(rest).unwrap(), "{}", query),
+                result => panic!("expected a syntax error for {}, got {:?}", query, result),
+            }
+        }
+
+        assert!(parse_query("DELETE FROM user_sessions WHERE user_id = 1 IF EXISTS -- done").is_ok());
+    }
+}
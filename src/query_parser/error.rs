@@ -1,14 +1,120 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use crate::query_parser::query::QueryParsingError;
+use nom::error::{Error as NomError, ErrorKind};
+use nom::Err as NomErr;
+use crate::query_parser::common_parser::{is_reserved_word, INVALID_TIMESTAMP};
+use crate::query_parser::query::{BindError, BuilderError, QueryParsingError, SyntaxError};
+
+impl SyntaxError {
+    pub(crate) fn new(source: &str, offset: usize, message: String, expected: Vec<String>) -> Self {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[offset..].find('\n').map_or(source.len(), |index| offset + index);
+        let line = source[..offset].matches('\n').count() + 1;
+        let column = source[line_start..offset].chars().count() + 1;
+        let snippet = format!("{}\n{}^", &source[line_start..line_end], " ".repeat(column - 1));
+
+        Self {
+            message,
+            offset,
+            line,
+            column,
+            expected,
+            snippet,
+        }
+    }
+
+    pub(crate) fn relocate(self, source: &str, offset: usize) -> Self {
+        SyntaxError::new(source, offset + self.offset, self.message, self.expected)
+    }
+}
+
+pub(crate) fn syntax_error(source: &str, error: NomErr<NomError<&str>>, message: &str, expected: &[&str]) -> QueryParsingError {
+    if let NomErr::Failure(NomError { input, code: INVALID_TIMESTAMP }) = error {
+        return QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len() - input.len(), "invalid timestamp literal".to_string(), Vec::new()));
+    }
+
+    let (offset, code) = match error {
+        NomErr::Error(error) | NomErr::Failure(error) => (source.len() - error.input.len(), Some(error.code)),
+        NomErr::Incomplete(_) => (source.len(), None),
+    };
+
+    if code == Some(ErrorKind::TooLarge) {
+        return QueryParsingError::QuerySyntaxError(SyntaxError::new(source, offset, "numeric literal out of range".to_string(), Vec::new()));
+    }
+
+    let expected = expected.iter().map(|token| token.to_string()).collect();
+    // A list stops before the separator when the next element can't be parsed, so the word that
+    // stopped it may follow a comma or an opening parenthesis.
+    let word = source[offset..]
+        .trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',' || ch == '(')
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .next()
+        .unwrap_or_default();
+
+    let message = if is_reserved_word(word) {
+        format!("{}; {} is a reserved keyword, double-quote it to use it as a name", message, word)
+    } else {
+        message.to_string()
+    };
+
+    QueryParsingError::QuerySyntaxError(SyntaxError::new(source, offset, message, expected))
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at line {}, column {}", self.message, self.line, self.column)?;
+
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.join(" or "))?;
+        }
+
+        write!(f, "\n{}", self.snippet)
+    }
+}
 
 impl Display for QueryParsingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             QueryParsingError::UnsupportedRequest(query) => write!(f, "the request {} is not supported", query),
-            QueryParsingError::QuerySyntaxError(msg, query) => write!(f, "an syntax error {} occurred while parsing the request {}", msg, query)
+            QueryParsingError::QuerySyntaxError(error) => write!(f, "an syntax error occurred: {}", error),
+            QueryParsingError::InvalidQuery(error) => write!(f, "an invalid query: {}", error),
+            QueryParsingError::InvalidParameters(error) => write!(f, "invalid query parameters: {}", error),
+        }
+    }
+}
+
+impl Error for QueryParsingError {}
+
+impl From<BuilderError> for QueryParsingError {
+    fn from(error: BuilderError) -> Self {
+        QueryParsingError::InvalidQuery(error)
+    }
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuilderError::MissingField(field) => write!(f, "the {} field is not set", field),
+        }
+    }
+}
+
+impl Error for BuilderError {}
+
+impl From<BindError> for QueryParsingError {
+    fn from(error: BindError) -> Self {
+        QueryParsingError::InvalidParameters(error)
+    }
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindError::ArityMismatch { expected, actual } => write!(f, "the query has {} placeholders but {} parameters were given", expected, actual),
+            BindError::NonNumericOperand { index } => write!(f, "the parameter {} is used in arithmetic but isn't a number or a collection", index),
         }
     }
 }
 
-impl Error for QueryParsingError {}
\ No newline at end of file
+impl Error for BindError {}
@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+use crate::query_parser::common_parser::{fold_case, parse_collection_literal, parse_identifier, parse_number, parse_quoted_identifier, parse_string, parse_timestamp_literal, parse_unreserved_identifier, parse_user_type_literal, parse_uuid, ws};
+use crate::query_parser::keyword::{FALSE, NULL, TRUE};
+use crate::query_parser::query::{ArithmeticOperator, Expression, Value};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::map;
+use nom::multi::{many0, many1, separated_list0};
+use nom::sequence::{delimited, pair, preceded, separated_pair};
+use nom::IResult;
+
+pub(crate) fn parse_expression(input: &str) -> IResult<&str, Expression<'_>> {
+    let additive_operator = ws(alt((
+        map(tag("+"), |_| ArithmeticOperator::Add),
+        map(tag("-"), |_| ArithmeticOperator::Subtract),
+    )));
+
+    let (input, first) = parse_term(input)?;
+    let (input, rest) = many0(pair(additive_operator, parse_term))(input)?;
+
+    Ok((input, fold_binary(first, rest)))
+}
+
+fn parse_term(input: &str) -> IResult<&str, Expression<'_>> {
+    let multiplicative_operator = ws(alt((
+        map(tag("*"), |_| ArithmeticOperator::Multiply),
+        map(tag("/"), |_| ArithmeticOperator::Divide),
+        map(tag("%"), |_| ArithmeticOperator::Modulo),
+    )));
+
+    let (input, first) = parse_factor(input)?;
+    let (input, rest) = many0(pair(multiplicative_operator, parse_factor))(input)?;
+
+    Ok((input, fold_binary(first, rest)))
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Expression<'_>> {
+    alt((
+        map(parse_uuid, Expression::Literal),
+        map(parse_timestamp_literal, Expression::Literal),
+        map(parse_number, Expression::Literal),
+        map(parse_string, Expression::Literal),
+        map(parse_collection_literal, Expression::Literal),
+        map(parse_user_type_literal, Expression::Literal),
+        map(ws(tag("?")), |_| Expression::Placeholder),
+        map(preceded(ws(tag(":")), parse_identifier), Expression::NamedPlaceholder),
+        delimited(ws(tag("(")), parse_expression, ws(tag(")"))),
+        parse_tuple,
+        parse_function,
+        parse_qualified_column,
+        map(parse_quoted_identifier, Expression::Column),
+        map(parse_unreserved_identifier, parse_column_or_literal),
+    ))(input)
+}
+
+fn parse_function(input: &str) -> IResult<&str, Expression<'_>> {
+    let arguments = delimited(
+        ws(tag("(")),
+        separated_list0(ws(tag(",")), parse_expression),
+        ws(tag(")")),
+    );
+
+    map(
+        pair(parse_identifier, arguments),
+        |(name, arguments)| Expression::Function(fold_case(name), arguments),
+    )(input)
+}
+
+fn parse_tuple(input: &str) -> IResult<&str, Expression<'_>> {
+    let elements = pair(parse_expression, many1(preceded(ws(tag(",")), parse_expression)));
+
+    map(
+        delimited(ws(tag("(")), elements, ws(tag(")"))),
+        |(first, rest)| Expression::Tuple(std::iter::once(first).chain(rest).collect()),
+    )(input)
+}
+
+fn parse_qualified_column(input: &str) -> IResult<&str, Expression<'_>> {
+    map(
+        separated_pair(parse_identifier, tag("."), parse_identifier),
+        |(qualifier, column)| Expression::QualifiedColumn(qualifier, column),
+    )(input)
+}
+
+fn parse_column_or_literal(identifier: Cow<'_, str>) -> Expression<'_> {
+    if identifier.eq_ignore_ascii_case(TRUE) {
+        Expression::Literal(Value::Bool(true))
+    } else if identifier.eq_ignore_ascii_case(FALSE) {
+        Expression::Literal(Value::Bool(false))
+    } else if identifier.eq_ignore_ascii_case(NULL) {
+        Expression::Literal(Value::Null)
+    } else {
+        Expression::Column(identifier)
+    }
+}
+
+fn fold_binary<'a>(first: Expression<'a>, rest: Vec<(ArithmeticOperator, Expression<'a>)>) -> Expression<'a> {
+    rest.into_iter().fold(first, |left, (operator, right)| {
+        Expression::Binary(Box::new(left), operator, Box::new(right))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn column(name: &str) -> Box<Expression<'_>> {
+        Box::new(Expression::Column(name.into()))
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let params = vec![
+            ("price", Expression::Column("price".into())),
+            ("TRUE", Expression::Literal(Value::Bool(true))),
+            (
+                "price * quantity",
+                Expression::Binary(column("price"), ArithmeticOperator::Multiply, column("quantity")),
+            ),
+            (
+                "price + tax * quantity",
+                Expression::Binary(
+                    column("price"),
+                    ArithmeticOperator::Add,
+                    Box::new(Expression::Binary(column("tax"), ArithmeticOperator::Multiply, column("quantity"))),
+                ),
+            ),
+            (
+                "(price + tax) * 1.2",
+                Expression::Binary(
+                    Box::new(Expression::Binary(column("price"), ArithmeticOperator::Add, column("tax"))),
+                    ArithmeticOperator::Multiply,
+                    Box::new(Expression::Literal(Value::Float(1.2))),
+                ),
+            ),
+            (
+                "UPPER(name)",
+                Expression::Function("upper".into(), vec![Expression::Column("name".into())]),
+            ),
+            (
+                "3e3be9fb-5888-4b0e-8f22-287b7d90a32f",
+                Expression::Literal(Value::Uuid(0x3e3be9fb_5888_4b0e_8f22_287b7d90a32f)),
+            ),
+            ("\"True\"", Expression::Column("True".into())),
+            (":Sensor", Expression::NamedPlaceholder("sensor".into())),
+            (":\"Sensor\" + ?", Expression::Binary(Box::new(Expression::NamedPlaceholder("Sensor".into())), ArithmeticOperator::Add, Box::new(Expression::Placeholder))),
+            ("(blog_id)", Expression::Column("blog_id".into())),
+            (
+                "(blog_id, post_id + 1)",
+                Expression::Tuple(vec![
+                    Expression::Column("blog_id".into()),
+                    Expression::Binary(column("post_id"), ArithmeticOperator::Add, Box::new(Expression::Literal(Value::Integer(1)))),
+                ]),
+            ),
+            ("Sensor1", Expression::Column("sensor1".into())),
+            (
+                "TIMESTAMP '2024-10-21 00:00:00'",
+                Expression::Literal(Value::Timestamp(1_729_468_800_000)),
+            ),
+            (
+                "timestamp",
+                Expression::Column("timestamp".into()),
+            ),
+            (
+                "toTimestamp(now())",
+                Expression::Function("totimestamp".into(), vec![Expression::Function("now".into(), Vec::new())]),
+            ),
+            (
+                "concat(name, ' ', surname)",
+                Expression::Function("concat".into(), vec![
+                    Expression::Column("name".into()),
+                    Expression::Literal(Value::String(" ".into())),
+                    Expression::Column("surname".into()),
+                ]),
+            ),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_expression(input), Ok(("", expected_result)));
+        }
+    }
+}
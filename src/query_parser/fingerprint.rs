@@ -0,0 +1,96 @@
+use crate::query_parser::binder::literals;
+use crate::query_parser::parser::parse_query;
+use crate::query_parser::query::{Expression, Query, Value};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Renders the shape of a query: its normalized CQL with every literal replaced by `?`, so
+/// statements that differ only in their values render the same. `NULL` is kept since `IS NULL`
+/// takes no placeholder. Statements other than reads and writes have no literals to replace.
+pub fn normalize(query: &Query) -> String {
+    let source = query.to_string();
+
+    // The query is parsed back from its own rendering to get a copy the literals can be
+    // replaced in. A rendered query always parses, the fallback only guards against a bug.
+    let mut copy = match parse_query(&source) {
+        Ok(copy) => copy,
+        Err(_) => return source,
+    };
+
+    for literal in literals(&mut copy) {
+        if !matches!(literal, Expression::Literal(Value::Null)) {
+            *literal = Expression::Placeholder;
+        }
+    }
+
+    copy.to_string()
+}
+
+/// Hashes the shape of a query with 64-bit FNV-1a, which is stable across runs and builds, so
+/// fingerprints can be stored and compared between processes.
+pub fn fingerprint(query: &Query) -> u64 {
+    normalize(query)
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::arbitrary::arb_query;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_normalize() {
+        let params = vec![
+            (
+                "select * from posts where blog_id = 1 and (posted_at, post_id) > (TIMESTAMP '2024-10-21', 20)",
+                "SELECT * FROM posts WHERE blog_id = ? AND (posted_at, post_id) > (?, ?)",
+            ),
+            (
+                "INSERT INTO users (user_id, email) VALUES (1, 'a@b.c') USING TTL 60",
+                "INSERT INTO users (user_id, email) VALUES (?, ?) USING TTL 60",
+            ),
+            (
+                "UPDATE page_views SET hits = hits + 1 WHERE page = ? IF hits IS NOT NULL",
+                "UPDATE page_views SET hits = hits + ? WHERE page = ? IF hits IS NOT NULL",
+            ),
+            (
+                "SELECT time_bucket('5 minutes', seen_at) FROM readings WHERE token(sensor_id) > -5",
+                "SELECT time_bucket(?, seen_at) FROM readings WHERE token(sensor_id) > ?",
+            ),
+            (
+                "CREATE TABLE t (a INT PRIMARY KEY) WITH default_time_to_live = 60",
+                "CREATE TABLE t (a INT, PRIMARY KEY ((a))) WITH default_time_to_live = 60",
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(normalize(&parse_query(query).unwrap()), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let fingerprint_of = |query| fingerprint(&parse_query(query).unwrap());
+
+        let shape = fingerprint_of("SELECT * FROM user_sessions WHERE user_id = 1");
+
+        assert_eq!(fingerprint_of("select *  from user_sessions where user_id = 42"), shape);
+        assert_eq!(fingerprint_of("SELECT * FROM user_sessions WHERE user_id = ?"), shape);
+        assert_ne!(fingerprint_of("SELECT * FROM user_sessions WHERE session_id = 1"), shape);
+        assert_ne!(fingerprint_of("SELECT * FROM user_sessions WHERE user_id IS NULL"), shape);
+    }
+
+    proptest! {
+        #[test]
+        fn test_normalize_is_stable(query in arb_query()) {
+            let normalized = normalize(&query);
+            let reparsed = parse_query(&normalized);
+
+            prop_assert!(reparsed.is_ok(), "{}", normalized);
+            prop_assert_eq!(normalize(&reparsed.unwrap()), normalized);
+        }
+    }
+}
@@ -0,0 +1,840 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result};
+use crate::query_parser::common_parser::quote_string_literal;
+use crate::query_parser::keyword::*;
+use crate::query_parser::query::{AdminQuery, AlterTableCondition, AlterTableQuery, ArithmeticOperator, BulkQuery, ClusteringOrder, Column, ColumnConstraint, ColumnType, CompactionOptions, Consistency, CreateFunctionQuery, CreateMaterializedViewQuery, CreateTypeQuery, PrimaryKey, CompactionStrategy, CompressionCodec, CompressionOptions, Condition, CopyQuery, CreateKeyspaceQuery, CreateTableQuery, DataDefinitionQuery, DataManipulationQuery, DeleteQuery, DropTableQuery, Expression, IfClause, InsertQuery, MetaQuery, Operator, Query, ReplicationOptions, SelectQuery, Selector, SessionQuery, TableOptions, UpdateQuery, Value};
+use crate::query_parser::timestamp::format_timestamp;
+
+impl Query<'_> {
+    /// Renders the query as normalized CQL that parses back into an equal query.
+    pub fn to_cql_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for Query<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Query::DataManipulationQuery(query) => write!(f, "{}", query),
+            Query::DataDefinitionQuery(query) => write!(f, "{}", query),
+            Query::SessionQuery(SessionQuery::Use(query)) => write!(f, "{} {}", USE, identifier(&query.keyspace)),
+            Query::SessionQuery(SessionQuery::Tracing(query)) => write!(f, "{} {}", TRACING, if query.enabled { ON } else { OFF }),
+            Query::BulkQuery(query) => write!(f, "{}", query),
+            Query::MetaQuery(query) => write!(f, "{}", query),
+            Query::AdminQuery(AdminQuery::Compact(query)) => write!(f, "{} {}", COMPACT, table_name(&query.keyspace, &query.table)),
+            Query::AdminQuery(AdminQuery::Flush(query)) => write!(f, "{} {}", FLUSH, table_name(&query.keyspace, &query.table)),
+            Query::Explain(query) => write!(f, "{} {}", EXPLAIN, query),
+            Query::Transaction(statements) => {
+                write!(f, "{} ", BEGIN_TRANSACTION)?;
+                write_list(f, statements, "; ")?;
+                write!(f, "; {}", COMMIT)
+            }
+        }
+    }
+}
+
+impl Display for DataManipulationQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            DataManipulationQuery::Select(query) => write!(f, "{}", query),
+            DataManipulationQuery::Insert(query) => write!(f, "{}", query),
+            DataManipulationQuery::Update(query) => write!(f, "{}", query),
+            DataManipulationQuery::Delete(query) => write!(f, "{}", query),
+        }
+    }
+}
+
+impl Display for SelectQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", SELECT)?;
+
+        if self.json {
+            write!(f, "{} ", JSON)?;
+        }
+
+        if self.distinct {
+            write!(f, "{} ", DISTINCT)?;
+        }
+
+        if self.columns.is_empty() {
+            write!(f, "*")?;
+        } else {
+            write_list(f, &self.columns, ", ")?;
+        }
+
+        write!(f, " {} {}", FROM, table_name(&self.keyspace, &self.table))?;
+
+        if let Some(table_alias) = &self.table_alias {
+            write!(f, " {} {}", AS, identifier(table_alias))?;
+        }
+
+        write_using(f, None, None, self.consistency, self.serial_consistency)?;
+        write_conditions(f, &self.conditions)?;
+
+        if let Some(per_partition_limit) = self.per_partition_limit {
+            write!(f, " {} {}", PER_PARTITION_LIMIT, per_partition_limit)?;
+        }
+
+        if self.allow_filtering {
+            write!(f, " {}", ALLOW_FILTERING)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for InsertQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {}", INSERT_INTO, table_name(&self.keyspace, &self.table))?;
+
+        match &self.json {
+            Some(json) => write!(f, " {} {}", JSON, quote_string_literal(json))?,
+            None => {
+                write!(f, " (")?;
+                write_list(f, &self.columns.iter().map(|column| identifier(column)).collect::<Vec<_>>(), ", ")?;
+                write!(f, ") {} ", VALUES)?;
+
+                for (index, row) in self.values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "(")?;
+                    write_list(f, row, ", ")?;
+                    write!(f, ")")?;
+                }
+            }
+        }
+
+        if self.if_not_exists {
+            write!(f, " {}", IF_NOT_EXISTS)?;
+        }
+
+        write_using(f, self.ttl, self.timestamp, self.consistency, self.serial_consistency)
+    }
+}
+
+impl Display for UpdateQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {}", UPDATE, table_name(&self.keyspace, &self.table))?;
+        write_using(f, self.ttl, self.timestamp, self.consistency, self.serial_consistency)?;
+        write!(f, " {} ", SET)?;
+
+        let assignments: Vec<String> = self.values
+            .iter()
+            .map(|(column, value)| format!("{} {} {}", identifier(column), EQUALS, value))
+            .collect();
+
+        write_list(f, &assignments, ", ")?;
+        write_conditions(f, &self.conditions)?;
+        write_if_clause(f, &self.if_clause)
+    }
+}
+
+impl Display for DeleteQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", DELETE)?;
+
+        if !self.columns.is_empty() {
+            write_list(f, &self.columns.iter().map(|column| identifier(column)).collect::<Vec<_>>(), ", ")?;
+            write!(f, " ")?;
+        }
+
+        write!(f, "{} {}", FROM, table_name(&self.keyspace, &self.table))?;
+
+        write_using(f, None, self.timestamp, self.consistency, self.serial_consistency)?;
+        write_conditions(f, &self.conditions)?;
+        write_if_clause(f, &self.if_clause)
+    }
+}
+
+impl Display for DataDefinitionQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            DataDefinitionQuery::CreateKeyspace(query) => write!(f, "{}", query),
+            DataDefinitionQuery::CreateTable(query) => write!(f, "{}", query),
+            DataDefinitionQuery::CreateMaterializedView(query) => write!(f, "{}", query),
+            DataDefinitionQuery::CreateFunction(query) => write!(f, "{}", query),
+            DataDefinitionQuery::CreateType(query) => write!(f, "{}", query),
+            DataDefinitionQuery::AlterTable(query) => write!(f, "{}", query),
+            DataDefinitionQuery::DropTable(query) => write!(f, "{}", query),
+        }
+    }
+}
+
+impl Display for CreateKeyspaceQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", CREATE_KEYSPACE)?;
+
+        if self.if_not_exists {
+            write!(f, "{} ", IF_NOT_EXISTS)?;
+        }
+
+        write!(f, "{}", identifier(&self.keyspace))?;
+
+        let mut options = Vec::new();
+
+        if let Some(replication) = &self.replication {
+            options.push(replication.to_string());
+        }
+
+        if let Some(durable_writes) = self.durable_writes {
+            options.push(format!("{} = {}", DURABLE_WRITES.to_lowercase(), durable_writes));
+        }
+
+        if !options.is_empty() {
+            write!(f, " {} {}", WITH, options.join(&format!(" {} ", AND)))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ReplicationOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} = {{", REPLICATION.to_lowercase())?;
+
+        match self {
+            ReplicationOptions::Simple { replication_factor } => write!(f, "'class': 'SimpleStrategy', 'replication_factor': {}", replication_factor)?,
+            ReplicationOptions::NetworkTopology { datacenters } => {
+                write!(f, "'class': 'NetworkTopologyStrategy'")?;
+
+                for (datacenter, replication_factor) in datacenters {
+                    write!(f, ", {}: {}", quote_string_literal(datacenter), replication_factor)?;
+                }
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for CreateTableQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", CREATE_TABLE)?;
+
+        if self.if_not_exists {
+            write!(f, "{} ", IF_NOT_EXISTS)?;
+        }
+
+        let columns: Vec<String> = self.columns.iter()
+            .map(|column| {
+                let mut definition = column.to_string();
+
+                if self.static_columns.contains(&column.name) {
+                    definition = format!("{} {}", definition, STATIC);
+                }
+
+                if let Some((_, default)) = self.defaults.iter().find(|(name, _)| *name == column.name) {
+                    definition = format!("{} {} {}", definition, DEFAULT, default);
+                }
+
+                for constraint in self.constraints.iter().filter(|constraint| constraint.column() == column.name) {
+                    definition = match constraint {
+                        ColumnConstraint::NotNull(_) => format!("{} {}", definition, NOT_NULL),
+                        ColumnConstraint::Check(_, check) => format!("{} {} ({})", definition, CHECK, check),
+                        ColumnConstraint::References { keyspace, table, .. } => format!("{} {} {}", definition, REFERENCES, table_name(keyspace, table)),
+                    };
+                }
+
+                definition
+            })
+            .collect();
+
+        write!(f, "{} ({}", table_name(&self.keyspace, &self.table), columns.join(", "))?;
+        write!(f, ", {})", self.primary_key)?;
+        write!(f, "{}", self.options)
+    }
+}
+
+impl Display for CreateMaterializedViewQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", CREATE_MATERIALIZED_VIEW)?;
+
+        if self.if_not_exists {
+            write!(f, "{} ", IF_NOT_EXISTS)?;
+        }
+
+        write!(f, "{} {} {} ", table_name(&self.keyspace, &self.view), AS, SELECT)?;
+
+        if self.columns.is_empty() {
+            write!(f, "*")?;
+        } else {
+            let columns: Vec<String> = self.columns.iter().map(|column| identifier(column)).collect();
+            write!(f, "{}", columns.join(", "))?;
+        }
+
+        write!(f, " {} {}", FROM, table_name(&self.base_keyspace, &self.base_table))?;
+
+        if !self.conditions.is_empty() {
+            write!(f, " {} ", WHERE)?;
+            write_list(f, &self.conditions, &format!(" {} ", AND))?;
+        }
+
+        write!(f, " {}{}", self.primary_key, self.options)
+    }
+}
+
+impl Display for CreateFunctionQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", CREATE_FUNCTION)?;
+
+        if self.if_not_exists {
+            write!(f, "{} ", IF_NOT_EXISTS)?;
+        }
+
+        write!(f, "{} (", table_name(&self.keyspace, &self.function))?;
+        write_list(f, &self.arguments, ", ")?;
+        write!(f, ") {} {} {} {}", RETURNS, self.return_type, AS, quote_string_literal(&self.body))
+    }
+}
+
+impl Display for CreateTypeQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", CREATE_TYPE)?;
+
+        if self.if_not_exists {
+            write!(f, "{} ", IF_NOT_EXISTS)?;
+        }
+
+        write!(f, "{} (", table_name(&self.keyspace, &self.name))?;
+        write_list(f, &self.fields, ", ")?;
+        write!(f, ")")
+    }
+}
+
+impl Display for PrimaryKey<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let partition_key: Vec<String> = self.partition_key.iter().map(|column| identifier(column)).collect();
+        write!(f, "{} (({})", PRIMARY_KEY, partition_key.join(", "))?;
+
+        for column in &self.clustering_key {
+            write!(f, ", {}", identifier(column))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl Display for TableOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut options = Vec::new();
+
+        if let Some(compression) = &self.compression {
+            options.push(compression.to_string());
+        }
+
+        if let Some(compaction) = &self.compaction {
+            options.push(compaction.to_string());
+        }
+
+        if let Some(seconds) = self.default_time_to_live {
+            options.push(format!("{} = {}", DEFAULT_TIME_TO_LIVE.to_lowercase(), seconds));
+        }
+
+        if let Some(seconds) = self.gc_grace_seconds {
+            options.push(format!("{} = {}", GC_GRACE_SECONDS.to_lowercase(), seconds));
+        }
+
+        if let Some(enabled) = self.validate_references {
+            options.push(format!("{} = {}", VALIDATE_REFERENCES.to_lowercase(), enabled));
+        }
+
+        if !self.clustering_order.is_empty() {
+            let columns = self.clustering_order
+                .iter()
+                .map(|(column, order)| format!("{} {}", identifier(column), order))
+                .collect::<Vec<_>>();
+            options.push(format!("{} ({})", CLUSTERING_ORDER_BY, columns.join(", ")));
+        }
+
+        if options.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " {} {}", WITH, options.join(&format!(" {} ", AND)))
+    }
+}
+
+impl Display for CompressionOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} = {{", COMPRESSION.to_lowercase())?;
+
+        match self.codec {
+            CompressionCodec::None => write!(f, "'enabled': false")?,
+            CompressionCodec::Lz4 => write!(f, "'class': 'LZ4Compressor'")?,
+            CompressionCodec::Snappy => write!(f, "'class': 'SnappyCompressor'")?,
+            CompressionCodec::Zstd => write!(f, "'class': 'ZstdCompressor'")?,
+        }
+
+        if let Some(chunk_length_in_kb) = self.chunk_length_in_kb {
+            write!(f, ", 'chunk_length_in_kb': {}", chunk_length_in_kb)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for CompactionOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let class = match self.strategy {
+            CompactionStrategy::SizeTiered => "SizeTieredCompactionStrategy",
+            CompactionStrategy::Leveled => "LeveledCompactionStrategy",
+            CompactionStrategy::TimeWindow => "TimeWindowCompactionStrategy",
+        };
+
+        write!(f, "{} = {{'class': '{}'", COMPACTION.to_lowercase(), class)?;
+
+        for (key, value) in &self.options {
+            write!(f, ", {}: {}", quote_string_literal(key), value)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for ClusteringOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ClusteringOrder::Asc => write!(f, "{}", ASC),
+            ClusteringOrder::Desc => write!(f, "{}", DESC),
+        }
+    }
+}
+
+impl Display for Column<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {}", identifier(&self.name), self.column_type)
+    }
+}
+
+impl Display for ColumnType<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let column_type = match self {
+            ColumnType::Uuid => UUID,
+            ColumnType::Int => INT,
+            ColumnType::Long => LONG,
+            ColumnType::Float => FLOAT,
+            ColumnType::Double => DOUBLE,
+            ColumnType::Timestamp => TIMESTAMP,
+            ColumnType::Text => TEXT,
+            ColumnType::Bool => BOOL,
+            ColumnType::Counter => COUNTER,
+            ColumnType::Frozen(name) => return write!(f, "{}<{}>", FROZEN, identifier(name)),
+            ColumnType::List(element) => return write!(f, "{}<{}>", LIST, element),
+            ColumnType::Set(element) => return write!(f, "{}<{}>", SET, element),
+            ColumnType::Map(key, value) => return write!(f, "{}<{}, {}>", MAP, key, value),
+        };
+
+        write!(f, "{}", column_type)
+    }
+}
+
+impl Display for AlterTableQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {} ", ALTER_TABLE, table_name(&self.keyspace, &self.table))?;
+        write_list(f, &self.conditions, ", ")
+    }
+}
+
+impl Display for AlterTableCondition<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AlterTableCondition::AddColumn(condition) => write!(f, "{} {} {}", ADD, identifier(&condition.column_name), condition.column_type),
+            AlterTableCondition::DropColumn(condition) => write!(f, "{} {}", DROP, identifier(&condition.column_name)),
+            AlterTableCondition::RenameColumn(condition) => write!(f, "{} {} {} {}", RENAME, identifier(&condition.old_name), TO, identifier(&condition.new_name)),
+            AlterTableCondition::AlterColumnType(condition) => write!(f, "{} {} {} {}", ALTER, identifier(&condition.column_name), TYPE, condition.column_type),
+        }
+    }
+}
+
+impl Display for DropTableQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ", DROP_TABLE)?;
+
+        if self.if_exists {
+            write!(f, "{} ", IF_EXISTS)?;
+        }
+
+        write!(f, "{}", table_name(&self.keyspace, &self.table))
+    }
+}
+
+impl Display for BulkQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            BulkQuery::CopyFrom(query) => write_copy(f, query, FROM),
+            BulkQuery::CopyTo(query) => write_copy(f, query, TO),
+        }
+    }
+}
+
+impl Display for MetaQuery<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            MetaQuery::ShowTables(query) => match &query.keyspace {
+                Some(keyspace) => write!(f, "{} {} {}", SHOW_TABLES, FROM, identifier(keyspace)),
+                None => write!(f, "{}", SHOW_TABLES),
+            },
+            MetaQuery::DescribeTable(query) => write!(f, "{} {}", DESCRIBE_TABLE, table_name(&query.keyspace, &query.table)),
+            MetaQuery::DescribeKeyspace(query) => write!(f, "{} {}", DESCRIBE_KEYSPACE, identifier(&query.keyspace)),
+        }
+    }
+}
+
+impl Display for Condition<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {} {}", self.left, self.operator, self.right)
+    }
+}
+
+impl Display for Consistency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let level = match self {
+            Consistency::Any => ANY,
+            Consistency::One => ONE,
+            Consistency::Two => TWO,
+            Consistency::Three => THREE,
+            Consistency::Quorum => QUORUM,
+            Consistency::All => ALL,
+            Consistency::LocalQuorum => LOCAL_QUORUM,
+            Consistency::EachQuorum => EACH_QUORUM,
+            Consistency::Serial => SERIAL,
+            Consistency::LocalSerial => LOCAL_SERIAL,
+            Consistency::LocalOne => LOCAL_ONE,
+        };
+
+        write!(f, "{}", level)
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let operator = match self {
+            Operator::Equals => EQUALS,
+            Operator::NotEquals => NOT_EQUALS,
+            Operator::Greater => GREATER,
+            Operator::GreaterOrEquals => GREATER_OR_EQUALS,
+            Operator::Less => LESS,
+            Operator::LessOrEquals => LESS_OR_EQUALS,
+            Operator::Is => IS,
+            Operator::IsNot => IS_NOT,
+        };
+
+        write!(f, "{}", operator)
+    }
+}
+
+impl Display for Selector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.expression)?;
+
+        match &self.alias {
+            Some(alias) => write!(f, " {} {}", AS, identifier(alias)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for Expression<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Expression::Column(column) => write!(f, "{}", identifier(column)),
+            Expression::QualifiedColumn(qualifier, column) => write!(f, "{}.{}", identifier(qualifier), identifier(column)),
+            Expression::Literal(value) => write!(f, "{}", value),
+            Expression::Function(name, arguments) => {
+                write!(f, "{}(", name)?;
+                write_list(f, arguments, ", ")?;
+                write!(f, ")")
+            }
+            Expression::Tuple(elements) => {
+                write!(f, "(")?;
+                write_list(f, elements, ", ")?;
+                write!(f, ")")
+            }
+            Expression::Placeholder => write!(f, "?"),
+            Expression::NamedPlaceholder(name) => write!(f, ":{}", identifier(name)),
+            Expression::Binary(left, operator, right) => {
+                write_operand(f, left, precedence(operator), false)?;
+                write!(f, " {} ", operator)?;
+                write_operand(f, right, precedence(operator), true)
+            }
+        }
+    }
+}
+
+impl Display for ArithmeticOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let operator = match self {
+            ArithmeticOperator::Add => "+",
+            ArithmeticOperator::Subtract => "-",
+            ArithmeticOperator::Multiply => "*",
+            ArithmeticOperator::Divide => "/",
+            ArithmeticOperator::Modulo => "%",
+        };
+
+        write!(f, "{}", operator)
+    }
+}
+
+impl Display for Value<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) if value.is_finite() && value.fract() == 0.0 => write!(f, "{:.1}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::String(value) => write!(f, "{}", quote_string_literal(value)),
+            Value::Bool(value) => write!(f, "{}", if *value { TRUE } else { FALSE }),
+            Value::Null => write!(f, "{}", NULL),
+            Value::UserType(fields) => {
+                let fields: Vec<String> = fields.iter().map(|(name, value)| format!("{}: {}", identifier(name), value)).collect();
+                write!(f, "{{{}}}", fields.join(", "))
+            }
+            Value::List(values) => {
+                write!(f, "[")?;
+                write_list(f, values, ", ")?;
+                write!(f, "]")
+            }
+            Value::Set(values) => {
+                write!(f, "{{")?;
+                write_list(f, values, ", ")?;
+                write!(f, "}}")
+            }
+            Value::Map(entries) => {
+                let entries: Vec<String> = entries.iter().map(|(key, value)| format!("{}: {}", key, value)).collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Value::Uuid(value) => write!(
+                f,
+                "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                value >> 96,
+                (value >> 80) & 0xffff,
+                (value >> 64) & 0xffff,
+                (value >> 48) & 0xffff,
+                value & 0xffff_ffff_ffff,
+            ),
+            Value::Timestamp(value) => write!(f, "{} {}", TIMESTAMP, quote_string_literal(&format_timestamp(*value))),
+        }
+    }
+}
+
+fn write_list<T: Display>(f: &mut Formatter<'_>, items: &[T], separator: &str) -> Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+
+    Ok(())
+}
+
+fn write_conditions(f: &mut Formatter<'_>, conditions: &[Condition<'_>]) -> Result {
+    if conditions.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, " {} ", WHERE)?;
+    write_list(f, conditions, &format!(" {} ", AND))
+}
+
+fn write_if_clause(f: &mut Formatter<'_>, if_clause: &Option<IfClause<'_>>) -> Result {
+    match if_clause {
+        Some(IfClause::Exists) => write!(f, " {}", IF_EXISTS),
+        Some(IfClause::Conditions(conditions)) => {
+            write!(f, " {} ", IF)?;
+            write_list(f, conditions, &format!(" {} ", AND))
+        }
+        None => Ok(()),
+    }
+}
+
+fn write_using(f: &mut Formatter<'_>, ttl: Option<u32>, timestamp: Option<i64>, consistency: Option<Consistency>, serial_consistency: Option<Consistency>) -> Result {
+    let mut options = Vec::new();
+
+    if let Some(ttl) = ttl {
+        options.push(format!("{} {}", TTL, ttl));
+    }
+
+    if let Some(timestamp) = timestamp {
+        options.push(format!("{} {}", TIMESTAMP, timestamp));
+    }
+
+    if let Some(consistency) = consistency {
+        options.push(format!("{} {}", CONSISTENCY, consistency));
+    }
+
+    if let Some(serial_consistency) = serial_consistency {
+        options.push(format!("{} {}", SERIAL_CONSISTENCY, serial_consistency));
+    }
+
+    if options.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, " {} ", USING)?;
+    write_list(f, &options, &format!(" {} ", AND))
+}
+
+fn write_copy(f: &mut Formatter<'_>, query: &CopyQuery<'_>, direction: &str) -> Result {
+    write!(f, "{} {}", COPY, table_name(&query.keyspace, &query.table))?;
+
+    if !query.columns.is_empty() {
+        write!(f, " (")?;
+        write_list(f, &query.columns.iter().map(|column| identifier(column)).collect::<Vec<_>>(), ", ")?;
+        write!(f, ")")?;
+    }
+
+    write!(f, " {} {}", direction, quote_string_literal(&query.file))?;
+
+    if !query.options.is_empty() {
+        let options: Vec<String> = query.options
+            .iter()
+            .map(|(name, value)| format!("{} {} {}", identifier(name), EQUALS, value))
+            .collect();
+
+        write!(f, " {} ", WITH)?;
+        write_list(f, &options, &format!(" {} ", AND))?;
+    }
+
+    Ok(())
+}
+
+fn write_operand(f: &mut Formatter<'_>, operand: &Expression<'_>, parent_precedence: u8, is_right: bool) -> Result {
+    let needs_parentheses = match operand {
+        Expression::Binary(_, operator, _) => {
+            precedence(operator) < parent_precedence || (is_right && precedence(operator) == parent_precedence)
+        }
+        _ => false,
+    };
+
+    if needs_parentheses {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+fn precedence(operator: &ArithmeticOperator) -> u8 {
+    match operator {
+        ArithmeticOperator::Add | ArithmeticOperator::Subtract => 1,
+        ArithmeticOperator::Multiply | ArithmeticOperator::Divide | ArithmeticOperator::Modulo => 2,
+    }
+}
+
+/// Quotes identifiers that would not survive being parsed back unquoted.
+fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+
+    let is_plain = matches!(chars.next(), Some(ch) if ch.is_lowercase() || ch == '_')
+        && chars.all(|ch| ch.is_lowercase() || ch.is_ascii_digit() || ch == '_')
+        && !name.eq_ignore_ascii_case(TRUE)
+        && !name.eq_ignore_ascii_case(FALSE)
+        && !name.eq_ignore_ascii_case(NULL)
+        && !is_reserved(name);
+
+    if is_plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
+
+fn table_name(keyspace: &Option<Cow<'_, str>>, table: &str) -> String {
+    match keyspace {
+        Some(keyspace) => format!("{}.{}", identifier(keyspace), identifier(table)),
+        None => identifier(table),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::query_parser::parser::parse_query;
+
+    #[test]
+    fn test_to_cql_string() {
+        let params = vec![
+            (
+                "select * from user_sessions where user_id = 12345 and type='LAPTOP' allow filtering",
+                "SELECT * FROM user_sessions WHERE user_id = 12345 AND type = 'LAPTOP' ALLOW FILTERING",
+            ),
+            (
+                "SELECT (price + tax) * quantity, price - (tax - 1), upper(\"Name\") FROM shop.products",
+                "SELECT (price + tax) * quantity, price - (tax - 1), upper(\"Name\") FROM shop.products",
+            ),
+            (
+                "INSERT INTO user_sessions (user_id, type) VALUES (1, 'O''Brien'), (2, 'b') USING TTL 60 AND TIMESTAMP 1000",
+                "INSERT INTO user_sessions (user_id, type) VALUES (1, 'O''Brien'), (2, 'b') USING TTL 60 AND TIMESTAMP 1000",
+            ),
+            (
+                "CREATE TABLE readings (sensor_id UUID PRIMARY KEY, value DOUBLE) WITH compression = {'class': 'lz4'}",
+                "CREATE TABLE readings (sensor_id UUID, value DOUBLE, PRIMARY KEY ((sensor_id))) WITH compression = {'class': 'LZ4Compressor'}",
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query).unwrap().to_cql_string(), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let params = vec![
+            "SELECT JSON * FROM user_sessions WHERE timestamp >= TIMESTAMP '2024-10-21 08:30:00+02:00'",
+            "SELECT price * 1.0 FROM products WHERE id = 3e3be9fb-5888-4b0e-8f22-287b7d90a32f AND flag = true",
+            "EXPLAIN DELETE type FROM analytics.user_sessions USING TIMESTAMP 1000 WHERE user_id = 1",
+            "INSERT INTO user_sessions JSON '{\"user_id\": 1}' IF NOT EXISTS",
+            "INSERT INTO user_sessions (user_id, created_at) VALUES (1, toTimestamp(now()))",
+            "UPDATE user_sessions USING TTL 60 SET type = 'TAB\\tLET', active = false WHERE user_id = 1 IF type = 'PHONE'",
+            "UPDATE user_sessions SET type = 'PHONE' WHERE user_id = 1 IF EXISTS",
+            "DELETE type FROM user_sessions WHERE user_id = 1 IF type = 'PHONE' AND visits = ?",
+            "SELECT * FROM user_sessions WHERE user_id = :user_id AND \"Type\" = :\"Type\"",
+            "UPDATE page_views SET hits = hits - 1 WHERE page = 'home'",
+            "SELECT * FROM user_sessions AS s USING CONSISTENCY ONE WHERE s.user_id = 1",
+            "DELETE FROM user_sessions USING TIMESTAMP 1000 AND CONSISTENCY EACH_QUORUM AND SERIAL CONSISTENCY SERIAL WHERE user_id = 1 IF EXISTS",
+            "SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) > ('x', 'y')",
+            "SELECT * FROM readings WHERE sensor_id = 1 PER PARTITION LIMIT 3 ALLOW FILTERING",
+            "BEGIN TRANSACTION INSERT INTO carts (user_id, item) VALUES (1, 'book'); UPDATE carts SET total = 10 WHERE user_id = 1; COMMIT",
+            "CREATE TABLE page_views (page TEXT PRIMARY KEY, hits COUNTER)",
+            "CREATE KEYSPACE IF NOT EXISTS analytics",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 3}",
+            "CREATE KEYSPACE analytics WITH replication = {'class': 'NetworkTopologyStrategy', 'dc1': 3, 'dc-2': 1} AND durable_writes = false",
+            "CREATE TABLE IF NOT EXISTS analytics.user_sessions (user_id UUID, session_id UUID, \"Timestamp\" TIMESTAMP, PRIMARY KEY ((user_id, session_id), \"Timestamp\")) WITH compression = {'enabled': false}",
+            "CREATE TABLE t (a INT, b INT, PRIMARY KEY ((a, b)))",
+            "CREATE TABLE orders (customer_id UUID, order_id UUID, email TEXT STATIC, PRIMARY KEY ((customer_id), order_id))",
+            "CREATE TABLE products (product_id UUID, price DOUBLE DEFAULT 0.0, currency TEXT DEFAULT 'EUR', PRIMARY KEY ((product_id)))",
+            "CREATE TABLE products (product_id UUID, price DOUBLE DEFAULT 1.0 NOT NULL CHECK (price > 0), name TEXT NOT NULL, PRIMARY KEY ((product_id)))",
+            "CREATE TABLE orders (order_id UUID, customer_id UUID NOT NULL REFERENCES shop.customers, PRIMARY KEY ((order_id))) WITH validate_references = true",
+            "CREATE FUNCTION IF NOT EXISTS analytics.fahrenheit (celsius DOUBLE) RETURNS DOUBLE AS 'celsius * 9 / 5 + 32'",
+            "CREATE FUNCTION \"Label\" (kind TEXT) RETURNS TEXT AS 'upper(kind)'",
+            "CREATE TYPE IF NOT EXISTS analytics.address (street TEXT, \"Zip\" FROZEN<zip>)",
+            "CREATE TABLE users (user_id UUID, address FROZEN<address>, PRIMARY KEY ((user_id)))",
+            "INSERT INTO users (user_id, address) VALUES (1, {street: 'Main St', \"Zip\": {code: 12345}})",
+            "SELECT address.city FROM users WHERE user_id = 1",
+            "CREATE TABLE users (user_id UUID, emails LIST<TEXT>, tags SET<TEXT>, prefs MAP<TEXT, FROZEN<pref>>, PRIMARY KEY ((user_id)))",
+            "UPDATE users SET tags = tags + {'vip'}, emails = ['a@b.c'] + emails, prefs = prefs - {'theme'} WHERE user_id = 1",
+            "INSERT INTO users (user_id, prefs) VALUES (1, {'theme': {color: 'dark'}, 'lang': NULL})",
+            "CREATE MATERIALIZED VIEW IF NOT EXISTS analytics.v AS SELECT * FROM t WHERE b IS NOT NULL AND a IS NOT NULL AND c = 1 PRIMARY KEY ((b, a)) WITH gc_grace_seconds = 0",
+            "CREATE TABLE t (a INT PRIMARY KEY) WITH compaction = {'class': 'TimeWindowCompactionStrategy', 'compaction_window_size': 1} AND default_time_to_live = 60 AND gc_grace_seconds = 0",
+            "CREATE TABLE t (a INT, b INT, \"C\" INT, PRIMARY KEY (a, b, \"C\")) WITH compression = {'class': 'LZ4Compressor'} AND CLUSTERING ORDER BY (b DESC, \"C\" ASC)",
+            "ALTER TABLE products ADD description TEXT, DROP price, RENAME title TO name, ALTER quantity TYPE LONG",
+            "DROP TABLE IF EXISTS products",
+            "USE analytics",
+            "TRACING ON",
+            "COPY readings (sensor_id, value) TO 'export.csv' WITH header = true AND delimiter = ';'",
+            "SHOW TABLES FROM analytics",
+            "SELECT * FROM user_sessions WHERE \"null\" IS NOT NULL AND type IS NULL ALLOW FILTERING",
+            "INSERT INTO user_sessions (user_id, type) VALUES (1, NULL)",
+            "DESCRIBE TABLE analytics.user_sessions",
+            "DESCRIBE KEYSPACE analytics",
+            "COMPACT analytics.user_sessions",
+            "FLUSH user_sessions",
+            "SELECT JSON DISTINCT sensor_id FROM readings",
+            "SELECT temperature AS temp, s.\"Humidity\" AS \"H\" FROM sensors AS s WHERE s.temperature > 20 ALLOW FILTERING",
+        ];
+
+        for query in params {
+            let parsed = parse_query(query).unwrap();
+            assert_eq!(parse_query(&parsed.to_cql_string()), Ok(parsed), "{}", query);
+        }
+    }
+}
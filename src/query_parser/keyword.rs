@@ -1,18 +1,72 @@
+pub(crate) const EXPLAIN: &str = "EXPLAIN";
+pub(crate) const BEGIN_TRANSACTION: &str = "BEGIN TRANSACTION";
+pub(crate) const COMMIT: &str = "COMMIT";
 pub(crate) const SELECT: &str = "SELECT";
 pub(crate) const INSERT_INTO: &str = "INSERT INTO";
 pub(crate) const UPDATE: &str = "UPDATE";
 pub(crate) const FROM: &str = "FROM";
 pub(crate) const WHERE: &str = "WHERE";
 pub(crate) const AND: &str = "AND";
+pub(crate) const ALLOW_FILTERING: &str = "ALLOW FILTERING";
+pub(crate) const PER_PARTITION_LIMIT: &str = "PER PARTITION LIMIT";
+pub(crate) const PER: &str = "PER";
 pub(crate) const VALUES: &str = "VALUES";
 pub(crate) const SET: &str = "SET";
 pub(crate) const DELETE: &str = "DELETE";
+pub(crate) const USING: &str = "USING";
+pub(crate) const TTL: &str = "TTL";
+pub(crate) const CONSISTENCY: &str = "CONSISTENCY";
+pub(crate) const SERIAL_CONSISTENCY: &str = "SERIAL CONSISTENCY";
+pub(crate) const CREATE_KEYSPACE: &str = "CREATE KEYSPACE";
 pub(crate) const CREATE_TABLE: &str ="CREATE TABLE";
+pub(crate) const CREATE_MATERIALIZED_VIEW: &str = "CREATE MATERIALIZED VIEW";
+pub(crate) const AS: &str = "AS";
+pub(crate) const CREATE_FUNCTION: &str = "CREATE FUNCTION";
+pub(crate) const RETURNS: &str = "RETURNS";
+pub(crate) const CREATE_TYPE: &str = "CREATE TYPE";
 pub(crate) const ALTER_TABLE: &str = "ALTER TABLE";
 pub(crate) const DROP_TABLE: &str = "DROP TABLE";
+pub(crate) const IF_NOT_EXISTS: &str = "IF NOT EXISTS";
+pub(crate) const IF_EXISTS: &str = "IF EXISTS";
+pub(crate) const IF: &str = "IF";
+pub(crate) const USE: &str = "USE";
+pub(crate) const COMPACT: &str = "COMPACT";
+pub(crate) const FLUSH: &str = "FLUSH";
+pub(crate) const TRACING: &str = "TRACING";
+pub(crate) const ON: &str = "ON";
+pub(crate) const OFF: &str = "OFF";
+pub(crate) const COPY: &str = "COPY";
+pub(crate) const SHOW_TABLES: &str = "SHOW TABLES";
+pub(crate) const DESCRIBE_TABLE: &str = "DESCRIBE TABLE";
+pub(crate) const DESCRIBE_KEYSPACE: &str = "DESCRIBE KEYSPACE";
+pub(crate) const TO: &str = "TO";
 pub(crate) const ADD: &str = "ADD";
 pub(crate) const DROP: &str = "DROP";
+pub(crate) const RENAME: &str = "RENAME";
+pub(crate) const ALTER: &str = "ALTER";
+pub(crate) const TYPE: &str = "TYPE";
 pub(crate) const PRIMARY_KEY: &str ="PRIMARY KEY";
+pub(crate) const STATIC: &str = "STATIC";
+pub(crate) const DEFAULT: &str = "DEFAULT";
+pub(crate) const NOT_NULL: &str = "NOT NULL";
+pub(crate) const CHECK: &str = "CHECK";
+pub(crate) const REFERENCES: &str = "REFERENCES";
+pub(crate) const WITH: &str = "WITH";
+pub(crate) const COMPRESSION: &str = "COMPRESSION";
+pub(crate) const COMPACTION: &str = "COMPACTION";
+pub(crate) const DEFAULT_TIME_TO_LIVE: &str = "DEFAULT_TIME_TO_LIVE";
+pub(crate) const GC_GRACE_SECONDS: &str = "GC_GRACE_SECONDS";
+pub(crate) const VALIDATE_REFERENCES: &str = "VALIDATE_REFERENCES";
+pub(crate) const REPLICATION: &str = "REPLICATION";
+pub(crate) const DURABLE_WRITES: &str = "DURABLE_WRITES";
+pub(crate) const CLUSTERING_ORDER_BY: &str = "CLUSTERING ORDER BY";
+pub(crate) const ASC: &str = "ASC";
+pub(crate) const DESC: &str = "DESC";
+pub(crate) const JSON: &str = "JSON";
+pub(crate) const DISTINCT: &str = "DISTINCT";
+pub(crate) const TOKEN: &str = "TOKEN";
+pub(crate) const WRITETIME: &str = "WRITETIME";
+pub(crate) const TIME_BUCKET: &str = "TIME_BUCKET";
 pub(crate) const UUID: &str = "UUID";
 pub(crate) const INT: &str = "INT";
 pub(crate) const LONG: &str = "LONG";
@@ -21,8 +75,27 @@ pub(crate) const DOUBLE: &str = "DOUBLE";
 pub(crate) const TEXT: &str = "TEXT";
 pub(crate) const TIMESTAMP: &str = "TIMESTAMP";
 pub(crate) const BOOL: &str = "BOOL";
+pub(crate) const COUNTER: &str = "COUNTER";
+pub(crate) const FROZEN: &str = "FROZEN";
+pub(crate) const LIST: &str = "LIST";
+pub(crate) const MAP: &str = "MAP";
+pub(crate) const ANY: &str = "ANY";
+pub(crate) const ONE: &str = "ONE";
+pub(crate) const TWO: &str = "TWO";
+pub(crate) const THREE: &str = "THREE";
+pub(crate) const QUORUM: &str = "QUORUM";
+pub(crate) const ALL: &str = "ALL";
+pub(crate) const LOCAL_QUORUM: &str = "LOCAL_QUORUM";
+pub(crate) const EACH_QUORUM: &str = "EACH_QUORUM";
+pub(crate) const SERIAL: &str = "SERIAL";
+pub(crate) const LOCAL_SERIAL: &str = "LOCAL_SERIAL";
+pub(crate) const LOCAL_ONE: &str = "LOCAL_ONE";
 pub (crate) const FALSE: &str = "FALSE";
 pub (crate) const TRUE: &str = "TRUE";
+pub (crate) const NULL: &str = "NULL";
+pub (crate) const IS: &str = "IS";
+pub (crate) const IS_NOT: &str = "IS NOT";
+pub (crate) const NOT: &str = "NOT";
 pub (crate) const EQUALS: &str = "=";
 pub (crate) const NOT_EQUALS: &str = "!=";
 pub (crate) const GREATER: &str = ">";
@@ -31,3 +104,18 @@ pub (crate) const LESS: &str = "<";
 pub (crate) const LESS_OR_EQUALS: &str = "<=";
 
 
+
+/// Words that can't be used unquoted as the name of a keyspace, table, column, alias or field,
+/// since the grammar gives them a meaning of their own. A double-quoted name may be any of them.
+/// `TRUE`, `FALSE` and `NULL` are read as literals and `TOKEN` as a function, so they aren't listed.
+pub(crate) const RESERVED_KEYWORDS: &[&str] = &[
+    "ADD", "ALLOW", "ALTER", "AND", "APPLY", "ASC", "BATCH", "BEGIN", "BY", "CREATE", "DELETE", "DESC",
+    "DESCRIBE", "DROP", "FROM", "GRANT", "IF", "IN", "INDEX", "INSERT", "INTO", "KEYSPACE", "LIMIT",
+    "MATERIALIZED", "MODIFY", "NOT", "OF", "ON", "OR", "ORDER", "PRIMARY", "RENAME", "REPLACE", "REVOKE",
+    "SCHEMA", "SELECT", "SET", "TABLE", "TO", "TRUNCATE", "UNLOGGED", "UPDATE", "USE", "USING", "VIEW",
+    "WHERE", "WITH",
+];
+
+pub(crate) fn is_reserved(word: &str) -> bool {
+    RESERVED_KEYWORDS.iter().any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
@@ -0,0 +1,80 @@
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::common_parser::{expect_end, parse_identifier, parse_keyword, parse_table_name};
+use crate::query_parser::keyword::{DESCRIBE_KEYSPACE, DESCRIBE_TABLE, FROM, SHOW_TABLES};
+use crate::query_parser::query::{DescribeKeyspaceQuery, DescribeTableQuery, MetaQuery, Query, QueryParsingError, ShowTablesQuery};
+use nom::combinator::opt;
+use nom::sequence::preceded;
+
+pub(crate) fn parse_show_tables_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(SHOW_TABLES)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'SHOW TABLES' statement", &[SHOW_TABLES]))
+    };
+
+    let (query, keyspace) = match opt(preceded(parse_keyword(FROM), parse_identifier))(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse keyspace name", &[FROM]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(Query::MetaQuery(MetaQuery::ShowTables(ShowTablesQuery { keyspace })))
+}
+
+pub(crate) fn parse_describe_table_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(DESCRIBE_TABLE)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'DESCRIBE TABLE' statement", &[DESCRIBE_TABLE]))
+    };
+
+    let (query, (keyspace, table)) = match parse_table_name(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse table name", &["table name"]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(Query::MetaQuery(MetaQuery::DescribeTable(DescribeTableQuery { keyspace, table })))
+}
+
+pub(crate) fn parse_describe_keyspace_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(DESCRIBE_KEYSPACE)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'DESCRIBE KEYSPACE' statement", &[DESCRIBE_KEYSPACE]))
+    };
+
+    let (query, keyspace) = match parse_identifier(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse keyspace name", &["keyspace name"]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(Query::MetaQuery(MetaQuery::DescribeKeyspace(DescribeKeyspaceQuery { keyspace })))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::query_parser::parser::parse_query;
+    use super::*;
+
+    #[test]
+    fn test_parse_meta_queries() {
+        let params = vec![
+            ("SHOW TABLES", MetaQuery::ShowTables(ShowTablesQuery { keyspace: None })),
+            ("SHOW TABLES FROM analytics", MetaQuery::ShowTables(ShowTablesQuery { keyspace: Some("analytics".into()) })),
+            (
+                "DESCRIBE TABLE analytics.user_sessions",
+                MetaQuery::DescribeTable(DescribeTableQuery {
+                    keyspace: Some("analytics".into()),
+                    table: "user_sessions".into(),
+                }),
+            ),
+            ("DESCRIBE KEYSPACE analytics", MetaQuery::DescribeKeyspace(DescribeKeyspaceQuery { keyspace: "analytics".into() })),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), Ok(Query::MetaQuery(expected_result)));
+        }
+    }
+}
@@ -1,37 +1,738 @@
-use nom::IResult;
-use nom::branch::alt;
-use nom::combinator::map;
-use crate::query_parser::{common_parser, ddl_parser, dml_parser};
+use crate::query_parser::{admin_parser, bulk_parser, common_parser, ddl_parser, dml_parser, meta_parser, session_parser};
+use crate::query_parser::dialect::{Dialect, Lenient};
 use crate::query_parser::keyword::*;
-use crate::query_parser::query::{Query, QueryParsingError, QueryType};
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::query::{DataManipulationQuery, Query, QueryParsingError, QueryType, SyntaxError};
+use unicode_normalization::char::is_combining_mark;
 
-pub(crate) fn parse_query(query: &str) -> Result<Query, QueryParsingError> {
+pub(crate) fn parse_query(query: &str) -> Result<Query<'_>, QueryParsingError> {
+    parse_query_with_dialect(query, &Lenient)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = query.len()), err))]
+pub(crate) fn parse_query_with_dialect<'a>(query: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
     let query_type = get_query_type(query)?;
 
-    match query_type {
-        QueryType::Select => dml_parser::parse_select_query(query),
-        QueryType::Insert => dml_parser::parse_insert(query),
-        QueryType::Update => dml_parser::parse_update(query),
-        QueryType::Delete => dml_parser::parse_delete(query),
-        QueryType::CreateTable => ddl_parser::parse_create_table_query(query),
+    #[cfg(feature = "tracing")]
+    tracing::debug!(?query_type, "routing statement");
+
+    if !dialect.supports(query_type) {
+        return Err(QueryParsingError::UnsupportedRequest(first_token(query).to_string()));
+    }
+
+    if !dialect.allows_unicode_identifiers() {
+        if let Some((offset, _)) = code_chars(query, dialect.allows_string_escapes()).find(|(_, ch)| !ch.is_ascii()) {
+            let message = "unquoted identifiers can only contain ASCII characters".to_string();
+            return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(query, offset, message, vec!["quoted identifier".to_string()])));
+        }
+    }
+
+    let words = code_words(query, dialect.allows_string_escapes());
+
+    common_parser::with_dialect(dialect, words, || match query_type {
+        QueryType::Explain => parse_explain_query(query, dialect),
+        QueryType::Transaction => parse_transaction(query, dialect),
+        QueryType::Select => dml_parser::parse_select_query(query, dialect),
+        QueryType::Insert => dml_parser::parse_insert(query, dialect),
+        QueryType::Update => dml_parser::parse_update(query, dialect),
+        QueryType::Delete => dml_parser::parse_delete(query, dialect),
+        QueryType::CreateKeyspace => ddl_parser::parse_create_keyspace_query(query),
+        QueryType::CreateTable => ddl_parser::parse_create_table_query(query, dialect),
+        QueryType::CreateMaterializedView => ddl_parser::parse_create_materialized_view_query(query),
+        QueryType::CreateFunction => ddl_parser::parse_create_function_query(query),
+        QueryType::CreateType => ddl_parser::parse_create_type_query(query),
         QueryType::AlterTable => ddl_parser::parse_alter_table_query(query),
         QueryType::DropTable => ddl_parser::parse_drop_table_query(query),
+        QueryType::Use => session_parser::parse_use_query(query),
+        QueryType::Tracing => session_parser::parse_tracing_query(query),
+        QueryType::Copy => bulk_parser::parse_copy_query(query),
+        QueryType::ShowTables => meta_parser::parse_show_tables_query(query),
+        QueryType::DescribeTable => meta_parser::parse_describe_table_query(query),
+        QueryType::DescribeKeyspace => meta_parser::parse_describe_keyspace_query(query),
+        QueryType::Compact => admin_parser::parse_compact_query(query),
+        QueryType::Flush => admin_parser::parse_flush_query(query),
+    })
+}
+
+/// Parses a script of statements terminated by semicolons. Semicolons inside string literals,
+/// quoted identifiers, comments and transactions do not end a statement, and empty statements are skipped.
+pub(crate) fn parse_script(source: &str) -> Result<Vec<Query<'_>>, QueryParsingError> {
+    let mut queries = Vec::new();
+
+    for (offset, statement) in script_statements(source, true) {
+        match parse_query(statement) {
+            Ok(query) => queries.push(query),
+            Err(error) => return Err(relocate(error, source, offset)),
+        }
+    }
+
+    Ok(queries)
+}
+
+/// Splits a script into its non-empty statements along with their offsets in the script. Without
+/// string escapes a backslash doesn't escape the quote after it.
+pub(crate) fn script_statements(source: &str, escapes: bool) -> Vec<(usize, &str)> {
+    split_statements(source, escapes)
+        .into_iter()
+        .filter(|(_, statement)| !matches!(common_parser::skip_trivia(statement), Ok((rest, _)) if rest.is_empty()))
+        .collect()
+}
+
+/// Points a syntax error of a statement at its position in the whole script.
+pub(crate) fn relocate(error: QueryParsingError, source: &str, offset: usize) -> QueryParsingError {
+    match error {
+        QueryParsingError::QuerySyntaxError(error) => QueryParsingError::QuerySyntaxError(error.relocate(source, offset)),
+        error => error,
+    }
+}
+
+fn split_statements(source: &str, escapes: bool) -> Vec<(usize, &str)> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+
+    for (index, ch) in code_chars(source, escapes) {
+        if ch == ';' && !is_open_transaction(&source[start..index]) {
+            statements.push((start, &source[start..index]));
+            start = index + 1;
+        }
+    }
+
+    statements.push((start, &source[start..]));
+    statements
+}
+
+/// Walks the characters of a source that are outside string literals, quoted identifiers and
+/// comments, along with their offsets.
+fn code_chars(source: &str, escapes: bool) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut chars = source.char_indices().peekable();
+
+    std::iter::from_fn(move || {
+        while let Some((index, ch)) = chars.next() {
+            match ch {
+                '\'' | '"' => {
+                    while let Some((_, next)) = chars.next() {
+                        if next == '\\' && ch == '\'' && escapes {
+                            chars.next();
+                        } else if next == ch {
+                            break;
+                        }
+                    }
+                }
+                '-' if matches!(chars.peek(), Some((_, '-'))) => {
+                    for (_, next) in chars.by_ref() {
+                        if next == '\n' {
+                            break;
+                        }
+                    }
+                }
+                '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                    chars.next();
+                    while let Some((_, next)) = chars.next() {
+                        if next == '*' && matches!(chars.peek(), Some((_, '/'))) {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => return Some((index, ch)),
+            }
+        }
+
+        None
+    })
+}
+
+/// The unquoted words of a source, such as keywords and unquoted identifiers.
+fn code_words(source: &str, escapes: bool) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut word: Option<(usize, usize)> = None;
+
+    for (index, ch) in code_chars(source, escapes) {
+        let is_word_char = ch.is_alphanumeric() || ch == '_' || is_combining_mark(ch);
+
+        match word {
+            Some((start, end)) if is_word_char && end == index => word = Some((start, index + ch.len_utf8())),
+            _ => {
+                words.extend(word.take().map(|(start, end)| &source[start..end]));
+
+                if is_word_char {
+                    word = Some((index, index + ch.len_utf8()));
+                }
+            }
+        }
+    }
+
+    words.extend(word.map(|(start, end)| &source[start..end]));
+    words
+}
+
+/// A transaction keeps the semicolons between its statements until its `COMMIT`.
+fn is_open_transaction(statement: &str) -> bool {
+    let statement = common_parser::skip_trivia(statement).map_or(statement, |(statement, _)| statement);
+    let statement_end = statement.trim_end();
+
+    let is_committed = statement_end.len() >= COMMIT.len()
+        && statement_end.is_char_boundary(statement_end.len() - COMMIT.len())
+        && statement_end[statement_end.len() - COMMIT.len()..].eq_ignore_ascii_case(COMMIT)
+        && !statement_end[..statement_end.len() - COMMIT.len()].ends_with(|ch: char| ch.is_alphanumeric() || ch == '_');
+
+    matches!(get_query_type(statement), Ok(QueryType::Transaction)) && !is_committed
+}
+
+/// Parses `BEGIN TRANSACTION <write>; ...; COMMIT`. All the writes have to go to one table,
+/// the planner checks that they also go to one partition.
+fn parse_transaction<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let body = match common_parser::parse_keyword(BEGIN_TRANSACTION)(source) {
+        Ok((body, _)) => body,
+        Err(error) => return Err(syntax_error(source, error, "expected the begin transaction keywords", &[BEGIN_TRANSACTION]))
+    };
+
+    let offset = source.len() - body.len();
+    let mut statements = script_statements(body, dialect.allows_string_escapes());
+
+    let is_committed = statements.last().is_some_and(|(_, last)| {
+        matches!(common_parser::parse_keyword(COMMIT)(last), Ok((rest, _)) if common_parser::skip_trivia(rest).is_ok_and(|(rest, _)| rest.is_empty()))
+    });
+
+    if !is_committed {
+        let message = "a transaction must end with COMMIT".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, source.len(), message, vec![COMMIT.to_string()])));
+    }
+
+    statements.pop();
+
+    let mut writes: Vec<DataManipulationQuery> = Vec::with_capacity(statements.len());
+
+    for (statement_offset, statement) in statements {
+        let statement_offset = offset + statement_offset;
+
+        let write = match parse_query_with_dialect(statement, dialect) {
+            Ok(Query::DataManipulationQuery(write @ (DataManipulationQuery::Insert(_) | DataManipulationQuery::Update(_) | DataManipulationQuery::Delete(_)))) => write,
+            Ok(_) => {
+                let expected = [INSERT_INTO, UPDATE, DELETE].iter().map(|token| token.to_string()).collect();
+                let message = "a transaction can only contain INSERT, UPDATE and DELETE statements".to_string();
+                return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, statement_offset, message, expected)));
+            }
+            Err(error) => return Err(relocate(error, source, statement_offset)),
+        };
+
+        if writes.first().is_some_and(|first| first.target() != write.target()) {
+            let message = "all the statements of a transaction must write to the same table".to_string();
+            return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, statement_offset, message, vec!["table name".to_string()])));
+        }
+
+        writes.push(write);
+    }
+
+    if writes.is_empty() {
+        let expected = [INSERT_INTO, UPDATE, DELETE].iter().map(|token| token.to_string()).collect();
+        let message = "a transaction must contain at least one statement".to_string();
+        return Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, offset, message, expected)));
+    }
+
+    Ok(Query::Transaction(writes))
+}
+
+fn parse_explain_query<'a>(source: &'a str, dialect: &dyn Dialect) -> Result<Query<'a>, QueryParsingError> {
+    let statement = match common_parser::parse_keyword(EXPLAIN)(source) {
+        Ok((statement, _)) => statement,
+        Err(error) => return Err(syntax_error(source, error, "expected the explain keyword", &[EXPLAIN]))
+    };
+
+    let offset = source.len() - statement.len();
+
+    match parse_query_with_dialect(statement, dialect) {
+        Ok(Query::DataManipulationQuery(statement)) => Ok(Query::Explain(statement)),
+        Ok(_) => {
+            let expected = [SELECT, INSERT_INTO, UPDATE, DELETE].iter().map(|token| token.to_string()).collect();
+            let message = "only data manipulation statements can be explained".to_string();
+            Err(QueryParsingError::QuerySyntaxError(SyntaxError::new(source, offset, message, expected)))
+        }
+        Err(QueryParsingError::QuerySyntaxError(error)) => Err(QueryParsingError::QuerySyntaxError(error.relocate(source, offset))),
+        Err(error) => Err(error)
     }
 }
 
+/// Statement types keyed by their leading keywords. A keyword only matches as a whole word.
+const ROUTES: [(&str, QueryType); 21] = [
+    (EXPLAIN, QueryType::Explain),
+    (BEGIN_TRANSACTION, QueryType::Transaction),
+    (SELECT, QueryType::Select),
+    (INSERT_INTO, QueryType::Insert),
+    (UPDATE, QueryType::Update),
+    (DELETE, QueryType::Delete),
+    (CREATE_KEYSPACE, QueryType::CreateKeyspace),
+    (CREATE_TABLE, QueryType::CreateTable),
+    (CREATE_MATERIALIZED_VIEW, QueryType::CreateMaterializedView),
+    (CREATE_FUNCTION, QueryType::CreateFunction),
+    (CREATE_TYPE, QueryType::CreateType),
+    (ALTER_TABLE, QueryType::AlterTable),
+    (DROP_TABLE, QueryType::DropTable),
+    (USE, QueryType::Use),
+    (TRACING, QueryType::Tracing),
+    (COPY, QueryType::Copy),
+    (SHOW_TABLES, QueryType::ShowTables),
+    (DESCRIBE_TABLE, QueryType::DescribeTable),
+    (DESCRIBE_KEYSPACE, QueryType::DescribeKeyspace),
+    (COMPACT, QueryType::Compact),
+    (FLUSH, QueryType::Flush),
+];
+
 fn get_query_type(query: &str) -> Result<QueryType, QueryParsingError> {
-    let query_type_result: IResult<&str, QueryType> = alt((
-        map(common_parser::parse_keyword(SELECT), |_| QueryType::Select),
-        map(common_parser::parse_keyword(INSERT_INTO), |_| QueryType::Insert),
-        map(common_parser::parse_keyword(UPDATE), |_| QueryType::Update),
-        map(common_parser::parse_keyword(DELETE), |_| QueryType::Delete),
-        map(common_parser::parse_keyword(CREATE_TABLE), |_| QueryType::CreateTable),
-        map(common_parser::parse_keyword(ALTER_TABLE), |_| QueryType::AlterTable),
-        map(common_parser::parse_keyword(DROP_TABLE), |_| QueryType::DropTable),
-    ))(query);
-
-    match query_type_result {
-        Ok((_, query_type)) => Ok(query_type),
-        Err(_) => Err(QueryParsingError::UnsupportedRequest(query.to_string()))
-    }
-}
\ No newline at end of file
+    let statement = skip_leading_trivia(query);
+
+    let route = ROUTES.iter().find(|(keyword, _)| {
+        statement.get(..keyword.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(keyword))
+            && !statement[keyword.len()..].starts_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+    });
+
+    match route {
+        Some((_, query_type)) => Ok(*query_type),
+        None => Err(QueryParsingError::UnsupportedRequest(first_token(query).to_string()))
+    }
+}
+
+fn skip_leading_trivia(query: &str) -> &str {
+    common_parser::skip_trivia(query).map_or(query, |(statement, _)| statement)
+}
+
+/// The first word of a statement, which names the request when it can't be served.
+fn first_token(query: &str) -> &str {
+    skip_leading_trivia(query).split(|ch: char| ch.is_whitespace() || ch == ';').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::dialect::{parse_with_dialect, Strict};
+    use crate::query_parser::builder::{ConditionBuilder, InsertQueryBuilder, SelectQueryBuilder};
+    use crate::query_parser::query::{BulkQuery, Operator, SessionQuery, UseQuery, Value};
+    use nom::error::{Error as NomError, ErrorKind};
+    use nom::Err as NomErr;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_get_query_type() {
+        let params = vec![
+            ("EXPLAIN SELECT * FROM user_sessions", QueryType::Explain),
+            ("select * from user_sessions", QueryType::Select),
+            ("INSERT INTO user_sessions (user_id) VALUES (1)", QueryType::Insert),
+            ("UPDATE user_sessions SET visits = 1 WHERE user_id = 1", QueryType::Update),
+            ("DELETE FROM user_sessions WHERE user_id = 1", QueryType::Delete),
+            ("CREATE KEYSPACE analytics WITH REPLICATION = {'class': 'SimpleStrategy'}", QueryType::CreateKeyspace),
+            ("CREATE TABLE user_sessions (user_id UUID PRIMARY KEY)", QueryType::CreateTable),
+            ("CREATE MATERIALIZED VIEW by_type AS SELECT * FROM user_sessions", QueryType::CreateMaterializedView),
+            ("CREATE FUNCTION fahrenheit (celsius DOUBLE) RETURNS DOUBLE AS 'celsius * 9 / 5 + 32'", QueryType::CreateFunction),
+            ("CREATE TYPE address (street TEXT, city TEXT)", QueryType::CreateType),
+            ("ALTER TABLE user_sessions DROP type", QueryType::AlterTable),
+            ("DROP TABLE user_sessions", QueryType::DropTable),
+            ("USE analytics", QueryType::Use),
+            ("TRACING ON", QueryType::Tracing),
+            ("COPY user_sessions TO 'sessions.csv'", QueryType::Copy),
+            ("SHOW TABLES", QueryType::ShowTables),
+            ("DESCRIBE TABLE user_sessions", QueryType::DescribeTable),
+            ("DESCRIBE KEYSPACE analytics", QueryType::DescribeKeyspace),
+            ("COMPACT user_sessions", QueryType::Compact),
+            ("-- maintenance\nFLUSH user_sessions", QueryType::Flush),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(get_query_type(query), Ok(expected_result), "{}", query);
+        }
+
+        let params = vec![
+            ("TRUNCATE user_sessions", "TRUNCATE"),
+            ("USERS", "USERS"),
+            ("  SELECTED * FROM user_sessions", "SELECTED"),
+            ("CREATE INDEX ON user_sessions (type)", "CREATE"),
+            ("", ""),
+        ];
+
+        for (query, expected_token) in params {
+            assert_eq!(get_query_type(query), Err(QueryParsingError::UnsupportedRequest(expected_token.to_string())), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_parse_explain() {
+        let query = "EXPLAIN SELECT * FROM user_sessions";
+
+        let statement = match SelectQueryBuilder::new().table("user_sessions".to_string()).build().unwrap() {
+            Query::DataManipulationQuery(statement) => statement,
+            query => panic!("unexpected query {:?}", query),
+        };
+
+        assert_eq!(parse_query(query), Ok(Query::Explain(statement)));
+        assert!(parse_query("EXPLAIN DROP TABLE user_sessions").is_err());
+        assert!(parse_query("EXPLAIN EXPLAIN SELECT * FROM user_sessions").is_err());
+    }
+
+    #[test]
+    fn test_parse_query_with_comments() {
+        let query = r#"
+        -- sessions of a single user
+        SELECT * /* every column */ FROM user_sessions
+        WHERE user_id = 12345 -- the user
+        "#;
+
+        let expected_result = SelectQueryBuilder::new()
+            .table("user_sessions".to_string())
+            .condition(ConditionBuilder::new()
+                .column("user_id".to_string())
+                .operator(Operator::Equals)
+                .value(Value::Integer(12345))
+                .build().unwrap())
+            .build().unwrap();
+
+        assert_eq!(parse_query(query), Ok(expected_result));
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let script = r#"
+        USE analytics;
+        -- a comment; not a statement
+        INSERT INTO user_sessions (user_id, type) VALUES (1, 'a;b''c');
+        /* ; */ ;
+        SELECT * FROM user_sessions WHERE user_id = 1
+        "#;
+
+        let expected_result = vec![
+            Query::SessionQuery(SessionQuery::Use(UseQuery { keyspace: "analytics".into() })),
+            InsertQueryBuilder::new()
+                .column("user_id".to_string())
+                .column("type".to_string())
+                .table("user_sessions".to_string())
+                .value(Value::Integer(1))
+                .value(Value::String("a;b'c".into()))
+                .build().unwrap(),
+            SelectQueryBuilder::new()
+                .table("user_sessions".to_string())
+                .condition(ConditionBuilder::new()
+                    .column("user_id".to_string())
+                    .operator(Operator::Equals)
+                    .value(Value::Integer(1))
+                    .build().unwrap())
+                .build().unwrap(),
+        ];
+
+        assert_eq!(parse_script(script), Ok(expected_result));
+        assert_eq!(parse_script(" ; -- nothing here"), Ok(Vec::new()));
+
+        let statements = parse_script(r"COPY user_sessions TO 'C:\data\sessions.csv'; SELECT * FROM user_sessions").unwrap();
+        let Query::BulkQuery(BulkQuery::CopyTo(copy)) = &statements[0] else { panic!("expected a copy query, got {:?}", statements[0]) };
+        assert_eq!((copy.file.as_ref(), statements.len()), (r"C:\data\sessions.csv", 2));
+
+        let error = match parse_script("USE analytics;\nSELECT * FORM user_sessions;") {
+            Err(QueryParsingError::QuerySyntaxError(error)) => error,
+            result => panic!("expected a syntax error, got {:?}", result),
+        };
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 10);
+    }
+
+    #[test]
+    fn test_parse_transaction() {
+        let script = r#"
+        BEGIN TRANSACTION
+            INSERT INTO carts (user_id, item) VALUES (1, 'book');
+            DELETE FROM carts WHERE user_id = 1 AND item = 'pen';
+        COMMIT;
+        SELECT * FROM carts
+        "#;
+
+        let queries = parse_script(script).unwrap();
+        assert_eq!(queries.len(), 2);
+
+        match &queries[0] {
+            Query::Transaction(statements) => {
+                assert_eq!(statements.len(), 2);
+                assert!(matches!(statements[0], DataManipulationQuery::Insert(_)));
+                assert!(matches!(statements[1], DataManipulationQuery::Delete(_)));
+            }
+            query => panic!("expected a transaction, got {:?}", query),
+        }
+
+        let params = vec![
+            "BEGIN TRANSACTION INSERT INTO carts (user_id) VALUES (1);",
+            "BEGIN TRANSACTION COMMIT",
+            "BEGIN TRANSACTION SELECT * FROM carts; COMMIT",
+            "BEGIN TRANSACTION INSERT INTO carts (user_id) VALUES (1); INSERT INTO orders (user_id) VALUES (1); COMMIT",
+            "BEGIN TRANSACTION INSERT INTO carts (user_id) VALUES (1); BEGIN TRANSACTION COMMIT; COMMIT",
+        ];
+
+        for query in params {
+            assert!(parse_query(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_dialect() {
+        #[derive(Debug)]
+        struct RowDeletes;
+
+        impl Dialect for RowDeletes {
+            fn allows_delete_columns(&self) -> bool {
+                false
+            }
+        }
+
+        let params = vec![
+            ("SELECT * FROM user_sessions WHERE device_type = 't\u{e9}l\u{e9}phone'", true),
+            ("SELECT \"r\u{e9}gion\" FROM user_sessions -- r\u{e9}gion", true),
+            ("DELETE device_type FROM user_sessions WHERE user_id = 1", true),
+            ("EXPLAIN SELECT * FROM user_sessions", false),
+            ("BEGIN TRANSACTION INSERT INTO user_sessions (user_id) VALUES (1); COMMIT", false),
+            ("SHOW TABLES", false),
+            ("COMPACT user_sessions", false),
+            ("DESCRIBE TABLE user_sessions", false),
+            ("DESCRIBE KEYSPACE analytics", false),
+            ("INSERT INTO user_sessions (user_id) VALUES (1)", true),
+            ("INSERT INTO user_sessions (user_id) VALUES (1), (2)", false),
+            ("INSERT INTO user_sessions (user_id) VALUES (1) USING TTL 60", true),
+            ("INSERT INTO user_sessions (user_id) VALUES (1) USING CONSISTENCY QUORUM", false),
+            ("UPDATE user_sessions USING TTL 60 AND SERIAL CONSISTENCY SERIAL SET visits = 1 WHERE user_id = 1", false),
+            ("SELECT * FROM user_sessions USING CONSISTENCY ONE WHERE user_id = 1", false),
+            ("SELECT * FROM user_sessions WHERE device_type IS NULL ALLOW FILTERING", false),
+            ("DELETE FROM user_sessions WHERE user_id = 1 IF device_type IS NOT NULL", false),
+            ("CREATE MATERIALIZED VIEW by_type AS SELECT * FROM user_sessions WHERE type IS NOT NULL AND user_id IS NOT NULL PRIMARY KEY (type, user_id)", true),
+            ("CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE)", true),
+            ("CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE DEFAULT 0.0)", false),
+            ("CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE NOT NULL)", false),
+            ("CREATE TABLE products (product_id UUID PRIMARY KEY, price DOUBLE CHECK (price > 0.0))", false),
+            ("CREATE TABLE products (product_id UUID PRIMARY KEY, shop_id UUID REFERENCES shops)", false),
+        ];
+
+        for (query, supported) in params {
+            assert!(parse_query_with_dialect(query, &Lenient).is_ok(), "{}", query);
+            assert_eq!(parse_with_dialect(query, &Strict).is_ok(), supported, "{}", query);
+        }
+
+        let error = parse_query_with_dialect("  EXPLAIN SELECT * FROM user_sessions", &Strict).unwrap_err();
+        assert_eq!(error, QueryParsingError::UnsupportedRequest("EXPLAIN".to_string()));
+
+        let error = parse_with_dialect("INSERT INTO user_sessions (user_id) VALUES (1), (2)", &Strict).unwrap_err();
+        let QueryParsingError::QuerySyntaxError(error) = error else { panic!("{:?}", error) };
+        assert_eq!((error.column, error.message.as_str()), (49, "the dialect doesn't allow inserting several rows at once"));
+
+        let error = parse_with_dialect("SELECT * FROM user_sessions WHERE device_type IS NULL ALLOW FILTERING", &Strict).unwrap_err();
+        let QueryParsingError::QuerySyntaxError(error) = error else { panic!("{:?}", error) };
+        assert_eq!(error.column, 47);
+
+        let insert = |value: &'static str| InsertQueryBuilder::new().column("path".to_string()).table("exports".to_string()).value(Value::String(value.into())).build().unwrap();
+        assert_eq!(parse_with_dialect(r"INSERT INTO exports (path) VALUES ('C:\new\')", &Strict), Ok(insert(r"C:\new\")));
+        assert_eq!(parse_with_dialect(r"INSERT INTO exports (path) VALUES ('C:\new\\')", &Lenient), Ok(insert("C:\new\\")));
+        assert_eq!(script_statements(r"INSERT INTO exports (path) VALUES ('C:\'); SELECT * FROM exports", false).len(), 2);
+
+        let error = parse_query_with_dialect("SELECT r\u{e9}gion FROM user_sessions", &Strict).unwrap_err();
+        let QueryParsingError::QuerySyntaxError(error) = error else { panic!("{:?}", error) };
+        assert_eq!((error.line, error.column), (1, 9));
+
+        let error = parse_query_with_dialect("DELETE device_type FROM user_sessions WHERE user_id = 1", &RowDeletes).unwrap_err();
+        let QueryParsingError::QuerySyntaxError(error) = error else { panic!("{:?}", error) };
+        assert_eq!((error.line, error.column, error.expected.clone()), (1, 8, vec![FROM.to_string()]));
+        assert!(parse_query_with_dialect("DELETE FROM user_sessions WHERE user_id = 1", &RowDeletes).is_ok());
+    }
+
+    #[test]
+    fn test_parse_never_panics() {
+        let statements = [
+            "SELECT user_id, visits * 2 + 1 AS score FROM analytics.user_sessions AS s WHERE s.user_id = 1 AND started_at >= '2024-01-01T00:00:00Z' ALLOW FILTERING",
+            "SELECT JSON * FROM user_sessions WHERE type IS NOT NULL",
+            "INSERT INTO user_sessions (user_id, type) VALUES (1, 'O''Brien'), (2, 'caf\\u00e9') IF NOT EXISTS USING TTL 60",
+            "UPDATE user_sessions USING TIMESTAMP 1 SET visits = visits + ? WHERE user_id = ? IF visits = 1",
+            "DELETE type FROM user_sessions WHERE user_id = 1",
+            "CREATE KEYSPACE IF NOT EXISTS analytics WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 3}",
+            "CREATE TABLE user_sessions (user_id UUID, started_at TIMESTAMP, visits COUNTER, PRIMARY KEY ((user_id), started_at)) WITH CLUSTERING ORDER BY (started_at DESC) AND compression = {'class': 'LZ4Compressor'}",
+            "CREATE MATERIALIZED VIEW by_type AS SELECT * FROM user_sessions WHERE type IS NOT NULL AND user_id IS NOT NULL PRIMARY KEY (type, user_id)",
+            "CREATE FUNCTION IF NOT EXISTS analytics.fahrenheit (celsius DOUBLE) RETURNS DOUBLE AS 'celsius * 9 / 5 + 32'",
+            "CREATE TYPE IF NOT EXISTS analytics.address (street TEXT, city TEXT)",
+            "INSERT INTO users (user_id, address) VALUES (1, {street: 'Main St', city: 'Springfield'})",
+            "ALTER TABLE user_sessions RENAME type TO kind",
+            "DROP TABLE IF EXISTS \"UserSessions\"",
+            "COPY user_sessions (user_id, type) FROM 'sessions.csv' WITH HEADER = true",
+            "EXPLAIN SELECT * FROM user_sessions /* comment */ -- trailing",
+            "USE analytics; TRACING ON; DESCRIBE TABLE user_sessions; SHOW TABLES; COMPACT user_sessions",
+        ];
+        let alphabet = ['\'', '"', '(', ')', ';', ',', '.', '?', '*', '-', '/', '\\', ' ', '\n', '0', '\u{e9}', '{', '}', ':'];
+        let mut rng = StdRng::seed_from_u64(3839);
+
+        for statement in statements {
+            let chars: Vec<char> = statement.chars().collect();
+
+            for end in 0..=chars.len() {
+                let prefix: String = chars[..end].iter().collect();
+                let _ = parse_query(&prefix);
+                let _ = parse_script(&prefix);
+            }
+
+            for _ in 0..1_000 {
+                let mut mutated = chars.clone();
+
+                for _ in 0..rng.gen_range(1..4) {
+                    let index = rng.gen_range(0..mutated.len());
+                    match rng.gen_range(0..3) {
+                        0 => mutated[index] = alphabet[rng.gen_range(0..alphabet.len())],
+                        1 => { mutated.remove(index); }
+                        _ => mutated.insert(index, alphabet[rng.gen_range(0..alphabet.len())]),
+                    }
+                }
+
+                let mutated: String = mutated.into_iter().collect();
+
+                if let Ok(query) = parse_query(&mutated) {
+                    let _ = parse_query(&query.to_cql_string());
+                }
+                let _ = parse_script(&mutated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let query = "SELECT user_id\nFROM user_sessions\nWHERE user_id = ";
+
+        let error = match parse_query(query) {
+            Err(QueryParsingError::QuerySyntaxError(error)) => error,
+            result => panic!("expected a syntax error, got {:?}", result),
+        };
+
+        assert_eq!(error.offset, 50);
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 17);
+        assert_eq!(error.expected, vec!["condition".to_string()]);
+        assert_eq!(error.snippet, "WHERE user_id = \n                ^");
+
+        let query = "EXPLAIN SELECT * FORM user_sessions";
+
+        let error = match parse_query(query) {
+            Err(QueryParsingError::QuerySyntaxError(error)) => error,
+            result => panic!("expected a syntax error, got {:?}", result),
+        };
+
+        assert_eq!(error.offset, 17);
+        assert_eq!(error.line, 1);
+        assert_eq!(error.column, 18);
+        assert_eq!(error.expected, vec![FROM.to_string()]);
+        assert_eq!(error.snippet, "EXPLAIN SELECT * FORM user_sessions\n                 ^");
+    }
+
+    #[test]
+    fn test_reject_trailing_input() {
+        let params = vec![
+            ("CREATE KEYSPACE analytics extra", "extra"),
+            ("CREATE TABLE users (user_id INT PRIMARY KEY) extra", "extra"),
+            ("DROP TABLE users CASCADE", "CASCADE"),
+            ("USE analytics extra", "extra"),
+            ("TRACING ON now", "now"),
+            ("DESCRIBE TABLE users extra", "extra"),
+            ("FLUSH users extra", "extra"),
+            ("COPY users TO 'users.csv' extra", "extra"),
+            ("EXPLAIN DELETE FROM users WHERE user_id = 1 extra", "extra"),
+        ];
+
+        for (query, rest) in params {
+            match parse_query(query) {
+                Err(QueryParsingError::QuerySyntaxError(error)) => {
+                    assert_eq!(error.offset, query.find(rest).unwrap(), "{}", query);
+                    assert_eq!(error.message, "unexpected input after the end of the statement", "{}", query);
+                }
+                result => panic!("expected a syntax error for {}, got {:?}", query, result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_timestamp_literal() {
+        let params = vec![
+            ("SELECT * FROM user_sessions WHERE timestamp > TIMESTAMP '2024-02-30'", 56),
+            ("SELECT * FROM user_sessions WHERE timestamp > TIMESTAMP '2024-10-21 08:30:00+25:00'", 56),
+            ("INSERT INTO user_sessions (user_id, timestamp) VALUES (1, TIMESTAMP 'yesterday')", 68),
+        ];
+
+        for (query, expected_offset) in params {
+            let error = match parse_query(query) {
+                Err(QueryParsingError::QuerySyntaxError(error)) => error,
+                result => panic!("expected a syntax error for {}, got {:?}", query, result),
+            };
+
+            assert_eq!(error.offset, expected_offset, "{}", query);
+            assert_eq!(error.message, "invalid timestamp literal", "{}", query);
+        }
+
+        assert!(parse_query("SELECT * FROM user_sessions WHERE timestamp > 0").is_ok());
+
+        let failure = NomErr::Failure(NomError::new("'x'", ErrorKind::MapRes));
+        let QueryParsingError::QuerySyntaxError(error) = syntax_error("'x'", failure, "cannot parse the value", &[]) else { panic!("expected a syntax error") };
+        assert_eq!(error.message, "cannot parse the value");
+    }
+
+    #[test]
+    fn test_reserved_keywords() {
+        let params = vec![
+            ("SELECT * FROM select", 14, "select"),
+            ("SELECT from FROM user_sessions", 7, "from"),
+            ("SELECT * FROM user_sessions WHERE where = 1", 34, "where"),
+            ("INSERT INTO user_sessions (user_id, order) VALUES (1, 2)", 34, "order"),
+            ("CREATE TABLE t (key INT PRIMARY KEY, table TEXT)", 37, "table"),
+            ("USE keyspace", 4, "keyspace"),
+        ];
+
+        for (query, expected_offset, word) in params {
+            match parse_query(query) {
+                Err(QueryParsingError::QuerySyntaxError(error)) => {
+                    assert_eq!(error.offset, expected_offset, "{}", query);
+                    assert!(error.message.ends_with(&format!("{} is a reserved keyword, double-quote it to use it as a name", word)), "{}", error.message);
+                }
+                result => panic!("expected a syntax error for {}, got {:?}", query, result),
+            }
+        }
+
+        let query = parse_query("SELECT \"from\", type FROM \"select\" WHERE \"where\" = 1 ALLOW FILTERING").unwrap();
+        let expected_result = SelectQueryBuilder::new()
+            .column("from")
+            .column("type")
+            .table("select")
+            .condition(ConditionBuilder::new().column("where").operator(Operator::Equals).value(Value::Integer(1)).build().unwrap())
+            .allow_filtering()
+            .build()
+            .unwrap();
+
+        assert_eq!(query, expected_result);
+        assert_eq!(query.to_cql_string(), "SELECT \"from\", type FROM \"select\" WHERE \"where\" = 1 ALLOW FILTERING");
+
+        #[derive(Debug)]
+        struct ReservesType;
+
+        impl Dialect for ReservesType {
+            fn is_reserved(&self, word: &str) -> bool {
+                word.eq_ignore_ascii_case("type") || is_reserved(word)
+            }
+        }
+
+        let Err(QueryParsingError::QuerySyntaxError(error)) = parse_with_dialect("SELECT Type FROM user_sessions", &ReservesType) else { panic!("expected a syntax error") };
+        assert_eq!(error.offset, 7);
+        assert!(error.message.ends_with("Type is a reserved keyword, double-quote it to use it as a name"), "{}", error.message);
+
+        assert!(parse_with_dialect("SELECT \"type\" FROM user_sessions WHERE user_id = 1", &ReservesType).is_ok());
+        assert!(parse_with_dialect("SELECT * FROM user_sessions WHERE user_id = 1", &ReservesType).is_ok());
+        assert!(parse_with_dialect("SELECT * FROM select", &ReservesType).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        let query = parse_query("SELECT user_id FROM analytics.user_sessions WHERE started_at > TIMESTAMP '2024-10-21'").unwrap();
+        let json = serde_json::to_string(&query).unwrap();
+
+        assert_eq!(json, r#"{"DataManipulationQuery":{"Select":{"columns":[{"expression":{"Column":"user_id"},"alias":null}],"keyspace":"analytics","table":"user_sessions","table_alias":null,"conditions":[{"left":{"Column":"started_at"},"operator":"Greater","right":{"Literal":{"Timestamp":1729468800000}}}],"per_partition_limit":null,"allow_filtering":false,"json":false,"distinct":false,"consistency":null,"serial_consistency":null}}}"#);
+        assert_eq!(serde_json::from_str::<Query>(&json).unwrap(), query);
+
+        let query = parse_query("CREATE TABLE sessions (id uuid PRIMARY KEY, score double)").unwrap();
+        let json = serde_json::to_string(&query).unwrap();
+
+        assert_eq!(serde_json::from_str::<Query>(&json).unwrap(), query);
+    }
+}
@@ -1,139 +1,514 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
+use crate::query_parser::keyword::TOKEN;
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum Query {
-    DataManipulationQuery(DataManipulationQuery),
-    DataDefinitionQuery(DataDefinitionQuery),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::enum_variant_names)]
+pub enum Query<'a> {
+    DataManipulationQuery(DataManipulationQuery<'a>),
+    DataDefinitionQuery(DataDefinitionQuery<'a>),
+    SessionQuery(SessionQuery<'a>),
+    BulkQuery(BulkQuery<'a>),
+    MetaQuery(MetaQuery<'a>),
+    AdminQuery(AdminQuery<'a>),
+    Explain(DataManipulationQuery<'a>),
+    /// Writes to one partition that are applied together or not at all.
+    Transaction(Vec<DataManipulationQuery<'a>>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum DataManipulationQuery {
-    Select(SelectQuery),
-    Insert(InsertQuery),
-    Update(UpdateQuery),
-    Delete(DeleteQuery),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataManipulationQuery<'a> {
+    Select(SelectQuery<'a>),
+    Insert(InsertQuery<'a>),
+    Update(UpdateQuery<'a>),
+    Delete(DeleteQuery<'a>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum DataDefinitionQuery {
-    CreateTable(CreateTableQuery),
-    AlterTable(AlterTableQuery),
-    DropTable(DropTableQuery),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataDefinitionQuery<'a> {
+    CreateKeyspace(CreateKeyspaceQuery<'a>),
+    CreateTable(CreateTableQuery<'a>),
+    CreateMaterializedView(CreateMaterializedViewQuery<'a>),
+    CreateFunction(CreateFunctionQuery<'a>),
+    CreateType(CreateTypeQuery<'a>),
+    AlterTable(AlterTableQuery<'a>),
+    DropTable(DropTableQuery<'a>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum Operator {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SessionQuery<'a> {
+    Use(UseQuery<'a>),
+    Tracing(TracingQuery),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BulkQuery<'a> {
+    CopyFrom(CopyQuery<'a>),
+    CopyTo(CopyQuery<'a>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetaQuery<'a> {
+    ShowTables(ShowTablesQuery<'a>),
+    DescribeTable(DescribeTableQuery<'a>),
+    DescribeKeyspace(DescribeKeyspaceQuery<'a>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdminQuery<'a> {
+    Compact(TableMaintenanceQuery<'a>),
+    Flush(TableMaintenanceQuery<'a>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operator {
     Equals,
     NotEquals,
     Greater,
     GreaterOrEquals,
     Less,
     LessOrEquals,
+    Is,
+    IsNot,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum QueryType {
+    Explain,
+    Transaction,
     Select,
     Insert,
     Update,
     Delete,
+    CreateKeyspace,
     CreateTable,
+    CreateMaterializedView,
+    CreateFunction,
+    CreateType,
     AlterTable,
-    DropTable
+    DropTable,
+    Use,
+    Tracing,
+    Copy,
+    ShowTables,
+    DescribeTable,
+    DescribeKeyspace,
+    Compact,
+    Flush,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct SelectQuery {
-    pub(crate) columns: Vec<String>,
-    pub(crate) table: String,
-    pub(crate) conditions: Vec<Condition>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectQuery<'a> {
+    pub columns: Vec<Selector<'a>>,
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub table_alias: Option<Cow<'a, str>>,
+    pub conditions: Vec<Condition<'a>>,
+    /// The most rows returned from each partition, from `PER PARTITION LIMIT n`.
+    pub per_partition_limit: Option<u32>,
+    pub allow_filtering: bool,
+    pub json: bool,
+    pub distinct: bool,
+    pub consistency: Option<Consistency>,
+    pub serial_consistency: Option<Consistency>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct InsertQuery {
-    pub(crate) columns: Vec<String>,
-    pub(crate) values: Vec<Value>,
-    pub(crate) table: String,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsertQuery<'a> {
+    pub columns: Vec<Cow<'a, str>>,
+    pub values: Vec<Vec<Expression<'a>>>,
+    pub json: Option<Cow<'a, str>>,
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub ttl: Option<u32>,
+    pub timestamp: Option<i64>,
+    pub if_not_exists: bool,
+    pub consistency: Option<Consistency>,
+    pub serial_consistency: Option<Consistency>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct UpdateQuery {
-    pub(crate) table: String,
-    pub(crate) values: Vec<(String, Value)>,
-    pub(crate) conditions: Vec<Condition>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub values: Vec<(Cow<'a, str>, Expression<'a>)>,
+    pub conditions: Vec<Condition<'a>>,
+    pub ttl: Option<u32>,
+    pub timestamp: Option<i64>,
+    pub if_clause: Option<IfClause<'a>>,
+    pub consistency: Option<Consistency>,
+    pub serial_consistency: Option<Consistency>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct DeleteQuery {
-    pub(crate) columns: Vec<String>,
-    pub(crate) table: String,
-    pub(crate) conditions: Vec<Condition>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeleteQuery<'a> {
+    pub columns: Vec<Cow<'a, str>>,
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub conditions: Vec<Condition<'a>>,
+    pub timestamp: Option<i64>,
+    pub if_clause: Option<IfClause<'a>>,
+    pub consistency: Option<Consistency>,
+    pub serial_consistency: Option<Consistency>,
+}
+
+/// How many replicas have to answer a statement. The serial consistency applies to the
+/// consensus round of a conditional statement and is either `SERIAL` or `LOCAL_SERIAL`.
+/// A single node accepts every level and ignores it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Consistency {
+    Any,
+    One,
+    Two,
+    Three,
+    Quorum,
+    All,
+    LocalQuorum,
+    EachQuorum,
+    Serial,
+    LocalSerial,
+    LocalOne,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct CreateTableQuery {
-    pub(crate) table: String,
-    pub(crate) primary_key: PrimaryKey,
-    pub(crate) columns: Vec<Column>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateKeyspaceQuery<'a> {
+    pub keyspace: Cow<'a, str>,
+    pub if_not_exists: bool,
+    pub replication: Option<ReplicationOptions<'a>>,
+    pub durable_writes: Option<bool>,
 }
 
+/// How the data of a keyspace is replicated. `SimpleStrategy` keeps the same number of replicas
+/// everywhere and `NetworkTopologyStrategy` sets a number per datacenter.
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct PrimaryKey {
-    pub(crate) partition_key: Vec<String>,
-    pub(crate) clustering_key: Vec<String>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplicationOptions<'a> {
+    Simple { replication_factor: u32 },
+    NetworkTopology { datacenters: Vec<(Cow<'a, str>, u32)> },
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct Column {
-    pub(crate) name: String,
-    pub(crate) column_type: ColumnType,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseQuery<'a> {
+    pub keyspace: Cow<'a, str>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct AlterTableQuery {
-    pub(crate) table: String,
-    pub(crate) conditions: Vec<AlterTableCondition>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TracingQuery {
+    pub enabled: bool,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct DropTableQuery {
-    pub(crate) table: String,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShowTablesQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct Condition {
-    pub(crate) column: String,
-    pub(crate) operator: Operator,
-    pub(crate) value: Value,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescribeTableQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum AlterTableCondition {
-    AddColumn(AddColumnCondition),
-    DropColumn(DropColumnCondition),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescribeKeyspaceQuery<'a> {
+    pub keyspace: Cow<'a, str>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct AddColumnCondition {
-    pub(crate) column_name: String,
-    pub(crate) column_type: ColumnType,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableMaintenanceQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) struct DropColumnCondition {
-    pub(crate) column_name: String,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CopyQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub columns: Vec<Cow<'a, str>>,
+    pub file: Cow<'a, str>,
+    pub options: Vec<(Cow<'a, str>, Value<'a>)>,
 }
 
-#[derive(Debug)]
-pub(crate) enum Value {
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateTableQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub primary_key: PrimaryKey<'a>,
+    pub columns: Vec<Column<'a>>,
+    /// Columns stored once per partition and shared by all of its clustering rows.
+    pub static_columns: Vec<Cow<'a, str>>,
+    /// Values written to the columns an INSERT leaves out.
+    pub defaults: Vec<(Cow<'a, str>, Value<'a>)>,
+    pub constraints: Vec<ColumnConstraint<'a>>,
+    pub if_not_exists: bool,
+    pub options: TableOptions<'a>,
+}
+
+/// A rule that every row written to the table has to satisfy.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnConstraint<'a> {
+    NotNull(Cow<'a, str>),
+    /// A condition on the column such as `price > 0`.
+    Check(Cow<'a, str>, Condition<'a>),
+    /// The column holds the partition key of a row in another table.
+    References {
+        column: Cow<'a, str>,
+        keyspace: Option<Cow<'a, str>>,
+        table: Cow<'a, str>,
+    },
+}
+
+impl ColumnConstraint<'_> {
+    pub fn column(&self) -> &str {
+        match self {
+            ColumnConstraint::NotNull(column) | ColumnConstraint::Check(column, _) | ColumnConstraint::References { column, .. } => column,
+        }
+    }
+}
+
+/// A table maintained from the writes to its base table, keyed by `primary_key` instead of the base table key.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateMaterializedViewQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub view: Cow<'a, str>,
+    pub if_not_exists: bool,
+    pub columns: Vec<Cow<'a, str>>,
+    pub base_keyspace: Option<Cow<'a, str>>,
+    pub base_table: Cow<'a, str>,
+    pub conditions: Vec<Condition<'a>>,
+    pub primary_key: PrimaryKey<'a>,
+    pub options: TableOptions<'a>,
+}
+
+/// A function whose body is an expression over its arguments, such as `celsius * 9 / 5 + 32`.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateFunctionQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub function: Cow<'a, str>,
+    pub if_not_exists: bool,
+    pub arguments: Vec<Column<'a>>,
+    pub return_type: ColumnType<'a>,
+    pub body: Cow<'a, str>,
+}
+
+/// A user-defined type, a named set of typed fields that columns use as `FROZEN<name>`.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateTypeQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub if_not_exists: bool,
+    pub fields: Vec<Column<'a>>,
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableOptions<'a> {
+    pub compression: Option<CompressionOptions>,
+    pub compaction: Option<CompactionOptions<'a>>,
+    pub default_time_to_live: Option<u32>,
+    pub gc_grace_seconds: Option<u32>,
+    /// Whether writes check that the partitions named by `REFERENCES` columns exist.
+    pub validate_references: Option<bool>,
+    pub clustering_order: Vec<(Cow<'a, str>, ClusteringOrder)>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompactionOptions<'a> {
+    pub strategy: CompactionStrategy,
+    pub options: Vec<(Cow<'a, str>, Value<'a>)>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompactionStrategy {
+    SizeTiered,
+    Leveled,
+    TimeWindow,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClusteringOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressionOptions {
+    pub codec: CompressionCodec,
+    pub chunk_length_in_kb: Option<u32>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompressionCodec {
+    None,
+    Lz4,
+    Snappy,
+    Zstd,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrimaryKey<'a> {
+    pub partition_key: Vec<Cow<'a, str>>,
+    pub clustering_key: Vec<Cow<'a, str>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Column<'a> {
+    pub name: Cow<'a, str>,
+    pub column_type: ColumnType<'a>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlterTableQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub conditions: Vec<AlterTableCondition<'a>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DropTableQuery<'a> {
+    pub keyspace: Option<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub if_exists: bool,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Condition<'a> {
+    pub left: Expression<'a>,
+    pub operator: Operator,
+    pub right: Expression<'a>,
+}
+
+/// A projected expression together with the name it is returned under.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Selector<'a> {
+    pub expression: Expression<'a>,
+    pub alias: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expression<'a> {
+    Column(Cow<'a, str>),
+    /// A column qualified with the table name or alias, such as `s.temperature`.
+    QualifiedColumn(Cow<'a, str>, Cow<'a, str>),
+    Literal(Value<'a>),
+    Binary(Box<Expression<'a>>, ArithmeticOperator, Box<Expression<'a>>),
+    Function(Cow<'a, str>, Vec<Expression<'a>>),
+    /// A parenthesized list of two or more expressions, such as `(blog_id, post_id)`.
+    Tuple(Vec<Expression<'a>>),
+    Placeholder,
+    /// A bind marker with a name, such as `:user_id`. It is bound by position like `?`.
+    NamedPlaceholder(Cow<'a, str>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IfClause<'a> {
+    Exists,
+    Conditions(Vec<Condition<'a>>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlterTableCondition<'a> {
+    AddColumn(AddColumnCondition<'a>),
+    DropColumn(DropColumnCondition<'a>),
+    RenameColumn(RenameColumnCondition<'a>),
+    AlterColumnType(AlterColumnTypeCondition<'a>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddColumnCondition<'a> {
+    pub column_name: Cow<'a, str>,
+    pub column_type: ColumnType<'a>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DropColumnCondition<'a> {
+    pub column_name: Cow<'a, str>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenameColumnCondition<'a> {
+    pub old_name: Cow<'a, str>,
+    pub new_name: Cow<'a, str>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlterColumnTypeCondition<'a> {
+    pub column_name: Cow<'a, str>,
+    pub column_type: ColumnType<'a>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value<'a> {
     Integer(i64),
     Float(f64),
-    String(String),
+    String(Cow<'a, str>),
     Bool(bool),
+    Uuid(u128),
+    Timestamp(i64),
+    Null,
+    UserType(Vec<(Cow<'a, str>, Value<'a>)>),
+    List(Vec<Value<'a>>),
+    Set(Vec<Value<'a>>),
+    Map(Vec<(Value<'a>, Value<'a>)>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum ColumnType {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnType<'a> {
     Uuid,
     Int,
     Long,
@@ -142,54 +517,242 @@ pub(crate) enum ColumnType {
     Timestamp,
     Text,
     Bool,
+    Counter,
+    Frozen(Cow<'a, str>),
+    List(Box<ColumnType<'a>>),
+    Set(Box<ColumnType<'a>>),
+    Map(Box<ColumnType<'a>>, Box<ColumnType<'a>>),
 }
 
-impl SelectQuery {
-    pub(crate) fn new(columns: Vec<String>, table: String, conditions: Vec<Condition>) -> Self {
-        Self { columns, table, conditions }
+impl DataManipulationQuery<'_> {
+    /// The keyspace and table the statement reads or writes.
+    pub(crate) fn target(&self) -> (Option<&str>, &str) {
+        let (keyspace, table) = match self {
+            DataManipulationQuery::Select(query) => (&query.keyspace, &query.table),
+            DataManipulationQuery::Insert(query) => (&query.keyspace, &query.table),
+            DataManipulationQuery::Update(query) => (&query.keyspace, &query.table),
+            DataManipulationQuery::Delete(query) => (&query.keyspace, &query.table),
+        };
+
+        (keyspace.as_deref(), table)
     }
 }
 
-impl InsertQuery {
-    pub(crate) fn new(columns: Vec<String>, table: String, values: Vec<Value>) -> Self {
-        Self { columns, table, values }
+impl ColumnType<'_> {
+    /// Whether a literal can be stored in a column of this type. Follows `codec::row::encode_value`,
+    /// which stores scalar values only, so no literal fits a collection or frozen column.
+    pub(crate) fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (ColumnType::Int, Value::Integer(value)) => i32::try_from(*value).is_ok(),
+            (ColumnType::Long | ColumnType::Counter, Value::Integer(_)) => true,
+            (ColumnType::Timestamp, Value::Timestamp(_) | Value::Integer(_)) => true,
+            (ColumnType::Float | ColumnType::Double, Value::Float(_)) => true,
+            (ColumnType::Text, Value::String(_)) => true,
+            (ColumnType::Bool, Value::Bool(_)) => true,
+            (ColumnType::Uuid, Value::Uuid(_)) => true,
+            _ => false,
+        }
     }
 }
 
-impl UpdateQuery {
-    pub(crate) fn new(table: String, values: Vec<(String, Value)>, conditions: Vec<Condition>) -> Self {
-        Self { table, values, conditions }
+impl<'a> SelectQuery<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(columns: Vec<Selector<'a>>, keyspace: Option<Cow<'a, str>>, table: Cow<'a, str>, table_alias: Option<Cow<'a, str>>, conditions: Vec<Condition<'a>>, per_partition_limit: Option<u32>, allow_filtering: bool, json: bool, distinct: bool, consistency: Option<Consistency>, serial_consistency: Option<Consistency>) -> Self {
+        Self { columns, keyspace, table, table_alias, conditions, per_partition_limit, allow_filtering, json, distinct, consistency, serial_consistency }
     }
 }
 
-impl DeleteQuery {
-    pub(crate) fn new(columns: Vec<String>, table: String, conditions: Vec<Condition>) -> Self {
-        Self { columns, table, conditions }
+impl<'a> InsertQuery<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(columns: Vec<Cow<'a, str>>, keyspace: Option<Cow<'a, str>>, table: Cow<'a, str>, values: Vec<Vec<Expression<'a>>>, json: Option<Cow<'a, str>>, ttl: Option<u32>, timestamp: Option<i64>, if_not_exists: bool, consistency: Option<Consistency>, serial_consistency: Option<Consistency>) -> Self {
+        Self { columns, keyspace, table, values, json, ttl, timestamp, if_not_exists, consistency, serial_consistency }
     }
 }
 
-impl Condition {
-    pub(crate) fn new(column: String, operator: Operator, value: Value) -> Self {
-        Self { column, operator, value }
+impl<'a> UpdateQuery<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(keyspace: Option<Cow<'a, str>>, table: Cow<'a, str>, values: Vec<(Cow<'a, str>, Expression<'a>)>, conditions: Vec<Condition<'a>>, ttl: Option<u32>, timestamp: Option<i64>, if_clause: Option<IfClause<'a>>, consistency: Option<Consistency>, serial_consistency: Option<Consistency>) -> Self {
+        Self { keyspace, table, values, conditions, ttl, timestamp, if_clause, consistency, serial_consistency }
+    }
+}
+
+impl<'a> DeleteQuery<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(columns: Vec<Cow<'a, str>>, keyspace: Option<Cow<'a, str>>, table: Cow<'a, str>, conditions: Vec<Condition<'a>>, timestamp: Option<i64>, if_clause: Option<IfClause<'a>>, consistency: Option<Consistency>, serial_consistency: Option<Consistency>) -> Self {
+        Self { columns, keyspace, table, conditions, timestamp, if_clause, consistency, serial_consistency }
+    }
+}
+
+impl<'a> Condition<'a> {
+    pub(crate) fn new(left: Expression<'a>, operator: Operator, right: Expression<'a>) -> Self {
+        Self { left, operator, right }
+    }
+
+    /// Returns the condition as the restricted side, the operator and the value, where the value
+    /// is a literal, a bind marker or a tuple of them. A comparison written value first such as
+    /// `1 < clustering` is turned around into `clustering > 1`.
+    pub(crate) fn restriction(&self) -> Option<(&Expression<'a>, Operator, &Expression<'a>)> {
+        if self.right.is_restriction_value() {
+            Some((&self.left, self.operator, &self.right))
+        } else if self.left.is_restriction_value() {
+            Some((&self.right, self.operator.reversed(), &self.left))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the column when the condition compares a bare column with a literal or a bind marker,
+    /// which is the only shape that can restrict a primary key.
+    pub(crate) fn restricted_column(&self) -> Option<&str> {
+        match self.restriction()? {
+            (Expression::Column(column) | Expression::QualifiedColumn(_, column), _, value) if !matches!(value, Expression::Tuple(_)) => Some(column),
+            _ => None,
+        }
+    }
+
+    /// Returns the arguments of `token(...)` when the condition compares the token of a row
+    /// with a literal or a bind marker, which is how a scan is bounded to a token range.
+    pub(crate) fn token_arguments(&self) -> Option<&[Expression<'a>]> {
+        match self.restriction()? {
+            (Expression::Function(name, arguments), _, value) if name.eq_ignore_ascii_case(TOKEN) && !matches!(value, Expression::Tuple(_)) => Some(arguments),
+            _ => None,
+        }
+    }
+
+    /// Returns the columns of a multi-column comparison such as `(blog_id, post_id) > ('x', 'y')`
+    /// when both sides are tuples of the same length, one of columns and the other of values.
+    pub(crate) fn tuple_columns(&self) -> Option<Vec<&str>> {
+        match self.restriction()? {
+            (Expression::Tuple(columns), _, Expression::Tuple(values)) if columns.len() == values.len() => {
+                columns.iter()
+                    .map(|column| match column {
+                        Expression::Column(column) | Expression::QualifiedColumn(_, column) => Some(column.as_ref()),
+                        _ => None,
+                    })
+                    .collect()
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        let mut columns = self.left.columns();
+        columns.extend(self.right.columns());
+        columns
+    }
+
+    pub(crate) fn qualifiers(&self) -> Vec<&str> {
+        let mut qualifiers = self.left.qualifiers();
+        qualifiers.extend(self.right.qualifiers());
+        qualifiers
+    }
+}
+
+impl Operator {
+    /// The operator that keeps the comparison true when its sides are swapped.
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            Operator::Greater => Operator::Less,
+            Operator::GreaterOrEquals => Operator::LessOrEquals,
+            Operator::Less => Operator::Greater,
+            Operator::LessOrEquals => Operator::GreaterOrEquals,
+            operator => operator,
+        }
+    }
+}
+
+impl<'a> Expression<'a> {
+    fn is_restriction_value(&self) -> bool {
+        match self {
+            Expression::Literal(_) | Expression::Placeholder | Expression::NamedPlaceholder(_) => true,
+            Expression::Tuple(values) => values.iter().all(|value| matches!(value, Expression::Literal(_) | Expression::Placeholder | Expression::NamedPlaceholder(_))),
+            Expression::Column(_) | Expression::QualifiedColumn(..) | Expression::Binary(..) | Expression::Function(..) => false,
+        }
+    }
+
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        match self {
+            Expression::Column(column) | Expression::QualifiedColumn(_, column) => vec![column.as_ref()],
+            Expression::Literal(_) => Vec::new(),
+            Expression::Binary(left, _, right) => {
+                let mut columns = left.columns();
+                columns.extend(right.columns());
+                columns
+            }
+            Expression::Function(_, arguments) | Expression::Tuple(arguments) => arguments.iter().flat_map(|argument| argument.columns()).collect(),
+            Expression::Placeholder | Expression::NamedPlaceholder(_) => Vec::new(),
+        }
+    }
+
+    pub(crate) fn has_placeholders(&self) -> bool {
+        match self {
+            Expression::Placeholder | Expression::NamedPlaceholder(_) => true,
+            Expression::Column(_) | Expression::QualifiedColumn(..) | Expression::Literal(_) => false,
+            Expression::Binary(left, _, right) => left.has_placeholders() || right.has_placeholders(),
+            Expression::Function(_, arguments) | Expression::Tuple(arguments) => arguments.iter().any(Expression::has_placeholders),
+        }
+    }
+
+    pub(crate) fn qualifiers(&self) -> Vec<&str> {
+        match self {
+            Expression::QualifiedColumn(qualifier, _) => vec![qualifier.as_ref()],
+            Expression::Column(_) | Expression::Literal(_) | Expression::Placeholder | Expression::NamedPlaceholder(_) => Vec::new(),
+            Expression::Binary(left, _, right) => {
+                let mut qualifiers = left.qualifiers();
+                qualifiers.extend(right.qualifiers());
+                qualifiers
+            }
+            Expression::Function(_, arguments) | Expression::Tuple(arguments) => arguments.iter().flat_map(|argument| argument.qualifiers()).collect(),
+        }
     }
 }
 
 #[derive(Debug)]
 #[derive(PartialEq)]
-pub(crate) enum QueryParsingError {
+pub enum QueryParsingError {
     UnsupportedRequest(String),
-    QuerySyntaxError(String, String),
+    QuerySyntaxError(SyntaxError),
+    InvalidQuery(BuilderError),
+    InvalidParameters(BindError),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum BuilderError {
+    MissingField(&'static str),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum BindError {
+    ArityMismatch { expected: usize, actual: usize },
+    NonNumericOperand { index: usize },
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct SyntaxError {
+    pub message: String,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub expected: Vec<String>,
+    pub snippet: String,
 }
 
-impl Eq for Value {}
+impl Eq for Value<'_> {}
 
-impl PartialEq for Value {
+impl PartialEq for Value<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::Integer(x), Value::Integer(y)) => x == y,
             (Value::Float(x), Value::Float(y)) => f64::eq(x, y),
             (Value::String(x), Value::String(y)) => x.eq(y),
             (Value::Bool(x), Value::Bool(y)) => x == y,
+            (Value::Uuid(x), Value::Uuid(y)) => x == y,
+            (Value::Timestamp(x), Value::Timestamp(y)) => x == y,
+            (Value::Null, Value::Null) => true,
+            (Value::UserType(x), Value::UserType(y)) => x == y,
+            (Value::List(x), Value::List(y)) => x == y,
+            (Value::Set(x), Value::Set(y)) => x == y,
+            (Value::Map(x), Value::Map(y)) => x == y,
             _ => false
         }
     }
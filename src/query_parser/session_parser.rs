@@ -0,0 +1,65 @@
+use crate::query_parser::error::syntax_error;
+use crate::query_parser::common_parser::{expect_end, parse_identifier, parse_keyword};
+use crate::query_parser::keyword::{OFF, ON, TRACING, USE};
+use crate::query_parser::query::{Query, QueryParsingError, SessionQuery, TracingQuery, UseQuery};
+use nom::branch::alt;
+use nom::combinator::map;
+
+pub(crate) fn parse_use_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(USE)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'USE' statement", &[USE]))
+    };
+
+    let (query, keyspace) = match parse_identifier(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "cannot parse keyspace name", &["keyspace name"]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(Query::SessionQuery(SessionQuery::Use(UseQuery { keyspace })))
+}
+
+pub(crate) fn parse_tracing_query(source: &str) -> Result<Query<'_>, QueryParsingError> {
+    let query = match parse_keyword(TRACING)(source) {
+        Ok((query, _)) => query,
+        Err(error) => return Err(syntax_error(source, error, "expected 'TRACING' statement", &[TRACING]))
+    };
+
+    let (query, enabled) = match alt((map(parse_keyword(ON), |_| true), map(parse_keyword(OFF), |_| false)))(query) {
+        Ok(result) => result,
+        Err(error) => return Err(syntax_error(source, error, "expected 'ON' or 'OFF'", &[ON, OFF]))
+    };
+
+    expect_end(source, query)?;
+
+    Ok(Query::SessionQuery(SessionQuery::Tracing(TracingQuery { enabled })))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::query_parser::parser::parse_query;
+    use super::*;
+
+    #[test]
+    fn test_use_keyspace() {
+        let query = "USE analytics";
+        let expected_result = UseQuery { keyspace: "analytics".into() };
+        assert_eq!(parse_query(query), Ok(Query::SessionQuery(SessionQuery::Use(expected_result))));
+    }
+
+    #[test]
+    fn test_tracing() {
+        let params = vec![
+            ("TRACING ON", Ok(Query::SessionQuery(SessionQuery::Tracing(TracingQuery { enabled: true })))),
+            ("tracing off", Ok(Query::SessionQuery(SessionQuery::Tracing(TracingQuery { enabled: false })))),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(parse_query(query), expected_result);
+        }
+
+        assert!(parse_query("TRACING MAYBE").is_err());
+    }
+}
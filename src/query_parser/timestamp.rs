@@ -0,0 +1,230 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while_m_n};
+use nom::character::complete::{char, one_of};
+use nom::combinator::{all_consuming, map, map_res, opt};
+use nom::sequence::{pair, preceded, tuple};
+use nom::IResult;
+
+const MILLIS_PER_SECOND: i64 = 1_000;
+const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+const MILLIS_PER_WEEK: i64 = 7 * MILLIS_PER_DAY;
+
+/// Parses an ISO-8601 timestamp such as `2024-10-21`, `2024-10-21 08:30:00` or
+/// `2024-10-21T08:30:00.250+02:00` into milliseconds since the Unix epoch.
+/// Timestamps without an offset are treated as UTC.
+pub(crate) fn parse_timestamp(input: &str) -> Result<i64, String> {
+    match all_consuming(timestamp)(input) {
+        Ok((_, millis)) => Ok(millis),
+        Err(_) => Err(format!("invalid timestamp '{}'", input)),
+    }
+}
+
+/// Formats milliseconds since the Unix epoch as an ISO-8601 UTC timestamp that
+/// `parse_timestamp` reads back unchanged.
+pub(crate) fn format_timestamp(millis: i64) -> String {
+    let days = millis.div_euclid(MILLIS_PER_DAY);
+    let time = millis.rem_euclid(MILLIS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        time / MILLIS_PER_HOUR,
+        time % MILLIS_PER_HOUR / MILLIS_PER_MINUTE,
+        time % MILLIS_PER_MINUTE / MILLIS_PER_SECOND,
+        time % MILLIS_PER_SECOND,
+    )
+}
+
+/// Parses an interval such as `5 minutes`, `1h` or `250 ms` into milliseconds. The amount is
+/// positive and the unit is one of milliseconds, seconds, minutes, hours, days and weeks.
+pub(crate) fn parse_interval(input: &str) -> Result<i64, String> {
+    let error = || format!("invalid interval '{}'", input);
+
+    let input = input.trim();
+    let split = input.find(|ch: char| !ch.is_ascii_digit()).ok_or_else(error)?;
+    let (amount, unit) = input.split_at(split);
+
+    let amount: i64 = amount.parse().map_err(|_| error())?;
+    let unit = match unit.trim_start().to_ascii_lowercase().as_str() {
+        "ms" | "millisecond" | "milliseconds" => 1,
+        "s" | "second" | "seconds" => MILLIS_PER_SECOND,
+        "m" | "min" | "minute" | "minutes" => MILLIS_PER_MINUTE,
+        "h" | "hour" | "hours" => MILLIS_PER_HOUR,
+        "d" | "day" | "days" => MILLIS_PER_DAY,
+        "w" | "week" | "weeks" => MILLIS_PER_WEEK,
+        _ => return Err(error()),
+    };
+
+    match amount.checked_mul(unit) {
+        Some(millis) if millis > 0 => Ok(millis),
+        _ => Err(error()),
+    }
+}
+
+fn timestamp(input: &str) -> IResult<&str, i64> {
+    let (input, days) = date(input)?;
+    let (input, time) = opt(preceded(one_of(" T"), time))(input)?;
+    let (input, offset) = opt(offset)(input)?;
+
+    Ok((input, days * MILLIS_PER_DAY + time.unwrap_or(0) - offset.unwrap_or(0)))
+}
+
+fn date(input: &str) -> IResult<&str, i64> {
+    map_res(
+        tuple((number(4), char('-'), number(2), char('-'), number(2))),
+        |(year, _, month, _, day)| {
+            if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+                return Err("the date is out of range");
+            }
+            Ok(days_from_civil(year, month, day))
+        },
+    )(input)
+}
+
+fn time(input: &str) -> IResult<&str, i64> {
+    let fraction = preceded(char('.'), take_while_m_n(1, 9, |ch: char| ch.is_ascii_digit()));
+
+    map_res(
+        tuple((number(2), char(':'), number(2), opt(pair(preceded(char(':'), number(2)), opt(fraction))))),
+        |(hour, _, minute, seconds)| {
+            let (second, fraction) = seconds.unwrap_or((0, None));
+
+            if hour > 23 || minute > 59 || second > 59 {
+                return Err("the time is out of range");
+            }
+
+            let millis = fraction
+                .map(|fraction: &str| format!("{:0<3}", fraction)[..3].parse::<i64>().unwrap_or(0))
+                .unwrap_or(0);
+
+            Ok(hour * MILLIS_PER_HOUR + minute * MILLIS_PER_MINUTE + second * MILLIS_PER_SECOND + millis)
+        },
+    )(input)
+}
+
+fn offset(input: &str) -> IResult<&str, i64> {
+    alt((
+        map(tag("Z"), |_| 0),
+        map_res(
+            tuple((one_of("+-"), number(2), opt(char(':')), opt(number(2)))),
+            |(sign, hours, _, minutes)| {
+                let minutes = minutes.unwrap_or(0);
+
+                if hours > 23 || minutes > 59 {
+                    return Err("the offset is out of range");
+                }
+
+                let offset = hours * MILLIS_PER_HOUR + minutes * MILLIS_PER_MINUTE;
+                Ok(if sign == '-' { -offset } else { offset })
+            },
+        ),
+    ))(input)
+}
+
+fn number<'a>(digits: usize) -> impl FnMut(&'a str) -> IResult<&'a str, i64> {
+    map_res(take_while_m_n(digits, digits, |ch: char| ch.is_ascii_digit()), str::parse::<i64>)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Counts days since 1970-01-01 in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        let params = vec![
+            ("1970-01-01", Ok(0)),
+            ("2024-10-21", Ok(1_729_468_800_000)),
+            ("2024-10-21 00:00:00", Ok(1_729_468_800_000)),
+            ("2024-10-21T08:30", Ok(1_729_499_400_000)),
+            ("2024-10-21T08:30:15.25Z", Ok(1_729_499_415_250)),
+            ("2024-10-21 10:30:15+02:00", Ok(1_729_499_415_000)),
+            ("2024-10-21 03:30:15-0500", Ok(1_729_499_415_000)),
+            ("1969-12-31 23:59:59", Ok(-1_000)),
+            ("2024-02-29", Ok(1_709_164_800_000)),
+            ("2023-02-29", Err("invalid timestamp '2023-02-29'".to_string())),
+            ("2024-10-21 24:00:00", Err("invalid timestamp '2024-10-21 24:00:00'".to_string())),
+            ("21/10/2024", Err("invalid timestamp '21/10/2024'".to_string())),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_timestamp(input), expected_result, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        let params = vec![
+            (0, "1970-01-01T00:00:00.000Z"),
+            (1_729_499_415_250, "2024-10-21T08:30:15.250Z"),
+            (1_709_164_800_000, "2024-02-29T00:00:00.000Z"),
+            (-1_000, "1969-12-31T23:59:59.000Z"),
+        ];
+
+        for (millis, expected_result) in params {
+            assert_eq!(format_timestamp(millis), expected_result);
+            assert_eq!(parse_timestamp(expected_result), Ok(millis));
+        }
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        let params = vec![
+            ("5 minutes", Ok(300_000)),
+            ("1h", Ok(3_600_000)),
+            ("250 ms", Ok(250)),
+            ("1 Day", Ok(86_400_000)),
+            ("2 weeks", Ok(1_209_600_000)),
+            ("0 seconds", Err("invalid interval '0 seconds'".to_string())),
+            ("-5 minutes", Err("invalid interval '-5 minutes'".to_string())),
+            ("5 fortnights", Err("invalid interval '5 fortnights'".to_string())),
+            ("minutes", Err("invalid interval 'minutes'".to_string())),
+            ("99999999999999999999 ms", Err("invalid interval '99999999999999999999 ms'".to_string())),
+        ];
+
+        for (input, expected_result) in params {
+            assert_eq!(parse_interval(input), expected_result, "{}", input);
+        }
+    }
+}
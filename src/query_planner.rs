@@ -0,0 +1,3 @@
+pub(crate) mod planner;
+pub(crate) mod plan;
+mod error;
@@ -0,0 +1,24 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::query_planner::plan::PlanningError;
+
+impl Display for PlanningError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanningError::UnknownColumn(column) => write!(f, "the column {} doesn't exist", column),
+            PlanningError::UnknownTable(table) => write!(f, "the table or alias {} isn't a part of the query", table),
+            PlanningError::TableNotInSchema(table) => write!(f, "the table {} isn't a part of the schema", table),
+            PlanningError::InvalidTokenRestriction => write!(f, "the token function must take the partition key columns in their declared order"),
+            PlanningError::InvalidTupleRestriction => write!(f, "a multi-column restriction must take consecutive clustering columns in their declared order"),
+            PlanningError::InvalidCellFunction(function) => write!(f, "the {} function takes a single column that isn't a part of the primary key", function),
+            PlanningError::InvalidTimeBucket(reason) => write!(f, "invalid time_bucket call: {}", reason),
+            PlanningError::FilteringRequired => write!(f, "the query requires filtering, use ALLOW FILTERING to execute it anyway"),
+            PlanningError::PartitionKeyNotRestricted => write!(f, "the partition key must be restricted by equality"),
+            PlanningError::MultiplePartitions => write!(f, "the statements of a transaction must write to a single partition"),
+            PlanningError::PrimaryKeyNotRestricted => write!(f, "the primary key must be fully restricted by equality"),
+            PlanningError::NonPrimaryKeyCondition(column) => write!(f, "the column {} isn't a part of the primary key", column),
+        }
+    }
+}
+
+impl Error for PlanningError {}
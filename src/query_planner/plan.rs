@@ -0,0 +1,89 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::ops::Bound;
+use crate::query_parser::query::{ColumnType, Condition, Value};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum AccessPath {
+    PointRead,
+    RangeScan,
+    TokenRangeScan,
+    FullScan,
+}
+
+/// What `EXPLAIN` reports about a read or write: how its rows are found, which of its conditions
+/// the key lookup answers and which are checked against every row read.
+#[derive(Debug, Eq, PartialEq)]
+pub struct QueryPlan<'a> {
+    pub access_path: AccessPath,
+    pub pushed_down_conditions: Vec<&'a Condition<'a>>,
+    pub filter_conditions: Vec<&'a Condition<'a>>,
+    /// An upper bound on the rows the statement touches, when the plan knows one.
+    pub estimated_rows: Option<u64>,
+    /// The part of the partition a point or range read visits. `None` for scans over several
+    /// partitions and when a bound is a bind marker.
+    pub clustering_slice: Option<ClusteringSlice<'a>>,
+    /// The positions of the table columns a read has to decode, or `None` when it needs whole rows.
+    pub read_columns: Option<Vec<usize>>,
+    /// How a write is applied, or `None` for a read.
+    pub write_path: Option<WritePath>,
+}
+
+/// How a write is applied. Plain writes are upserts that never read the row they replace, writes
+/// with an `IF` clause read it first and apply only when the condition holds.
+#[derive(Debug, Eq, PartialEq)]
+pub enum WritePath {
+    Upsert,
+    CompareAndSet,
+}
+
+/// The part of a partition a read needs: rows whose clustering key starts with `prefix` and whose
+/// next clustering columns fall between `start` and `end`. A bound holds several values when a
+/// tuple comparison restricts several columns at once.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ClusteringSlice<'a> {
+    pub prefix: Vec<&'a Value<'a>>,
+    pub start: Bound<Vec<&'a Value<'a>>>,
+    pub end: Bound<Vec<&'a Value<'a>>>,
+}
+
+/// The columns a SELECT returns, in the order of its projection.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ResultSetMetadata<'a> {
+    pub columns: Vec<ColumnSpec<'a>>,
+}
+
+/// A returned column. Computed selectors and fields of user-defined types have no declared type,
+/// and only columns read from the table have a table of origin.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ColumnSpec<'a> {
+    pub name: Cow<'a, str>,
+    pub column_type: Option<&'a ColumnType<'a>>,
+    pub table: Option<&'a str>,
+}
+
+/// A bind marker of a prepared statement, at its position among the parameters. A marker takes
+/// its own name when it has one, otherwise the name of the column it is bound to, and the type
+/// of that column when the statement ties it to one.
+#[derive(Debug, Eq, PartialEq)]
+pub struct BindMarkerSpec<'a> {
+    pub position: usize,
+    pub name: Cow<'a, str>,
+    pub column_type: Option<&'a ColumnType<'a>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum PlanningError {
+    UnknownColumn(String),
+    UnknownTable(String),
+    TableNotInSchema(String),
+    InvalidTokenRestriction,
+    InvalidTupleRestriction,
+    InvalidCellFunction(String),
+    InvalidTimeBucket(String),
+    FilteringRequired,
+    PartitionKeyNotRestricted,
+    MultiplePartitions,
+    PrimaryKeyNotRestricted,
+    NonPrimaryKeyCondition(String),
+}
@@ -0,0 +1,1262 @@
+use std::borrow::Cow;
+use std::ops::Bound;
+use crate::query_parser::keyword::{TIME_BUCKET, TOKEN, TTL, WRITETIME};
+use crate::query_parser::timestamp::parse_interval;
+use crate::query_parser::query::{ColumnType, Condition, Expression, CreateTableQuery, DataManipulationQuery, DeleteQuery, IfClause, InsertQuery, Operator, SelectQuery, Selector, UpdateQuery, Value};
+use crate::query_planner::plan::{AccessPath, BindMarkerSpec, ClusteringSlice, ColumnSpec, PlanningError, QueryPlan, ResultSetMetadata, WritePath};
+
+struct KeyRestrictions {
+    partition_key_restricted: bool,
+    clustering_prefix: usize,
+    clustering_range: bool,
+    token_range: bool,
+    requires_filtering: bool,
+    non_key_column: Option<String>,
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(table = %table.table), err))]
+pub(crate) fn explain<'a>(query: &'a DataManipulationQuery, table: &CreateTableQuery) -> Result<QueryPlan<'a>, PlanningError> {
+    resolve_table(query.target(), table)?;
+
+    let (access_path, conditions) = match query {
+        DataManipulationQuery::Select(query) => (plan_select(query, table)?, &query.conditions),
+        DataManipulationQuery::Update(query) => (plan_update(query, table)?, &query.conditions),
+        DataManipulationQuery::Delete(query) => (plan_delete(query, table)?, &query.conditions),
+        DataManipulationQuery::Insert(_) => return Ok(QueryPlan {
+            access_path: AccessPath::PointRead,
+            pushed_down_conditions: Vec::new(),
+            filter_conditions: Vec::new(),
+            estimated_rows: Some(1),
+            clustering_slice: None,
+            read_columns: None,
+            write_path: write_path(query),
+        }),
+    };
+
+    let restrictions = analyze_conditions(conditions, table)?;
+
+    let (pushed_down_conditions, filter_conditions): (Vec<_>, Vec<_>) = conditions
+        .iter()
+        .partition(|condition| is_pushed_down(condition, &restrictions, table));
+
+    let clustering_slice = match access_path {
+        AccessPath::PointRead | AccessPath::RangeScan => clustering_slice(&pushed_down_conditions, &restrictions, table),
+        AccessPath::TokenRangeScan | AccessPath::FullScan => None,
+    };
+
+    let estimated_rows = match (&access_path, query) {
+        (AccessPath::PointRead, _) => Some(1),
+        // A range scan stays within one partition, so it returns at most the per-partition limit.
+        (AccessPath::RangeScan, DataManipulationQuery::Select(query)) => query.per_partition_limit.map(u64::from),
+        _ => None,
+    };
+
+    let read_columns = match query {
+        DataManipulationQuery::Select(query) => read_columns(query, &filter_conditions, table),
+        DataManipulationQuery::Insert(_) | DataManipulationQuery::Update(_) | DataManipulationQuery::Delete(_) => None,
+    };
+
+    Ok(QueryPlan {
+        access_path,
+        pushed_down_conditions,
+        filter_conditions,
+        estimated_rows,
+        clustering_slice,
+        read_columns,
+        write_path: write_path(query),
+    })
+}
+
+fn write_path(query: &DataManipulationQuery) -> Option<WritePath> {
+    let conditional = match query {
+        DataManipulationQuery::Select(_) => return None,
+        DataManipulationQuery::Insert(query) => query.if_not_exists,
+        DataManipulationQuery::Update(query) => query.if_clause.is_some(),
+        DataManipulationQuery::Delete(query) => query.if_clause.is_some(),
+    };
+
+    Some(if conditional { WritePath::CompareAndSet } else { WritePath::Upsert })
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(table = %query.table), ret, err))]
+pub(crate) fn plan_select(query: &SelectQuery, table: &CreateTableQuery) -> Result<AccessPath, PlanningError> {
+    let qualifiers = query.columns.iter()
+        .flat_map(|selector| selector.expression.qualifiers())
+        .filter(|qualifier| !is_user_type_column(qualifier, table))
+        .chain(query.conditions.iter().flat_map(Condition::qualifiers));
+
+    resolve_qualifiers(qualifiers, query.table_alias.as_deref().unwrap_or(&query.table))?;
+
+    let restrictions = analyze_conditions(&query.conditions, table)?;
+
+    if restrictions.requires_filtering && !query.allow_filtering {
+        return Err(PlanningError::FilteringRequired);
+    }
+
+    Ok(access_path(&restrictions, table))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(table = %query.table), ret, err))]
+pub(crate) fn plan_update(query: &UpdateQuery, table: &CreateTableQuery) -> Result<AccessPath, PlanningError> {
+    resolve_qualifiers(query.conditions.iter().flat_map(Condition::qualifiers), &query.table)?;
+
+    let restrictions = analyze_conditions(&query.conditions, table)?;
+
+    if let Some(column) = restrictions.non_key_column {
+        return Err(PlanningError::NonPrimaryKeyCondition(column));
+    }
+
+    match access_path(&restrictions, table) {
+        AccessPath::PointRead if !restrictions.requires_filtering => Ok(AccessPath::PointRead),
+        _ => Err(PlanningError::PrimaryKeyNotRestricted),
+    }
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(table = %query.table), ret, err))]
+pub(crate) fn plan_delete(query: &DeleteQuery, table: &CreateTableQuery) -> Result<AccessPath, PlanningError> {
+    resolve_qualifiers(query.conditions.iter().flat_map(Condition::qualifiers), &query.table)?;
+
+    let restrictions = analyze_conditions(&query.conditions, table)?;
+
+    if let Some(column) = restrictions.non_key_column {
+        return Err(PlanningError::NonPrimaryKeyCondition(column));
+    }
+
+    if !restrictions.partition_key_restricted || restrictions.requires_filtering {
+        return Err(PlanningError::PartitionKeyNotRestricted);
+    }
+
+    Ok(access_path(&restrictions, table))
+}
+
+/// A transaction is applied as one write to one partition, so every row it writes has to
+/// name the same partition key.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(table = %table.table), err))]
+pub(crate) fn plan_transaction(statements: &[DataManipulationQuery], table: &CreateTableQuery) -> Result<(), PlanningError> {
+    let mut partition = None;
+
+    for statement in statements {
+        let keys = match statement {
+            DataManipulationQuery::Insert(query) => inserted_partition_keys(query, table)?,
+            DataManipulationQuery::Update(query) => {
+                plan_update(query, table)?;
+                vec![restricted_partition_key(&query.conditions, table)?]
+            }
+            DataManipulationQuery::Delete(query) => {
+                plan_delete(query, table)?;
+                vec![restricted_partition_key(&query.conditions, table)?]
+            }
+            DataManipulationQuery::Select(_) => continue,
+        };
+
+        for key in keys {
+            match &partition {
+                None => partition = Some(key),
+                Some(partition) if *partition == key => {}
+                Some(_) => return Err(PlanningError::MultiplePartitions),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The partition key values a statement reads or writes, one key per partition. Statements that
+/// don't restrict the whole partition key by equality have none.
+pub(crate) fn partition_keys<'a>(query: &'a DataManipulationQuery, table: &CreateTableQuery) -> Vec<Vec<&'a Expression<'a>>> {
+    let keys = match query {
+        DataManipulationQuery::Insert(query) => inserted_partition_keys(query, table),
+        DataManipulationQuery::Select(SelectQuery { conditions, .. })
+        | DataManipulationQuery::Update(UpdateQuery { conditions, .. })
+        | DataManipulationQuery::Delete(DeleteQuery { conditions, .. }) => restricted_partition_key(conditions, table).map(|key| vec![key]),
+    };
+
+    keys.unwrap_or_default()
+}
+
+fn inserted_partition_keys<'a>(query: &'a InsertQuery, table: &CreateTableQuery) -> Result<Vec<Vec<&'a Expression<'a>>>, PlanningError> {
+    let indexes = table.primary_key.partition_key.iter()
+        .map(|column| query.columns.iter().position(|name| name == column))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(PlanningError::PartitionKeyNotRestricted)?;
+
+    query.values.iter()
+        .map(|row| indexes.iter().map(|index| row.get(*index)).collect::<Option<Vec<_>>>())
+        .collect::<Option<Vec<_>>>()
+        .ok_or(PlanningError::PartitionKeyNotRestricted)
+}
+
+fn restricted_partition_key<'a>(conditions: &'a [Condition<'a>], table: &CreateTableQuery) -> Result<Vec<&'a Expression<'a>>, PlanningError> {
+    table.primary_key.partition_key.iter()
+        .map(|column| {
+            conditions.iter().find_map(|condition| match condition.restriction() {
+                Some((_, Operator::Equals, value)) if condition.restricted_column() == Some(column.as_ref()) => Some(value),
+                _ => None,
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(PlanningError::PartitionKeyNotRestricted)
+}
+
+/// Describes the columns a SELECT returns. `SELECT *` returns the table columns in their
+/// declared order and `SELECT JSON` returns a single text column holding each row as JSON.
+pub(crate) fn result_metadata<'a>(query: &SelectQuery<'a>, table: &'a CreateTableQuery<'a>) -> Result<ResultSetMetadata<'a>, PlanningError> {
+    if query.json {
+        return Ok(ResultSetMetadata {
+            columns: vec![ColumnSpec { name: Cow::Borrowed("[json]"), column_type: Some(&ColumnType::Text), table: None }],
+        });
+    }
+
+    let columns = if query.columns.is_empty() {
+        table.columns.iter()
+            .map(|column| ColumnSpec { name: Cow::Borrowed(&column.name), column_type: Some(&column.column_type), table: Some(&table.table) })
+            .collect()
+    } else {
+        query.columns.iter()
+            .map(|selector| column_spec(selector, table))
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(ResultSetMetadata { columns })
+}
+
+fn column_spec<'a>(selector: &Selector<'a>, table: &'a CreateTableQuery<'a>) -> Result<ColumnSpec<'a>, PlanningError> {
+    if let Expression::Function(function, arguments) = &selector.expression {
+        let column_type = match cell_metadata_type(function, arguments, table)? {
+            Some(column_type) => Some(column_type),
+            None => time_bucket_type(function, arguments, table)?,
+        };
+
+        if let Some(column_type) = column_type {
+            let name = selector.alias.clone().unwrap_or_else(|| Cow::Owned(selector.expression.to_string()));
+            return Ok(ColumnSpec { name, column_type: Some(column_type), table: None });
+        }
+    }
+
+    let column = match &selector.expression {
+        Expression::Column(name) => Some(name),
+        Expression::QualifiedColumn(qualifier, name) if !is_user_type_column(qualifier, table) => Some(name),
+        _ => None,
+    };
+
+    let name = match (&selector.alias, column) {
+        (Some(alias), _) => alias.clone(),
+        (None, Some(column)) => column.clone(),
+        (None, None) => Cow::Owned(selector.expression.to_string()),
+    };
+
+    let column = match column {
+        Some(column) => column,
+        None => return Ok(ColumnSpec { name, column_type: None, table: None }),
+    };
+
+    match table.columns.iter().find(|definition| definition.name == *column) {
+        Some(definition) => Ok(ColumnSpec { name, column_type: Some(&definition.column_type), table: Some(&table.table) }),
+        None => Err(PlanningError::UnknownColumn(column.to_string())),
+    }
+}
+
+/// The name and type the bind marker at an expression takes from where it stands.
+type MarkerContext<'a> = Option<(&'a str, &'a ColumnType<'a>)>;
+
+/// Describes the bind markers of a statement in the order they are bound. A marker compared with
+/// or assigned to a column takes that column's type, one compared with `token(...)` is a long.
+/// Markers elsewhere, such as function arguments, have no inferred type.
+pub(crate) fn bind_metadata<'a>(query: &DataManipulationQuery<'a>, table: &'a CreateTableQuery<'a>) -> Result<Vec<BindMarkerSpec<'a>>, PlanningError> {
+    let mut markers = Vec::new();
+
+    match query {
+        DataManipulationQuery::Select(query) => {
+            for selector in &query.columns {
+                bind_markers(&selector.expression, None, &mut markers);
+            }
+            condition_markers(&query.conditions, table, &mut markers)?;
+        }
+        DataManipulationQuery::Insert(query) => {
+            for row in &query.values {
+                for (column, value) in query.columns.iter().zip(row) {
+                    bind_markers(value, Some(column_context(column, table)?), &mut markers);
+                }
+            }
+        }
+        DataManipulationQuery::Update(query) => {
+            for (column, value) in &query.values {
+                bind_markers(value, Some(column_context(column, table)?), &mut markers);
+            }
+            condition_markers(&query.conditions, table, &mut markers)?;
+            if let Some(IfClause::Conditions(conditions)) = &query.if_clause {
+                condition_markers(conditions, table, &mut markers)?;
+            }
+        }
+        DataManipulationQuery::Delete(query) => {
+            condition_markers(&query.conditions, table, &mut markers)?;
+            if let Some(IfClause::Conditions(conditions)) = &query.if_clause {
+                condition_markers(conditions, table, &mut markers)?;
+            }
+        }
+    }
+
+    Ok(markers)
+}
+
+fn condition_markers<'a>(conditions: &[Condition<'a>], table: &'a CreateTableQuery<'a>, markers: &mut Vec<BindMarkerSpec<'a>>) -> Result<(), PlanningError> {
+    for condition in conditions {
+        comparison_markers(&condition.left, &condition.right, table, markers)?;
+    }
+
+    Ok(())
+}
+
+/// Each side of a comparison gives the markers on the other side their context. Tuples of the
+/// same length are compared element by element, with the markers still bound left to right.
+fn comparison_markers<'a>(left: &Expression<'a>, right: &Expression<'a>, table: &'a CreateTableQuery<'a>, markers: &mut Vec<BindMarkerSpec<'a>>) -> Result<(), PlanningError> {
+    if let (Expression::Tuple(left), Expression::Tuple(right)) = (left, right) {
+        if left.len() == right.len() {
+            let left_contexts = left.iter().map(|element| expression_context(element, table)).collect::<Result<Vec<_>, _>>()?;
+            let right_contexts = right.iter().map(|element| expression_context(element, table)).collect::<Result<Vec<_>, _>>()?;
+
+            for (element, context) in left.iter().zip(right_contexts) {
+                bind_markers(element, context, markers);
+            }
+            for (element, context) in right.iter().zip(left_contexts) {
+                bind_markers(element, context, markers);
+            }
+
+            return Ok(());
+        }
+    }
+
+    let left_context = expression_context(left, table)?;
+    let right_context = expression_context(right, table)?;
+
+    bind_markers(left, right_context, markers);
+    bind_markers(right, left_context, markers);
+
+    Ok(())
+}
+
+fn expression_context<'a>(expression: &Expression, table: &'a CreateTableQuery<'a>) -> Result<MarkerContext<'a>, PlanningError> {
+    match expression {
+        Expression::Column(column) => column_context(column, table).map(Some),
+        Expression::QualifiedColumn(qualifier, column) if !is_user_type_column(qualifier, table) => column_context(column, table).map(Some),
+        Expression::Function(name, _) if name.eq_ignore_ascii_case(TOKEN) => Ok(Some(("partition key token", &ColumnType::Long))),
+        _ => Ok(None),
+    }
+}
+
+fn column_context<'a>(column: &str, table: &'a CreateTableQuery<'a>) -> Result<(&'a str, &'a ColumnType<'a>), PlanningError> {
+    table.columns.iter()
+        .find(|definition| definition.name == column)
+        .map(|definition| (definition.name.as_ref(), &definition.column_type))
+        .ok_or_else(|| PlanningError::UnknownColumn(column.to_string()))
+}
+
+/// Records the markers of an expression. Arithmetic keeps the context, so the marker in
+/// `hits = hits + ?` is typed like `hits`, while function arguments and tuple elements lose it.
+fn bind_markers<'a>(expression: &Expression<'a>, context: MarkerContext<'a>, markers: &mut Vec<BindMarkerSpec<'a>>) {
+    let name = match expression {
+        Expression::Placeholder => context.map_or(Cow::Borrowed("?"), |(column, _)| Cow::Borrowed(column)),
+        Expression::NamedPlaceholder(name) => name.clone(),
+        Expression::Binary(left, _, right) => {
+            bind_markers(left, context, markers);
+            bind_markers(right, context, markers);
+            return;
+        }
+        Expression::Function(_, arguments) | Expression::Tuple(arguments) => {
+            for argument in arguments {
+                bind_markers(argument, None, markers);
+            }
+            return;
+        }
+        Expression::Column(_) | Expression::QualifiedColumn(..) | Expression::Literal(_) => return,
+    };
+
+    markers.push(BindMarkerSpec { position: markers.len(), name, column_type: context.map(|(_, column_type)| column_type) });
+}
+
+/// The columns a SELECT has to decode from the rows it reads: the ones its projection and its
+/// filtering refer to. Key conditions are checked against the key, so they don't add columns.
+/// `SELECT *` and `SELECT JSON` read whole rows.
+fn read_columns(query: &SelectQuery, filter_conditions: &[&Condition], table: &CreateTableQuery) -> Option<Vec<usize>> {
+    if query.columns.is_empty() || query.json {
+        return None;
+    }
+
+    let expressions = query.columns.iter()
+        .map(|selector| &selector.expression)
+        .chain(filter_conditions.iter().flat_map(|condition| [&condition.left, &condition.right]));
+
+    // A qualifier names either the table or a user-defined type column such as `address` in
+    // `address.city`, so the names of both kinds are matched against the table columns.
+    let names: Vec<&str> = expressions
+        .flat_map(|expression| expression.columns().into_iter().chain(expression.qualifiers()))
+        .collect();
+
+    let positions = table.columns.iter()
+        .enumerate()
+        .filter(|(_, column)| names.contains(&column.name.as_ref()))
+        .map(|(position, _)| position)
+        .collect();
+
+    Some(positions)
+}
+
+/// `writetime(column)` and `ttl(column)` read the write timestamp and the remaining time to live
+/// of a cell, so they take a single column outside the primary key. Other functions aren't typed.
+fn cell_metadata_type(function: &str, arguments: &[Expression], table: &CreateTableQuery) -> Result<Option<&'static ColumnType<'static>>, PlanningError> {
+    let column_type = if function.eq_ignore_ascii_case(WRITETIME) {
+        &ColumnType::Long
+    } else if function.eq_ignore_ascii_case(TTL) {
+        &ColumnType::Int
+    } else {
+        return Ok(None);
+    };
+
+    let column = match arguments {
+        [Expression::Column(column) | Expression::QualifiedColumn(_, column)] => column,
+        _ => return Err(PlanningError::InvalidCellFunction(function.to_string())),
+    };
+
+    if !table.columns.iter().any(|definition| definition.name == *column) {
+        return Err(PlanningError::UnknownColumn(column.to_string()));
+    }
+
+    if table.primary_key.partition_key.contains(column) || table.primary_key.clustering_key.contains(column) {
+        return Err(PlanningError::InvalidCellFunction(function.to_string()));
+    }
+
+    Ok(Some(column_type))
+}
+
+/// `time_bucket('5 minutes', column)` rounds a timestamp column down to the start of its bucket,
+/// so it takes an interval literal and a timestamp column and returns a timestamp.
+fn time_bucket_type(function: &str, arguments: &[Expression], table: &CreateTableQuery) -> Result<Option<&'static ColumnType<'static>>, PlanningError> {
+    if !function.eq_ignore_ascii_case(TIME_BUCKET) {
+        return Ok(None);
+    }
+
+    let (interval, column) = match arguments {
+        [Expression::Literal(Value::String(interval)), Expression::Column(column) | Expression::QualifiedColumn(_, column)] => (interval, column),
+        _ => return Err(PlanningError::InvalidTimeBucket("it takes an interval literal and a timestamp column".to_string())),
+    };
+
+    parse_interval(interval).map_err(PlanningError::InvalidTimeBucket)?;
+
+    match table.columns.iter().find(|definition| definition.name == *column) {
+        Some(definition) if definition.column_type == ColumnType::Timestamp => Ok(Some(&ColumnType::Timestamp)),
+        Some(_) => Err(PlanningError::InvalidTimeBucket(format!("the column {} isn't a timestamp", column))),
+        None => Err(PlanningError::UnknownColumn(column.to_string())),
+    }
+}
+
+/// Finds the table a statement targets in a snapshot of the schema. A statement or a table without
+/// a keyspace matches the table of that name in any keyspace.
+pub(crate) fn find_table<'s>(query: &DataManipulationQuery, schema: &'s [CreateTableQuery<'s>]) -> Result<&'s CreateTableQuery<'s>, PlanningError> {
+    let (keyspace, name) = query.target();
+
+    schema.iter()
+        .find(|table| table.table == name && (keyspace.is_none() || table.keyspace.is_none() || table.keyspace.as_deref() == keyspace))
+        .ok_or_else(|| PlanningError::TableNotInSchema(keyspace.map_or(name.to_string(), |keyspace| format!("{}.{}", keyspace, name))))
+}
+
+/// The statement must target the planned table. Its keyspace is the one it names or, once the
+/// session has qualified it, the current one; either side without a keyspace matches any.
+fn resolve_table((keyspace, name): (Option<&str>, &str), table: &CreateTableQuery) -> Result<(), PlanningError> {
+    let same_keyspace = match (keyspace, table.keyspace.as_deref()) {
+        (Some(keyspace), Some(table_keyspace)) => keyspace == table_keyspace,
+        _ => true,
+    };
+
+    if same_keyspace && name == table.table {
+        Ok(())
+    } else {
+        Err(PlanningError::UnknownTable(keyspace.map_or(name.to_string(), |keyspace| format!("{}.{}", keyspace, name))))
+    }
+}
+
+/// A qualified column must name its table by the alias when the query aliases the table,
+/// otherwise by the table name.
+fn resolve_qualifiers<'a>(mut qualifiers: impl Iterator<Item = &'a str>, table: &str) -> Result<(), PlanningError> {
+    match qualifiers.find(|qualifier| *qualifier != table) {
+        Some(qualifier) => Err(PlanningError::UnknownTable(qualifier.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// A projection such as `address.city` reads a field of a user-defined type column
+/// rather than a column of a table named `address`.
+fn is_user_type_column(name: &str, table: &CreateTableQuery) -> bool {
+    table.columns.iter().any(|column| column.name == name && matches!(column.column_type, ColumnType::Frozen(_)))
+}
+
+fn access_path(restrictions: &KeyRestrictions, table: &CreateTableQuery) -> AccessPath {
+    if !restrictions.partition_key_restricted {
+        if restrictions.token_range { AccessPath::TokenRangeScan } else { AccessPath::FullScan }
+    } else if restrictions.clustering_prefix == table.primary_key.clustering_key.len() {
+        AccessPath::PointRead
+    } else {
+        AccessPath::RangeScan
+    }
+}
+
+fn is_pushed_down(condition: &Condition, restrictions: &KeyRestrictions, table: &CreateTableQuery) -> bool {
+    let primary_key = &table.primary_key;
+
+    if condition.token_arguments().is_some() {
+        return restrictions.token_range && !restrictions.partition_key_restricted;
+    }
+
+    if let Some(columns) = condition.tuple_columns() {
+        let start = columns.first().and_then(|first| primary_key.clustering_key.iter().position(|column| column == first));
+
+        return match start {
+            Some(start) if condition.operator == Operator::Equals => start + columns.len() <= restrictions.clustering_prefix,
+            Some(start) => start == restrictions.clustering_prefix && restrictions.clustering_range,
+            None => false,
+        };
+    }
+
+    let restricted_column = match condition.restricted_column() {
+        Some(column) => column,
+        None => return false,
+    };
+
+    if primary_key.partition_key.iter().any(|column| column == restricted_column) {
+        return restrictions.partition_key_restricted;
+    }
+
+    match primary_key.clustering_key.iter().position(|column| column == restricted_column) {
+        Some(index) if index < restrictions.clustering_prefix => true,
+        Some(index) => index == restrictions.clustering_prefix && restrictions.clustering_range,
+        None => false,
+    }
+}
+
+/// Translates the pushed-down clustering conditions into the bounds of the slice the storage reads,
+/// so a range over a clustering column doesn't materialize the whole partition. Returns `None` when
+/// a bound is a bind marker, as the slice is then known only once the statement is bound.
+fn clustering_slice<'a>(conditions: &[&'a Condition<'a>], restrictions: &KeyRestrictions, table: &CreateTableQuery) -> Option<ClusteringSlice<'a>> {
+    let clustering_key = &table.primary_key.clustering_key;
+    let mut prefix: Vec<Option<&Value>> = vec![None; restrictions.clustering_prefix];
+    let mut start = Bound::Unbounded;
+    let mut end = Bound::Unbounded;
+
+    for condition in conditions {
+        let Some((_, operator, value)) = condition.restriction() else { continue };
+
+        let (columns, values) = match (condition.tuple_columns(), condition.restricted_column(), value) {
+            (Some(columns), _, Expression::Tuple(values)) => (columns, values.iter().collect()),
+            (None, Some(column), value) => (vec![column], vec![value]),
+            _ => continue,
+        };
+
+        let index = match columns.first().and_then(|first| clustering_key.iter().position(|column| column == first)) {
+            Some(index) => index,
+            None => continue,
+        };
+
+        let values = values.into_iter()
+            .map(|value| match value {
+                Expression::Literal(value) => Some(value),
+                _ => None,
+            })
+            .collect::<Option<Vec<&Value>>>()?;
+
+        match operator {
+            Operator::Equals => {
+                for (slot, value) in prefix.iter_mut().skip(index).zip(values) {
+                    *slot = Some(value);
+                }
+            }
+            Operator::Greater => start = Bound::Excluded(values),
+            Operator::GreaterOrEquals => start = Bound::Included(values),
+            Operator::Less => end = Bound::Excluded(values),
+            Operator::LessOrEquals => end = Bound::Included(values),
+            Operator::NotEquals | Operator::Is | Operator::IsNot => {}
+        }
+    }
+
+    Some(ClusteringSlice {
+        prefix: prefix.into_iter().flatten().collect(),
+        start,
+        end,
+    })
+}
+
+fn analyze_conditions(conditions: &[Condition], table: &CreateTableQuery) -> Result<KeyRestrictions, PlanningError> {
+    let primary_key = &table.primary_key;
+
+    let mut partition_operators: Vec<Vec<Operator>> = vec![Vec::new(); primary_key.partition_key.len()];
+    let mut clustering_operators: Vec<Vec<Operator>> = vec![Vec::new(); primary_key.clustering_key.len()];
+    let mut token_operators: Vec<Operator> = Vec::new();
+    let mut requires_filtering = false;
+    let mut non_key_column = None;
+
+    for condition in conditions {
+        let columns = condition.columns();
+
+        if let Some(unknown_column) = columns.iter().find(|name| !table.columns.iter().any(|column| column.name == **name)) {
+            return Err(PlanningError::UnknownColumn(unknown_column.to_string()));
+        }
+
+        let operator = condition.restriction().map_or(condition.operator, |(_, operator, _)| operator);
+
+        if let Some(arguments) = condition.token_arguments() {
+            let is_partition_key = arguments.len() == primary_key.partition_key.len()
+                && arguments.iter().zip(&primary_key.partition_key).all(|(argument, column)| {
+                    matches!(argument, Expression::Column(name) | Expression::QualifiedColumn(_, name) if name == column)
+                });
+
+            if !is_partition_key {
+                return Err(PlanningError::InvalidTokenRestriction);
+            }
+
+            token_operators.push(operator);
+            continue;
+        }
+
+        if let Some(tuple_columns) = condition.tuple_columns() {
+            let start = tuple_columns.first()
+                .and_then(|first| primary_key.clustering_key.iter().position(|column| column == first))
+                .filter(|start| {
+                    primary_key.clustering_key.get(*start..*start + tuple_columns.len())
+                        .is_some_and(|key| key.iter().zip(&tuple_columns).all(|(column, name)| column == name))
+                });
+
+            let start = match start {
+                Some(start) => start,
+                None => return Err(PlanningError::InvalidTupleRestriction),
+            };
+
+            // A tuple slice orders rows by its first column, the following columns only break ties.
+            if operator == Operator::Equals {
+                for operators in &mut clustering_operators[start..start + tuple_columns.len()] {
+                    operators.push(operator);
+                }
+            } else {
+                clustering_operators[start].push(operator);
+            }
+
+            continue;
+        }
+
+        let restricted_column = match condition.restricted_column() {
+            Some(column) => column,
+            None => {
+                requires_filtering = true;
+                if let Some(column) = columns.first() {
+                    non_key_column.get_or_insert_with(|| column.to_string());
+                }
+                continue;
+            }
+        };
+
+        if let Some(index) = primary_key.partition_key.iter().position(|column| column == restricted_column) {
+            partition_operators[index].push(operator);
+        } else if let Some(index) = primary_key.clustering_key.iter().position(|column| column == restricted_column) {
+            clustering_operators[index].push(operator);
+        } else {
+            requires_filtering = true;
+            non_key_column.get_or_insert_with(|| restricted_column.to_string());
+        }
+    }
+
+    let partition_key_restricted = partition_operators.iter().all(|operators| is_equality(operators));
+    let token_range = !token_operators.is_empty() && is_range(&token_operators);
+
+    if !token_operators.is_empty() && (!token_range || partition_key_restricted) {
+        requires_filtering = true;
+    }
+
+    if !partition_key_restricted && partition_operators.iter().any(|operators| !operators.is_empty()) {
+        requires_filtering = true;
+    }
+
+    let mut clustering_prefix = 0;
+    let mut clustering_range = false;
+    let mut slice_closed = false;
+
+    for operators in &clustering_operators {
+        if operators.is_empty() {
+            slice_closed = true;
+        } else if slice_closed || !partition_key_restricted {
+            requires_filtering = true;
+        } else if is_equality(operators) {
+            clustering_prefix += 1;
+        } else if is_range(operators) {
+            clustering_range = true;
+            slice_closed = true;
+        } else {
+            requires_filtering = true;
+            slice_closed = true;
+        }
+    }
+
+    Ok(KeyRestrictions {
+        partition_key_restricted,
+        clustering_prefix,
+        clustering_range,
+        token_range,
+        requires_filtering,
+        non_key_column,
+    })
+}
+
+fn is_equality(operators: &[Operator]) -> bool {
+    matches!(operators, [Operator::Equals])
+}
+
+fn is_range(operators: &[Operator]) -> bool {
+    let lower_bounds = operators.iter().filter(|operator| matches!(operator, Operator::Greater | Operator::GreaterOrEquals)).count();
+    let upper_bounds = operators.iter().filter(|operator| matches!(operator, Operator::Less | Operator::LessOrEquals)).count();
+    lower_bounds <= 1 && upper_bounds <= 1 && lower_bounds + upper_bounds == operators.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::parser::parse_query;
+    use crate::query_parser::query::{DataDefinitionQuery, DataManipulationQuery, Query};
+    use crate::session::Session;
+
+    const USER_SESSIONS: &str = "CREATE TABLE user_sessions (user_id UUID, session_id UUID, timestamp TIMESTAMP, device_type TEXT, PRIMARY KEY ((user_id, session_id), timestamp))";
+
+    fn table(query: &str) -> CreateTableQuery<'_> {
+        match parse_query(query) {
+            Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) => table,
+            result => panic!("expected a create table query, got {:?}", result),
+        }
+    }
+
+    fn dml(query: &str) -> DataManipulationQuery<'_> {
+        match parse_query(query) {
+            Ok(Query::DataManipulationQuery(query)) => query,
+            result => panic!("expected a data manipulation query, got {:?}", result),
+        }
+    }
+
+    fn plan(query: &str, table: &CreateTableQuery) -> Result<AccessPath, PlanningError> {
+        match dml(query) {
+            DataManipulationQuery::Select(query) => plan_select(&query, table),
+            DataManipulationQuery::Update(query) => plan_update(&query, table),
+            DataManipulationQuery::Delete(query) => plan_delete(&query, table),
+            query => panic!("unexpected query {:?}", query),
+        }
+    }
+
+    #[test]
+    fn test_plan_select() {
+        let table = table(USER_SESSIONS);
+
+        let params = vec![
+            (
+                "SELECT * FROM user_sessions",
+                Ok(AccessPath::FullScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp = 3",
+                Ok(AccessPath::PointRead)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2",
+                Ok(AccessPath::RangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp >= 3 AND timestamp < 4",
+                Ok(AccessPath::RangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 ALLOW FILTERING",
+                Ok(AccessPath::FullScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND device_type = 'PHONE'",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE timestamp > 3",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp != 3",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE browser = 'FIREFOX'",
+                Err(PlanningError::UnknownColumn("browser".to_string()))
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp >= TIMESTAMP '2024-10-21 00:00:00'",
+                Ok(AccessPath::RangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp + 10 > 3",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE lower(browser) = 'firefox'",
+                Err(PlanningError::UnknownColumn("browser".to_string()))
+            ),
+            (
+                "SELECT s.device_type AS device FROM user_sessions s WHERE s.user_id = 1 AND s.session_id = 2 AND timestamp = 3",
+                Ok(AccessPath::PointRead)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE user_sessions.user_id = 1 AND user_sessions.session_id = 2",
+                Ok(AccessPath::RangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions AS s WHERE user_sessions.user_id = 1",
+                Err(PlanningError::UnknownTable("user_sessions".to_string()))
+            ),
+            (
+                "SELECT t.device_type FROM user_sessions s",
+                Err(PlanningError::UnknownTable("t".to_string()))
+            ),
+            (
+                "SELECT * FROM user_sessions s WHERE s.browser = 'FIREFOX'",
+                Err(PlanningError::UnknownColumn("browser".to_string()))
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE token(user_id, session_id) > -100 AND token(user_id, session_id) <= 100",
+                Ok(AccessPath::TokenRangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions s WHERE TOKEN(s.user_id, s.session_id) > 0",
+                Ok(AccessPath::TokenRangeScan)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE token(user_id, session_id) > 0 AND timestamp = 3",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE token(user_id, session_id) > 0 AND token(user_id, session_id) > 1",
+                Err(PlanningError::FilteringRequired)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE token(session_id, user_id) > 0",
+                Err(PlanningError::InvalidTokenRestriction)
+            ),
+            (
+                "SELECT * FROM user_sessions WHERE token(user_id) > 0",
+                Err(PlanningError::InvalidTokenRestriction)
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan(query, &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_plan_tuple_restrictions() {
+        let table = table("CREATE TABLE posts (user_id INT, blog_id TEXT, post_id TEXT, title TEXT, PRIMARY KEY (user_id, blog_id, post_id))");
+
+        let params = vec![
+            ("SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) > ('x', 'y')", Ok(AccessPath::RangeScan)),
+            ("SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) > ('x', 'y') AND blog_id <= 'z'", Ok(AccessPath::RangeScan)),
+            ("SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) = ('x', 'y')", Ok(AccessPath::PointRead)),
+            ("SELECT * FROM posts WHERE user_id = 1 AND blog_id = 'x' AND (post_id, title) > ('y', 'z')", Err(PlanningError::InvalidTupleRestriction)),
+            ("SELECT * FROM posts WHERE user_id = 1 AND (post_id, blog_id) > ('y', 'x')", Err(PlanningError::InvalidTupleRestriction)),
+            ("SELECT * FROM posts WHERE (blog_id, post_id) > ('x', 'y')", Err(PlanningError::FilteringRequired)),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan(query, &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_plan_user_type_fields() {
+        let table = table("CREATE TABLE users (user_id UUID PRIMARY KEY, name TEXT, address FROZEN<address>)");
+
+        let params = vec![
+            ("SELECT name, address.city FROM users WHERE user_id = 1", Ok(AccessPath::PointRead)),
+            ("SELECT u.address FROM users u WHERE u.user_id = 1", Ok(AccessPath::PointRead)),
+            ("SELECT name.first FROM users WHERE user_id = 1", Err(PlanningError::UnknownTable("name".to_string()))),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan(query, &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let table = table(USER_SESSIONS);
+
+        let query = dml("SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp >= 3 AND device_type = 'PHONE' ALLOW FILTERING");
+        let plan = explain(&query, &table).unwrap();
+
+        let pushed_down_columns: Vec<&str> = plan.pushed_down_conditions.iter().flat_map(|condition| condition.restricted_column()).collect();
+        let filter_columns: Vec<&str> = plan.filter_conditions.iter().flat_map(|condition| condition.restricted_column()).collect();
+
+        assert_eq!(plan.access_path, AccessPath::RangeScan);
+        assert_eq!(pushed_down_columns, vec!["user_id", "session_id", "timestamp"]);
+        assert_eq!(filter_columns, vec!["device_type"]);
+        assert_eq!(plan.estimated_rows, None);
+
+        let query = dml("SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp = 3");
+        let plan = explain(&query, &table).unwrap();
+
+        assert_eq!(plan.access_path, AccessPath::PointRead);
+        assert_eq!(plan.pushed_down_conditions.len(), 3);
+        assert!(plan.filter_conditions.is_empty());
+        assert_eq!(plan.estimated_rows, Some(1));
+
+        let query = dml("SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2 PER PARTITION LIMIT 5");
+        let plan = explain(&query, &table).unwrap();
+
+        assert_eq!(plan.access_path, AccessPath::RangeScan);
+        assert_eq!(plan.estimated_rows, Some(5));
+
+        let query = dml("SELECT * FROM user_sessions WHERE token(user_id, session_id) > 0 AND token(user_id, session_id) <= 100 AND device_type = 'PHONE' ALLOW FILTERING");
+        let plan = explain(&query, &table).unwrap();
+
+        assert_eq!(plan.access_path, AccessPath::TokenRangeScan);
+        assert_eq!(plan.pushed_down_conditions.len(), 2);
+        assert_eq!(plan.filter_conditions.len(), 1);
+    }
+
+    #[test]
+    fn test_explain_read_columns() {
+        let table = table("CREATE TABLE users (user_id UUID PRIMARY KEY, name TEXT, email TEXT, visits INT, address FROZEN<address>)");
+
+        let params = vec![
+            ("SELECT * FROM users WHERE user_id = 1", None),
+            ("SELECT JSON name FROM users WHERE user_id = 1", None),
+            ("SELECT email, name FROM users WHERE user_id = 1", Some(vec![1, 2])),
+            ("SELECT address.city FROM users WHERE user_id = 1", Some(vec![4])),
+            ("SELECT writetime(email), visits + 1 FROM users WHERE user_id = 1", Some(vec![2, 3])),
+            ("SELECT name FROM users WHERE visits > 3 ALLOW FILTERING", Some(vec![1, 3])),
+            ("UPDATE users SET visits = 1 WHERE user_id = 1", None),
+        ];
+
+        for (query, expected_result) in params {
+            let statement = dml(query);
+            assert_eq!(explain(&statement, &table).unwrap().read_columns, expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_explain_write_path() {
+        let table = table(USER_SESSIONS);
+
+        let params = vec![
+            ("SELECT * FROM user_sessions WHERE user_id = 1 AND session_id = 2", None),
+            ("INSERT INTO user_sessions (user_id, session_id, timestamp) VALUES (1, 2, 3)", Some(WritePath::Upsert)),
+            ("INSERT INTO user_sessions (user_id, session_id, timestamp) VALUES (1, 2, 3) IF NOT EXISTS", Some(WritePath::CompareAndSet)),
+            ("UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND session_id = 2 AND timestamp = 3", Some(WritePath::Upsert)),
+            ("UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND session_id = 2 AND timestamp = 3 IF EXISTS", Some(WritePath::CompareAndSet)),
+            ("DELETE FROM user_sessions WHERE user_id = 1 AND session_id = 2", Some(WritePath::Upsert)),
+            ("DELETE FROM user_sessions WHERE user_id = 1 AND session_id = 2 IF device_type = 'PHONE'", Some(WritePath::CompareAndSet)),
+        ];
+
+        for (query, expected_result) in params {
+            let statement = dml(query);
+            assert_eq!(explain(&statement, &table).unwrap().write_path, expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_explain_clustering_slice() {
+        let table = table("CREATE TABLE posts (user_id INT, blog_id TEXT, post_id INT, title TEXT, PRIMARY KEY (user_id, blog_id, post_id))");
+        let blog = Value::String("x".into());
+
+        let params = vec![
+            (
+                "SELECT * FROM posts WHERE user_id = 1 AND blog_id = 'x' AND post_id >= 10 AND post_id < 20",
+                Some(ClusteringSlice { prefix: vec![&blog], start: Bound::Included(vec![&Value::Integer(10)]), end: Bound::Excluded(vec![&Value::Integer(20)]) }),
+            ),
+            (
+                "SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) > ('x', 10)",
+                Some(ClusteringSlice { prefix: vec![], start: Bound::Excluded(vec![&blog, &Value::Integer(10)]), end: Bound::Unbounded }),
+            ),
+            (
+                "SELECT * FROM posts WHERE user_id = 1 AND (blog_id, post_id) = ('x', 10)",
+                Some(ClusteringSlice { prefix: vec![&blog, &Value::Integer(10)], start: Bound::Unbounded, end: Bound::Unbounded }),
+            ),
+            (
+                "SELECT * FROM posts WHERE 1 = user_id AND 'x' = blog_id AND 10 < post_id",
+                Some(ClusteringSlice { prefix: vec![&blog], start: Bound::Excluded(vec![&Value::Integer(10)]), end: Bound::Unbounded }),
+            ),
+            (
+                "SELECT * FROM posts WHERE user_id = ? AND blog_id = :blog_id AND post_id > ?",
+                None,
+            ),
+            (
+                "SELECT * FROM posts WHERE user_id = 1 AND post_id <= 10 ALLOW FILTERING",
+                Some(ClusteringSlice { prefix: vec![], start: Bound::Unbounded, end: Bound::Unbounded }),
+            ),
+            (
+                "SELECT * FROM posts WHERE blog_id = 'x' ALLOW FILTERING",
+                None,
+            ),
+        ];
+
+        for (query, expected_slice) in params {
+            let query = dml(query);
+            assert_eq!(explain(&query, &table).unwrap().clustering_slice, expected_slice);
+        }
+    }
+
+    #[test]
+    fn test_result_metadata() {
+        let table = table("CREATE TABLE users (user_id UUID PRIMARY KEY, name TEXT, visits INT, address FROZEN<address>, seen_at TIMESTAMP)");
+
+        let select = |query| match dml(query) {
+            DataManipulationQuery::Select(query) => query,
+            query => panic!("expected a select query, got {:?}", query),
+        };
+
+        let spec = |name: &'static str, column_type, table| ColumnSpec { name: Cow::Borrowed(name), column_type, table };
+
+        let params = vec![
+            (
+                "SELECT * FROM users",
+                Ok(vec![
+                    spec("user_id", Some(&ColumnType::Uuid), Some("users")),
+                    spec("name", Some(&ColumnType::Text), Some("users")),
+                    spec("visits", Some(&ColumnType::Int), Some("users")),
+                    spec("address", Some(&table.columns[3].column_type), Some("users")),
+                    spec("seen_at", Some(&ColumnType::Timestamp), Some("users")),
+                ]),
+            ),
+            (
+                "SELECT u.name AS user_name, visits + 1, address.city FROM users u",
+                Ok(vec![
+                    spec("user_name", Some(&ColumnType::Text), Some("users")),
+                    spec("visits + 1", None, None),
+                    spec("address.city", None, None),
+                ]),
+            ),
+            (
+                "SELECT JSON name FROM users",
+                Ok(vec![spec("[json]", Some(&ColumnType::Text), None)]),
+            ),
+            (
+                "SELECT email FROM users",
+                Err(PlanningError::UnknownColumn("email".to_string())),
+            ),
+            (
+                "SELECT WRITETIME(name), ttl(u.visits) AS visits_ttl FROM users u",
+                Ok(vec![
+                    spec("writetime(name)", Some(&ColumnType::Long), None),
+                    spec("visits_ttl", Some(&ColumnType::Int), None),
+                ]),
+            ),
+            (
+                "SELECT writetime(user_id) FROM users",
+                Err(PlanningError::InvalidCellFunction("writetime".to_string())),
+            ),
+            (
+                "SELECT ttl(name, visits) FROM users",
+                Err(PlanningError::InvalidCellFunction("ttl".to_string())),
+            ),
+            (
+                "SELECT ttl(email) FROM users",
+                Err(PlanningError::UnknownColumn("email".to_string())),
+            ),
+            (
+                "SELECT time_bucket('5 minutes', seen_at) AS bucket, TIME_BUCKET('1h', u.seen_at) FROM users u",
+                Ok(vec![
+                    spec("bucket", Some(&ColumnType::Timestamp), None),
+                    spec("time_bucket('1h', u.seen_at)", Some(&ColumnType::Timestamp), None),
+                ]),
+            ),
+            (
+                "SELECT time_bucket('5 fortnights', seen_at) FROM users",
+                Err(PlanningError::InvalidTimeBucket("invalid interval '5 fortnights'".to_string())),
+            ),
+            (
+                "SELECT time_bucket('5 minutes', name) FROM users",
+                Err(PlanningError::InvalidTimeBucket("the column name isn't a timestamp".to_string())),
+            ),
+            (
+                "SELECT time_bucket(seen_at) FROM users",
+                Err(PlanningError::InvalidTimeBucket("it takes an interval literal and a timestamp column".to_string())),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            let select = select(query);
+            assert_eq!(result_metadata(&select, &table).map(|metadata| metadata.columns), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_bind_metadata() {
+        let table = table("CREATE TABLE posts (blog_id UUID, posted_at TIMESTAMP, post_id INT, title TEXT, views INT, address FROZEN<address>, PRIMARY KEY ((blog_id), posted_at, post_id))");
+
+        let spec = |position, name: &'static str, column_type| BindMarkerSpec { position, name: Cow::Borrowed(name), column_type };
+
+        let params = vec![
+            (
+                "SELECT title, upper(?) FROM posts WHERE blog_id = :blog AND ? <= posted_at AND (posted_at, post_id) > (?, :post)",
+                Ok(vec![
+                    spec(0, "?", None),
+                    spec(1, "blog", Some(&ColumnType::Uuid)),
+                    spec(2, "posted_at", Some(&ColumnType::Timestamp)),
+                    spec(3, "posted_at", Some(&ColumnType::Timestamp)),
+                    spec(4, "post", Some(&ColumnType::Int)),
+                ]),
+            ),
+            (
+                "SELECT * FROM posts WHERE token(blog_id) > ? AND address.city = ? ALLOW FILTERING",
+                Ok(vec![
+                    spec(0, "partition key token", Some(&ColumnType::Long)),
+                    spec(1, "?", None),
+                ]),
+            ),
+            (
+                "INSERT INTO posts (blog_id, posted_at, post_id, title) VALUES (?, ?, 1, :title), (?, toTimestamp(?), 2, 'b')",
+                Ok(vec![
+                    spec(0, "blog_id", Some(&ColumnType::Uuid)),
+                    spec(1, "posted_at", Some(&ColumnType::Timestamp)),
+                    spec(2, "title", Some(&ColumnType::Text)),
+                    spec(3, "blog_id", Some(&ColumnType::Uuid)),
+                    spec(4, "?", None),
+                ]),
+            ),
+            (
+                "UPDATE posts SET views = views + ? WHERE blog_id = ? AND posted_at = 0 AND post_id = 1 IF title = ?",
+                Ok(vec![
+                    spec(0, "views", Some(&ColumnType::Int)),
+                    spec(1, "blog_id", Some(&ColumnType::Uuid)),
+                    spec(2, "title", Some(&ColumnType::Text)),
+                ]),
+            ),
+            (
+                "DELETE FROM posts WHERE blog_id = ? AND author = ?",
+                Err(PlanningError::UnknownColumn("author".to_string())),
+            ),
+            (
+                "INSERT INTO posts (blog_id, author) VALUES (?, ?)",
+                Err(PlanningError::UnknownColumn("author".to_string())),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            let query = dml(query);
+            assert_eq!(bind_metadata(&query, &table), expected_result, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_plan_transaction() {
+        let table = table("CREATE TABLE carts (user_id INT, item TEXT, quantity INT, PRIMARY KEY (user_id, item))");
+
+        let transaction = |query| match parse_query(query) {
+            Ok(Query::Transaction(statements)) => statements,
+            result => panic!("expected a transaction, got {:?}", result),
+        };
+
+        let params = vec![
+            (
+                "BEGIN TRANSACTION INSERT INTO carts (user_id, item, quantity) VALUES (1, 'book', 1); UPDATE carts SET quantity = 2 WHERE user_id = 1 AND item = 'pen'; DELETE FROM carts WHERE user_id = 1; COMMIT",
+                Ok(()),
+            ),
+            (
+                "BEGIN TRANSACTION INSERT INTO carts (user_id, item) VALUES (1, 'book'), (2, 'book'); COMMIT",
+                Err(PlanningError::MultiplePartitions),
+            ),
+            (
+                "BEGIN TRANSACTION INSERT INTO carts (user_id, item) VALUES (1, 'book'); DELETE FROM carts WHERE user_id = 2; COMMIT",
+                Err(PlanningError::MultiplePartitions),
+            ),
+            (
+                "BEGIN TRANSACTION INSERT INTO carts (item) VALUES ('book'); COMMIT",
+                Err(PlanningError::PartitionKeyNotRestricted),
+            ),
+            (
+                "BEGIN TRANSACTION UPDATE carts SET quantity = 2 WHERE user_id = 1; COMMIT",
+                Err(PlanningError::PrimaryKeyNotRestricted),
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan_transaction(&transaction(query), &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_plan_update() {
+        let table = table(USER_SESSIONS);
+
+        let params = vec![
+            (
+                "UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND session_id = 2 AND timestamp = 3",
+                Ok(AccessPath::PointRead)
+            ),
+            (
+                "UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND session_id = 2",
+                Err(PlanningError::PrimaryKeyNotRestricted)
+            ),
+            (
+                "UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND session_id = 2 AND timestamp > 3",
+                Err(PlanningError::PrimaryKeyNotRestricted)
+            ),
+            (
+                "UPDATE user_sessions SET timestamp = 3 WHERE device_type = 'PHONE'",
+                Err(PlanningError::NonPrimaryKeyCondition("device_type".to_string()))
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan(query, &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_plan_delete() {
+        let table = table(USER_SESSIONS);
+
+        let params = vec![
+            (
+                "DELETE FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND timestamp = 3",
+                Ok(AccessPath::PointRead)
+            ),
+            (
+                "DELETE FROM user_sessions WHERE user_id = 1 AND session_id = 2",
+                Ok(AccessPath::RangeScan)
+            ),
+            (
+                "DELETE FROM user_sessions WHERE user_id = 1",
+                Err(PlanningError::PartitionKeyNotRestricted)
+            ),
+            (
+                "DELETE FROM user_sessions WHERE user_id = 1 AND session_id = 2 AND device_type = 'PHONE'",
+                Err(PlanningError::NonPrimaryKeyCondition("device_type".to_string()))
+            ),
+        ];
+
+        for (query, expected_result) in params {
+            assert_eq!(plan(query, &table), expected_result, "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_explain_resolves_table() {
+        let mut session = Session::new();
+        let table = session.parse("CREATE TABLE analytics.user_sessions (user_id UUID PRIMARY KEY, device_type TEXT)").unwrap();
+        let Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table)) = table else { panic!("expected a create table query") };
+
+        session.parse("USE analytics").unwrap();
+
+        let params = vec![
+            ("SELECT * FROM user_sessions WHERE user_id = 1", Ok(AccessPath::PointRead)),
+            ("SELECT * FROM analytics.user_sessions WHERE user_id = 1", Ok(AccessPath::PointRead)),
+            ("SELECT * FROM archive.user_sessions WHERE user_id = 1", Err(PlanningError::UnknownTable("archive.user_sessions".to_string()))),
+            ("DELETE FROM devices WHERE user_id = 1", Err(PlanningError::UnknownTable("analytics.devices".to_string()))),
+        ];
+
+        for (query, expected_result) in params {
+            let Ok(Query::DataManipulationQuery(statement)) = session.parse(query) else { panic!("expected a data manipulation query") };
+            assert_eq!(explain(&statement, &table).map(|plan| plan.access_path), expected_result, "{}", query);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod row;
+pub mod output;
+pub mod mutation;
+mod error;
+
+pub use crate::query_planner::plan::{ColumnSpec, ResultSetMetadata};
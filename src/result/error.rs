@@ -0,0 +1,15 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::result::row::RowError;
+
+impl Display for RowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowError::UnknownColumn(column) => write!(f, "the row has no column {}", column),
+            RowError::UnexpectedNull(column) => write!(f, "the column {} is null", column),
+            RowError::TypeMismatch { column, expected } => write!(f, "the value of the column {} is not {}", column, expected),
+        }
+    }
+}
+
+impl Error for RowError {}
@@ -0,0 +1,65 @@
+use std::borrow::Cow;
+use crate::query_parser::query::Value;
+use crate::result::row::Row;
+
+/// The column cqlsh shows the outcome of a conditional write under.
+pub const APPLIED: &str = "[applied]";
+
+/// What an INSERT, UPDATE or DELETE reports back. A write is applied unless it is conditional
+/// and its condition didn't hold, in which case `current` holds the row the condition was
+/// checked against, if there was one.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MutationResult<'a> {
+    pub applied: bool,
+    pub timestamp: i64,
+    pub current: Option<Row<'a>>,
+    pub warnings: Vec<String>,
+}
+
+impl<'a> MutationResult<'a> {
+    pub fn applied(timestamp: i64) -> Self {
+        Self { applied: true, timestamp, current: None, warnings: Vec::new() }
+    }
+
+    pub fn rejected(timestamp: i64, current: Option<Row<'a>>) -> Self {
+        Self { applied: false, timestamp, current, warnings: Vec::new() }
+    }
+
+    #[inline]
+    pub fn warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// The row a conditional write returns: the `[applied]` flag followed by the current values
+    /// of the row when the write wasn't applied.
+    pub fn into_row(self) -> Row<'a> {
+        let mut cells = vec![(Cow::Borrowed(APPLIED), Value::Bool(self.applied))];
+
+        if let Some(current) = self.current {
+            cells.extend(current.into_cells());
+        }
+
+        Row::new(cells)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_into_row() {
+        let result = MutationResult::applied(1_700_000_000_000).warning("the partition is larger than 100MB");
+
+        assert_eq!(result.warnings, vec!["the partition is larger than 100MB".to_string()]);
+        assert_eq!(result.into_row(), Row::new(vec![(APPLIED.into(), Value::Bool(true))]));
+
+        let current = Row::new(vec![("user_id".into(), Value::Integer(1)), ("email".into(), Value::String("a@b.c".into()))]);
+        let row = MutationResult::rejected(1_700_000_000_000, Some(current)).into_row();
+
+        assert_eq!(row.columns().collect::<Vec<_>>(), vec![APPLIED, "user_id", "email"]);
+        assert_eq!(row.get::<bool>(APPLIED), Ok(false));
+        assert_eq!(row.get::<String>("email"), Ok("a@b.c".to_string()));
+    }
+}
@@ -0,0 +1,230 @@
+use std::str::FromStr;
+use crate::query_parser::query::Value;
+use crate::query_parser::timestamp::format_timestamp;
+use crate::result::row::Row;
+use crate::result::ResultSetMetadata;
+
+/// How a result set is printed, following the output modes of cqlsh.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Aligned columns under a header.
+    Table,
+    /// One JSON object per row.
+    Json,
+    Csv,
+    /// One block per row with a line per column, for rows too wide for a table.
+    Vertical,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "vertical" => Ok(OutputFormat::Vertical),
+            _ => Err(format!("unknown output format {}, expected table, json, csv or vertical", format)),
+        }
+    }
+}
+
+/// Renders rows under the columns of the result set, in their order. A column a row doesn't have
+/// is printed as null.
+pub fn render(format: OutputFormat, metadata: &ResultSetMetadata, rows: &[Row]) -> String {
+    let columns: Vec<&str> = metadata.columns.iter().map(|column| column.name.as_ref()).collect();
+
+    match format {
+        OutputFormat::Table => render_table(&columns, rows),
+        OutputFormat::Json => render_json(&columns, rows),
+        OutputFormat::Csv => render_csv(&columns, rows),
+        OutputFormat::Vertical => render_vertical(&columns, rows),
+    }
+}
+
+fn render_table(columns: &[&str], rows: &[Row]) -> String {
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| columns.iter().map(|column| cell(row.value(column))).collect())
+        .collect();
+
+    let widths: Vec<usize> = columns.iter().enumerate()
+        .map(|(index, column)| cells.iter().map(|row| row[index].chars().count()).fold(column.chars().count(), usize::max))
+        .collect();
+
+    let line = |values: Vec<&str>| -> String {
+        let values: Vec<String> = values.iter().zip(&widths).map(|(value, width)| format!(" {:<width$} ", value, width = width)).collect();
+        values.join("|").trim_end().to_string()
+    };
+
+    let mut output = vec![line(columns.to_vec())];
+    output.push(widths.iter().map(|width| "-".repeat(width + 2)).collect::<Vec<_>>().join("+"));
+    output.extend(cells.iter().map(|row| line(row.iter().map(String::as_str).collect())));
+    output.push(String::new());
+    output.push(format!("({} rows)", rows.len()));
+    output.join("\n")
+}
+
+fn render_vertical(columns: &[&str], rows: &[Row]) -> String {
+    let width = columns.iter().map(|column| column.chars().count()).max().unwrap_or_default();
+    let mut output = Vec::new();
+
+    for (index, row) in rows.iter().enumerate() {
+        output.push(format!("@ Row {}", index + 1));
+        output.push(format!("{}+{}", "-".repeat(width + 2), "-".repeat(width + 2)));
+
+        for column in columns {
+            output.push(format!(" {:<width$} | {}", column, cell(row.value(column)), width = width).trim_end().to_string());
+        }
+
+        output.push(String::new());
+    }
+
+    output.push(format!("({} rows)", rows.len()));
+    output.join("\n")
+}
+
+fn render_csv(columns: &[&str], rows: &[Row]) -> String {
+    let mut output = vec![columns.iter().map(|column| csv_field(column)).collect::<Vec<_>>().join(",")];
+
+    for row in rows {
+        let fields: Vec<String> = columns.iter()
+            .map(|column| match row.value(column) {
+                None | Some(Value::Null) => String::new(),
+                value => csv_field(&text(value)),
+            })
+            .collect();
+        output.push(fields.join(","));
+    }
+
+    output.join("\n")
+}
+
+fn render_json(columns: &[&str], rows: &[Row]) -> String {
+    rows.iter()
+        .map(|row| {
+            let fields: Vec<String> = columns.iter()
+                .map(|column| format!("{}: {}", json_string(column), json(row.value(column).unwrap_or(&Value::Null))))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A value as cqlsh shows it: text without quotes and timestamps without the `TIMESTAMP` keyword.
+fn text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "null".to_string(),
+        Some(Value::String(value)) => value.to_string(),
+        Some(Value::Timestamp(value)) => format_timestamp(*value),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// A value on one line of a table, with line breaks escaped so they don't break the layout.
+fn cell(value: Option<&Value>) -> String {
+    text(value).replace('\r', "\\r").replace('\n', "\\n")
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn json(value: &Value) -> String {
+    match value {
+        Value::Integer(value) => value.to_string(),
+        Value::Float(value) if value.is_finite() => value.to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Float(_) | Value::Null => "null".to_string(),
+        Value::String(value) => json_string(value),
+        Value::Uuid(_) | Value::Timestamp(_) => json_string(&text(Some(value))),
+        Value::UserType(fields) => {
+            let fields: Vec<String> = fields.iter().map(|(name, value)| format!("{}: {}", json_string(name), json(value))).collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        Value::List(values) | Value::Set(values) => {
+            let values: Vec<String> = values.iter().map(json).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Map(entries) => {
+            let entries: Vec<String> = entries.iter().map(|(key, value)| format!("{}: {}", json_string(&text(Some(key))), json(value))).collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+
+    for char in value.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            char if char.is_control() => output.push_str(&format!("\\u{:04x}", char as u32)),
+            char => output.push(char),
+        }
+    }
+
+    output.push('"');
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::query::ColumnType;
+    use crate::result::ColumnSpec;
+
+    fn users() -> Vec<Row<'static>> {
+        vec![
+            Row::new(vec![("user_id".into(), Value::Integer(1)), ("name".into(), Value::String("alice".into())), ("bio".into(), Value::Null)]),
+            Row::new(vec![("user_id".into(), Value::Integer(20)), ("name".into(), Value::String("bob, \"jr\"".into())), ("bio".into(), Value::String("line\nbreak".into()))]),
+        ]
+    }
+
+    #[test]
+    fn test_render() {
+        let column = |name: &'static str, column_type| ColumnSpec { name: name.into(), column_type: Some(column_type), table: Some("users") };
+        let metadata = ResultSetMetadata { columns: vec![column("user_id", &ColumnType::Int), column("name", &ColumnType::Text), column("bio", &ColumnType::Text)] };
+        let rows = users();
+
+        let params = vec![
+            (
+                OutputFormat::Table,
+                " user_id | name      | bio\n---------+-----------+-------------\n 1       | alice     | null\n 20      | bob, \"jr\" | line\\nbreak\n\n(2 rows)",
+            ),
+            (
+                OutputFormat::Json,
+                "{\"user_id\": 1, \"name\": \"alice\", \"bio\": null}\n{\"user_id\": 20, \"name\": \"bob, \\\"jr\\\"\", \"bio\": \"line\\nbreak\"}",
+            ),
+            (
+                OutputFormat::Csv,
+                "user_id,name,bio\n1,alice,\n20,\"bob, \"\"jr\"\"\",\"line\nbreak\"",
+            ),
+            (
+                OutputFormat::Vertical,
+                "@ Row 1\n---------+---------\n user_id | 1\n name    | alice\n bio     | null\n\n@ Row 2\n---------+---------\n user_id | 20\n name    | bob, \"jr\"\n bio     | line\\nbreak\n\n(2 rows)",
+            ),
+        ];
+
+        for (format, expected_output) in params {
+            assert_eq!(render(format, &metadata, &rows), expected_output, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!("JSON".parse(), Ok(OutputFormat::Json));
+        assert_eq!("vertical".parse(), Ok(OutputFormat::Vertical));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
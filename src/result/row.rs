@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+use crate::query_parser::query::Value;
+
+/// A row returned by a query, with its values in the order of the result columns.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Row<'a> {
+    cells: Vec<(Cow<'a, str>, Value<'a>)>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum RowError {
+    UnknownColumn(String),
+    UnexpectedNull(String),
+    TypeMismatch { column: String, expected: &'static str },
+}
+
+/// A Rust type a column value converts to. Conversions are checked, so an `INT` doesn't
+/// silently read as a `bool` and a `BIGINT` out of the `i32` range doesn't wrap.
+pub trait FromValue<'a>: Sized {
+    const EXPECTED: &'static str;
+
+    fn from_value(value: &Value<'a>) -> Option<Self>;
+}
+
+impl<'a> Row<'a> {
+    pub fn new(cells: Vec<(Cow<'a, str>, Value<'a>)>) -> Self {
+        Self { cells }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.cells.iter().map(|(column, _)| column.as_ref())
+    }
+
+    pub(crate) fn into_cells(self) -> Vec<(Cow<'a, str>, Value<'a>)> {
+        self.cells
+    }
+
+    pub fn value(&self, column: &str) -> Option<&Value<'a>> {
+        self.cells.iter().find(|(name, _)| name == column).map(|(_, value)| value)
+    }
+
+    /// Reads a column as `T`, for example `row.get::<i64>("quantity")`. Nullable columns
+    /// are read as `Option<T>`.
+    pub fn get<T: FromValue<'a>>(&self, column: &str) -> Result<T, RowError> {
+        let value = self.value(column).ok_or_else(|| RowError::UnknownColumn(column.to_string()))?;
+
+        match T::from_value(value) {
+            Some(value) => Ok(value),
+            None if *value == Value::Null => Err(RowError::UnexpectedNull(column.to_string())),
+            None => Err(RowError::TypeMismatch { column: column.to_string(), expected: T::EXPECTED }),
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for i64 {
+    const EXPECTED: &'static str = "an integer";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Integer(value) | Value::Timestamp(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for i32 {
+    const EXPECTED: &'static str = "a 32-bit integer";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Integer(value) => i32::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for f64 {
+    const EXPECTED: &'static str = "a floating point number";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for bool {
+    const EXPECTED: &'static str = "a boolean";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// UUIDs are read as their 128-bit value.
+impl<'a> FromValue<'a> for u128 {
+    const EXPECTED: &'static str = "a UUID";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Uuid(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for Cow<'a, str> {
+    const EXPECTED: &'static str = "text";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::String(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FromValue<'a> for String {
+    const EXPECTED: &'static str = "text";
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        Cow::from_value(value).map(Cow::into_owned)
+    }
+}
+
+impl<'a, T: FromValue<'a>> FromValue<'a> for Option<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_value(value: &Value<'a>) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn order() -> Row<'static> {
+        Row::new(vec![
+            ("order_id".into(), Value::Uuid(42)),
+            ("quantity".into(), Value::Integer(3_000_000_000)),
+            ("price".into(), Value::Float(9.5)),
+            ("note".into(), Value::Null),
+            ("customer".into(), Value::String("alice".into())),
+        ])
+    }
+
+    #[test]
+    fn test_get() {
+        let row = order();
+
+        assert_eq!(row.get::<u128>("order_id"), Ok(42));
+        assert_eq!(row.get::<i64>("quantity"), Ok(3_000_000_000));
+        assert_eq!(row.get::<f64>("price"), Ok(9.5));
+        assert_eq!(row.get::<String>("customer"), Ok("alice".to_string()));
+        assert_eq!(row.get::<Option<String>>("note"), Ok(None));
+        assert_eq!(row.get::<Option<String>>("customer"), Ok(Some("alice".to_string())));
+        assert_eq!(row.columns().collect::<Vec<_>>(), vec!["order_id", "quantity", "price", "note", "customer"]);
+    }
+
+    #[test]
+    fn test_get_errors() {
+        let row = order();
+
+        assert_eq!(row.get::<i64>("total"), Err(RowError::UnknownColumn("total".to_string())));
+        assert_eq!(row.get::<String>("note"), Err(RowError::UnexpectedNull("note".to_string())));
+        assert_eq!(row.get::<i32>("quantity"), Err(RowError::TypeMismatch { column: "quantity".to_string(), expected: "a 32-bit integer" }));
+        assert_eq!(row.get::<bool>("price"), Err(RowError::TypeMismatch { column: "price".to_string(), expected: "a boolean" }));
+    }
+}
@@ -0,0 +1,398 @@
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+use crate::query_parser::binder::bind;
+use crate::query_parser::dialect::{Dialect, Lenient};
+use crate::query_parser::parser::{parse_query_with_dialect, relocate, script_statements};
+use crate::query_parser::query::{AdminQuery, BulkQuery, CreateTableQuery, DataDefinitionQuery, DataManipulationQuery, MetaQuery, Query, QueryParsingError, SessionQuery, Value};
+use crate::query_planner::planner::{bind_metadata, explain, find_table, result_metadata};
+use crate::error::Error;
+use crate::result::ResultSetMetadata;
+pub use crate::query_planner::plan::{BindMarkerSpec, ClusteringSlice, QueryPlan, WritePath};
+
+/// Carries state between statements of one client. `USE` switches the current keyspace and
+/// statements that don't name a keyspace are qualified with it. `TRACING ON` makes the session
+/// time each stage of the following statements. The dialect decides which statements the
+/// session accepts.
+#[derive(Debug)]
+pub struct Session {
+    keyspace: Option<String>,
+    tracing: bool,
+    last_trace: Option<Trace>,
+    dialect: Box<dyn Dialect>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::with_dialect(Lenient)
+    }
+}
+
+/// A statement parsed ahead of its execution, with what a client needs to bind and read it: its
+/// bind markers in the order they are bound and, for a SELECT, the columns it returns.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PreparedStatement<'a> {
+    pub query: Query<'a>,
+    pub bind_markers: Vec<BindMarkerSpec<'a>>,
+    pub result_metadata: Option<ResultSetMetadata<'a>>,
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TraceEvent {
+    pub stage: &'static str,
+    pub elapsed: Duration,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dialect(dialect: impl Dialect + 'static) -> Self {
+        Self { keyspace: None, tracing: false, last_trace: None, dialect: Box::new(dialect) }
+    }
+
+    pub fn keyspace(&self) -> Option<&str> {
+        self.keyspace.as_deref()
+    }
+
+    /// Never panics on arbitrary input; malformed statements are reported as errors.
+    pub fn parse<'a>(&mut self, source: &'a str) -> Result<Query<'a>, QueryParsingError> {
+        self.parse_with_params(source, &[])
+    }
+
+    /// Returns the trace of the last statement parsed, prepared or explained while tracing was on.
+    /// Parsing records a parse and a bind stage, preparing a parse and a plan stage and explaining
+    /// a plan stage.
+    pub fn last_trace(&self) -> Option<&Trace> {
+        self.last_trace.as_ref()
+    }
+
+    /// Prepares a statement against a snapshot of the schema without binding its markers. Reads
+    /// and writes must target one of the tables of the schema, and the statements of a transaction
+    /// number their markers one after another. Other statements have no markers to describe. As
+    /// with any statement, a target without a keyspace is qualified with the current one, but
+    /// preparing a `USE` doesn't switch it.
+    pub fn prepare<'a>(&mut self, source: &'a str, schema: &'a [CreateTableQuery<'a>]) -> Result<PreparedStatement<'a>, Error> {
+        let mut trace = Trace::default();
+
+        let started_at = Instant::now();
+        let mut query = parse_query_with_dialect(source, self.dialect.as_ref())?;
+        trace.events.push(TraceEvent { stage: "parse", elapsed: started_at.elapsed() });
+
+        self.qualify(&mut query);
+
+        let started_at = Instant::now();
+
+        let mut bind_markers = Vec::new();
+        let mut metadata = None;
+
+        match &query {
+            Query::DataManipulationQuery(statement) | Query::Explain(statement) => {
+                let table = find_table(statement, schema)?;
+                bind_markers = bind_metadata(statement, table)?;
+
+                if let Query::DataManipulationQuery(DataManipulationQuery::Select(select)) = &query {
+                    metadata = Some(result_metadata(select, table)?);
+                }
+            }
+            Query::Transaction(statements) => {
+                for statement in statements {
+                    let table = find_table(statement, schema)?;
+
+                    for mut marker in bind_metadata(statement, table)? {
+                        marker.position = bind_markers.len();
+                        bind_markers.push(marker);
+                    }
+                }
+            }
+            Query::DataDefinitionQuery(_) | Query::SessionQuery(_) | Query::BulkQuery(_) | Query::MetaQuery(_) | Query::AdminQuery(_) => {}
+        }
+
+        trace.events.push(TraceEvent { stage: "plan", elapsed: started_at.elapsed() });
+
+        if self.tracing {
+            self.last_trace = Some(trace);
+        }
+
+        Ok(PreparedStatement { query, bind_markers, result_metadata: metadata })
+    }
+
+    /// Plans a read or write against a snapshot of the schema, as `EXPLAIN` does. The statement
+    /// must target one of the tables of the schema; pass the inner statement of a parsed `EXPLAIN`.
+    pub fn explain<'a>(&mut self, statement: &'a DataManipulationQuery<'a>, schema: &[CreateTableQuery]) -> Result<QueryPlan<'a>, Error> {
+        let started_at = Instant::now();
+        let table = find_table(statement, schema)?;
+        let plan = explain(statement, table)?;
+
+        if self.tracing {
+            self.last_trace = Some(Trace { events: vec![TraceEvent { stage: "plan", elapsed: started_at.elapsed() }] });
+        }
+
+        Ok(plan)
+    }
+
+    /// Parses the statements of a script one by one, so a `USE` applies to the statements after it
+    /// and a malformed statement doesn't prevent parsing the rest.
+    pub fn parse_script<'a>(&mut self, source: &'a str) -> Vec<Result<Query<'a>, QueryParsingError>> {
+        script_statements(source, self.dialect.allows_string_escapes())
+            .into_iter()
+            .map(|(offset, statement)| self.parse(statement).map_err(|error| relocate(error, source, offset)))
+            .collect()
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(keyspace = self.keyspace.as_deref())))]
+    pub fn parse_with_params<'a>(&mut self, source: &'a str, params: &[Value<'a>]) -> Result<Query<'a>, QueryParsingError> {
+        let mut trace = Trace::default();
+
+        let started_at = Instant::now();
+        let mut query = parse_query_with_dialect(source, self.dialect.as_ref())?;
+        trace.events.push(TraceEvent { stage: "parse", elapsed: started_at.elapsed() });
+
+        let started_at = Instant::now();
+        bind(&mut query, params)?;
+        trace.events.push(TraceEvent { stage: "bind", elapsed: started_at.elapsed() });
+
+        if self.tracing {
+            self.last_trace = Some(trace);
+        }
+
+        if let Query::SessionQuery(SessionQuery::Use(statement)) = &query {
+            self.keyspace = Some(statement.keyspace.to_string());
+        } else if let Query::SessionQuery(SessionQuery::Tracing(statement)) = &query {
+            self.tracing = statement.enabled;
+        } else {
+            self.qualify(&mut query);
+        }
+
+        Ok(query)
+    }
+
+    fn qualify(&self, query: &mut Query) {
+        if let Some(keyspace) = &self.keyspace {
+            for slot in keyspaces_of(query) {
+                slot.get_or_insert_with(|| Cow::Owned(keyspace.clone()));
+            }
+        }
+    }
+}
+
+fn keyspaces_of<'a, 'b>(query: &'a mut Query<'b>) -> Vec<&'a mut Option<Cow<'b, str>>> {
+    let keyspace = match query {
+        Query::DataManipulationQuery(statement) | Query::Explain(statement) => keyspace_of(statement),
+        Query::Transaction(statements) => return statements.iter_mut().map(keyspace_of).collect(),
+        Query::DataDefinitionQuery(statement) => match statement {
+            DataDefinitionQuery::CreateTable(statement) => &mut statement.keyspace,
+            DataDefinitionQuery::AlterTable(statement) => &mut statement.keyspace,
+            DataDefinitionQuery::DropTable(statement) => &mut statement.keyspace,
+            DataDefinitionQuery::CreateFunction(statement) => &mut statement.keyspace,
+            DataDefinitionQuery::CreateType(statement) => &mut statement.keyspace,
+            DataDefinitionQuery::CreateMaterializedView(statement) => return vec![&mut statement.keyspace, &mut statement.base_keyspace],
+            DataDefinitionQuery::CreateKeyspace(_) => return Vec::new(),
+        },
+        Query::BulkQuery(BulkQuery::CopyFrom(statement) | BulkQuery::CopyTo(statement)) => &mut statement.keyspace,
+        Query::MetaQuery(statement) => match statement {
+            MetaQuery::ShowTables(statement) => &mut statement.keyspace,
+            MetaQuery::DescribeTable(statement) => &mut statement.keyspace,
+            MetaQuery::DescribeKeyspace(_) => return Vec::new(),
+        },
+        Query::AdminQuery(AdminQuery::Compact(statement) | AdminQuery::Flush(statement)) => &mut statement.keyspace,
+        Query::SessionQuery(_) => return Vec::new(),
+    };
+
+    vec![keyspace]
+}
+
+fn keyspace_of<'a, 'b>(statement: &'a mut DataManipulationQuery<'b>) -> &'a mut Option<Cow<'b, str>> {
+    match statement {
+        DataManipulationQuery::Select(statement) => &mut statement.keyspace,
+        DataManipulationQuery::Insert(statement) => &mut statement.keyspace,
+        DataManipulationQuery::Update(statement) => &mut statement.keyspace,
+        DataManipulationQuery::Delete(statement) => &mut statement.keyspace,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query_parser::dialect::Strict;
+    use crate::query_parser::builder::{ConditionBuilder, SelectQueryBuilder};
+    use crate::query_parser::query::{ColumnType, Operator};
+    use crate::query_planner::plan::{AccessPath, PlanningError};
+    use crate::result::ColumnSpec;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_use_sets_default_keyspace() {
+        let mut session = Session::new();
+
+        assert_eq!(session.parse("SELECT * FROM user_sessions"), Ok(SelectQueryBuilder::new().table("user_sessions".to_string()).build().unwrap()));
+
+        session.parse("USE analytics").unwrap();
+        assert_eq!(session.keyspace(), Some("analytics"));
+
+        let expected_result = SelectQueryBuilder::new()
+            .keyspace("analytics".to_string())
+            .table("user_sessions".to_string())
+            .condition(ConditionBuilder::new()
+                .column("user_id".to_string())
+                .operator(Operator::Equals)
+                .value(Value::Integer(1))
+                .build().unwrap())
+            .build().unwrap();
+
+        assert_eq!(session.parse_with_params("SELECT * FROM user_sessions WHERE user_id = ?", &[Value::Integer(1)]), Ok(expected_result));
+
+        let expected_result = SelectQueryBuilder::new().keyspace("archive".to_string()).table("user_sessions".to_string()).build().unwrap();
+        assert_eq!(session.parse("SELECT * FROM archive.user_sessions"), Ok(expected_result));
+
+        assert!(session.parse("USE").is_err());
+        assert_eq!(session.keyspace(), Some("analytics"));
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let mut session = Session::new();
+        let results = session.parse_script("USE analytics; SELECT * FROM; SELECT * FROM user_sessions;");
+
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_err());
+        assert_eq!(results[2], Ok(SelectQueryBuilder::new().keyspace("analytics".to_string()).table("user_sessions".to_string()).build().unwrap()));
+
+        match &results[1] {
+            Err(QueryParsingError::QuerySyntaxError(error)) => assert_eq!(error.column, 29),
+            result => panic!("expected a syntax error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_tracing() {
+        let mut session = Session::new();
+
+        session.parse("SELECT * FROM user_sessions").unwrap();
+        assert_eq!(session.last_trace(), None);
+
+        session.parse("TRACING ON").unwrap();
+        session.parse("SELECT * FROM user_sessions").unwrap();
+
+        let stages = |session: &Session| session.last_trace().unwrap().events.iter().map(|event| event.stage).collect::<Vec<_>>();
+        assert_eq!(stages(&session), vec!["parse", "bind"]);
+
+        let Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) = session.parse("CREATE TABLE user_sessions (user_id INT PRIMARY KEY, device_type TEXT)") else { panic!("expected a create table query") };
+        let schema = vec![table];
+
+        session.prepare("SELECT * FROM user_sessions WHERE user_id = ?", &schema).unwrap();
+        assert_eq!(stages(&session), vec!["parse", "plan"]);
+
+        let Ok(Query::Explain(statement)) = session.parse("EXPLAIN SELECT * FROM user_sessions WHERE user_id = 1") else { panic!("expected an explain query") };
+        session.explain(&statement, &schema).unwrap();
+        assert_eq!(stages(&session), vec!["plan"]);
+    }
+
+    #[test]
+    fn test_strict_dialect() {
+        let mut session = Session::with_dialect(Strict);
+
+        session.parse("USE analytics").unwrap();
+        assert!(session.parse("SELECT * FROM user_sessions").is_ok());
+        assert_eq!(session.parse("TRACING ON"), Err(QueryParsingError::UnsupportedRequest("TRACING".to_string())));
+        assert!(Session::new().parse("TRACING ON").is_ok());
+    }
+
+    #[test]
+    fn test_prepare() {
+        let mut session = Session::new();
+        let Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) = session.parse("CREATE TABLE analytics.user_sessions (user_id INT, timestamp TIMESTAMP, device_type TEXT, PRIMARY KEY ((user_id), timestamp))") else { panic!("expected a create table query") };
+        let schema = vec![table];
+
+        session.parse("USE analytics").unwrap();
+
+        let statement = session.prepare("SELECT device_type AS device FROM user_sessions WHERE user_id = ? AND timestamp > :since", &schema).unwrap();
+
+        assert_eq!(statement.bind_markers, vec![
+            BindMarkerSpec { position: 0, name: "user_id".into(), column_type: Some(&ColumnType::Int) },
+            BindMarkerSpec { position: 1, name: "since".into(), column_type: Some(&ColumnType::Timestamp) },
+        ]);
+        assert_eq!(statement.result_metadata, Some(ResultSetMetadata {
+            columns: vec![ColumnSpec { name: "device".into(), column_type: Some(&ColumnType::Text), table: Some("user_sessions") }],
+        }));
+
+        let statement = session.prepare("BEGIN TRANSACTION INSERT INTO user_sessions (user_id, timestamp) VALUES (?, ?); DELETE FROM user_sessions WHERE user_id = ? AND timestamp = ?; COMMIT", &schema).unwrap();
+        let positions: Vec<usize> = statement.bind_markers.iter().map(|marker| marker.position).collect();
+
+        assert_eq!(positions, vec![0, 1, 2, 3]);
+        assert_eq!(statement.result_metadata, None);
+
+        assert!(matches!(session.prepare("SELECT * FROM archive.user_sessions", &schema), Err(Error::Planning(PlanningError::TableNotInSchema(table))) if table == "archive.user_sessions"));
+        assert!(matches!(session.prepare("SELECT * FROM user_sessions WHERE missing = ?", &schema), Err(Error::Planning(PlanningError::UnknownColumn(_)))));
+        assert!(matches!(session.prepare("SELEC * FROM user_sessions", &schema), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut session = Session::new();
+        let Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) = session.parse("CREATE TABLE analytics.user_sessions (user_id INT, timestamp TIMESTAMP, device_type TEXT, PRIMARY KEY ((user_id), timestamp))") else { panic!("expected a create table query") };
+        let schema = vec![table];
+
+        session.parse("USE analytics").unwrap();
+
+        let Ok(Query::Explain(statement)) = session.parse("EXPLAIN SELECT * FROM user_sessions WHERE user_id = 1 AND device_type = 'PHONE' ALLOW FILTERING") else { panic!("expected an explain query") };
+        let plan = session.explain(&statement, &schema).unwrap();
+
+        assert_eq!(plan.access_path, AccessPath::RangeScan);
+        assert_eq!(plan.pushed_down_conditions.iter().map(|condition| condition.to_string()).collect::<Vec<_>>(), vec!["user_id = 1"]);
+        assert_eq!(plan.filter_conditions.iter().map(|condition| condition.to_string()).collect::<Vec<_>>(), vec!["device_type = 'PHONE'"]);
+        assert_eq!(plan.estimated_rows, None);
+
+        let Ok(Query::Explain(statement)) = session.parse("EXPLAIN SELECT * FROM user_sessions WHERE user_id = 1 AND timestamp >= 10 AND timestamp < 20") else { panic!("expected an explain query") };
+        let plan = session.explain(&statement, &schema).unwrap();
+
+        assert_eq!(plan.clustering_slice, Some(ClusteringSlice {
+            prefix: vec![],
+            start: Bound::Included(vec![&Value::Integer(10)]),
+            end: Bound::Excluded(vec![&Value::Integer(20)]),
+        }));
+
+        assert_eq!(plan.write_path, None);
+
+        let Ok(Query::Explain(statement)) = session.parse("EXPLAIN SELECT device_type FROM user_sessions WHERE user_id = 1") else { panic!("expected an explain query") };
+        assert_eq!(session.explain(&statement, &schema).unwrap().read_columns, Some(vec![2]));
+
+        let Ok(Query::Explain(statement)) = session.parse("EXPLAIN UPDATE user_sessions SET device_type = 'PHONE' WHERE user_id = 1 AND timestamp = 0 IF EXISTS") else { panic!("expected an explain query") };
+        assert_eq!(session.explain(&statement, &schema).unwrap().write_path, Some(WritePath::CompareAndSet));
+
+        let Ok(Query::DataManipulationQuery(statement)) = session.parse("SELECT * FROM archive.user_sessions") else { panic!("expected a select query") };
+        assert!(matches!(session.explain(&statement, &schema), Err(Error::Planning(PlanningError::TableNotInSchema(_)))));
+    }
+
+    #[test]
+    fn test_prepare_function_types() {
+        let Ok(Query::DataDefinitionQuery(DataDefinitionQuery::CreateTable(table))) = Session::new().parse("CREATE TABLE sensors (sensor_id INT, reading_time TIMESTAMP, temperature DOUBLE, PRIMARY KEY (sensor_id, reading_time))") else { panic!("expected a create table query") };
+        let schema = vec![table];
+
+        let params = vec![
+            ("SELECT writetime(temperature) FROM sensors WHERE sensor_id = 1", Ok(("writetime(temperature)", &ColumnType::Long))),
+            ("SELECT ttl(temperature) AS remaining FROM sensors WHERE sensor_id = 1", Ok(("remaining", &ColumnType::Int))),
+            ("SELECT ttl(reading_time) FROM sensors WHERE sensor_id = 1", Err(PlanningError::InvalidCellFunction("ttl".to_string()))),
+            ("SELECT time_bucket('5 minutes', reading_time) AS bucket FROM sensors WHERE sensor_id = 1", Ok(("bucket", &ColumnType::Timestamp))),
+            ("SELECT time_bucket('5 minutes', temperature) FROM sensors WHERE sensor_id = 1", Err(PlanningError::InvalidTimeBucket("the column temperature isn't a timestamp".to_string()))),
+        ];
+
+        for (query, expected_result) in params {
+            let result = match Session::new().prepare(query, &schema) {
+                Ok(statement) => {
+                    let column = &statement.result_metadata.unwrap().columns[0];
+                    Ok((column.name.to_string(), column.column_type.unwrap()))
+                }
+                Err(Error::Planning(error)) => Err(error),
+                Err(error) => panic!("expected a planning error, got {:?}", error),
+            };
+
+            assert_eq!(result, expected_result.map(|(name, column_type)| (name.to_string(), column_type)), "{}", query);
+        }
+    }
+}
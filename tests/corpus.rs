@@ -0,0 +1,53 @@
+//! Golden-file tests of the grammar. Every `tests/corpus/*.cql` script is parsed statement by
+//! statement and the dump of the results is compared with the `.snap` file next to it.
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the snapshots after an intended grammar change,
+//! then review the diff.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uranus::session::Session;
+
+fn dump(script: &str) -> String {
+    let mut session = Session::new();
+    let mut output = String::new();
+
+    for (index, result) in session.parse_script(script).iter().enumerate() {
+        match result {
+            Ok(query) => writeln!(output, "-- statement {}\n{:#?}\n", index + 1, query).unwrap(),
+            Err(error) => writeln!(output, "-- statement {}\nerror: {}\n", index + 1, error).unwrap(),
+        }
+    }
+
+    output
+}
+
+fn scripts(directory: &Path) -> Vec<PathBuf> {
+    let mut scripts: Vec<PathBuf> = fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "cql"))
+        .collect();
+
+    scripts.sort();
+    scripts
+}
+
+#[test]
+fn test_corpus() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut mismatches = Vec::new();
+
+    for script in scripts(&directory) {
+        let actual = dump(&fs::read_to_string(&script).unwrap());
+        let snapshot = script.with_extension("snap");
+
+        if update {
+            fs::write(&snapshot, &actual).unwrap();
+        } else if fs::read_to_string(&snapshot).ok().as_deref() != Some(actual.as_str()) {
+            mismatches.push(script.display().to_string());
+        }
+    }
+
+    assert!(mismatches.is_empty(), "the snapshots of {:?} are out of date, rerun with UPDATE_SNAPSHOTS=1 and review the diff", mismatches);
+}